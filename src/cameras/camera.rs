@@ -1,9 +1,11 @@
 use super::super::math::matrix4::Matrix4;
+use super::super::core::layers::Layers;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Camera {
 	pub matrix_world_inverse: Matrix4,
 	pub projection_matrix: Matrix4,
+	pub layers: Layers,
 }
 
 impl Camera {
@@ -11,6 +13,7 @@ impl Camera {
 		Camera {
 			matrix_world_inverse: Matrix4::new(),
 			projection_matrix: Matrix4::new(),
+			layers: Layers::new(),
 		}
 	}
 
@@ -21,4 +24,12 @@ impl Camera {
 	pub fn get_projection_matrix(&self) -> &Matrix4 {
 		&self.projection_matrix
 	}
+
+	pub fn get_layers(&self) -> &Layers {
+		&self.layers
+	}
+
+	pub fn get_layers_mut(&mut self) -> &mut Layers {
+		&mut self.layers
+	}
 }
\ No newline at end of file
@@ -0,0 +1,834 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use super::object3d::{Object3D, HasObject3D};
+use super::scene_node::SceneNode;
+use super::super::math::matrix4::Matrix4;
+use super::super::math::vector3::Vector3;
+#[cfg(feature = "rayon")]
+use super::super::math::quaternion::Quaternion;
+#[cfg(feature = "rayon")]
+use super::transform;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "rayon")]
+use self::rayon::prelude::*;
+
+// A scene graph that owns its nodes, as an alternative to linking objects with
+// Rc<RefCell<HasObject3D>>. The hierarchy is kept as ids next to the nodes instead of
+// inside them, so parents and children can be reached without runtime borrows.
+//
+// Nodes are SceneNodes rather than Object3Ds, which hold Rc links, so a SceneGraph is Send
+// and can be built on a worker thread. Objects and whole Rc hierarchies are copied in and
+// out with insert_object, insert_tree, to_object3d and to_tree.
+
+// A handle to a node. The generation makes handles to removed nodes stale instead of letting
+// them alias whatever reuses the slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId {
+	index: u32,
+	generation: u32,
+}
+
+impl NodeId {
+	pub fn get_index(&self) -> usize {
+		self.index as usize
+	}
+
+	pub fn get_generation(&self) -> u32 {
+		self.generation
+	}
+}
+
+struct Node {
+	data: SceneNode,
+	parent: Option<NodeId>,
+	children: Vec<NodeId>,
+}
+
+struct Slot {
+	generation: u32,
+	node: Option<Node>,
+}
+
+pub struct SceneGraph {
+	slots: Vec<Slot>,
+	free: Vec<u32>,
+	roots: Vec<NodeId>,
+	len: usize,
+}
+
+impl SceneGraph {
+	pub fn new() -> SceneGraph {
+		SceneGraph {
+			slots: vec![],
+			free: vec![],
+			roots: vec![],
+			len: 0,
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	pub fn contains(&self, id: NodeId) -> bool {
+		self.node(id).is_some()
+	}
+
+	// Adds a node as a new root.
+	pub fn insert(&mut self, data: SceneNode) -> NodeId {
+		let node = Node {
+			data,
+			parent: None,
+			children: vec![],
+		};
+
+		let id = match self.free.pop() {
+			Some(index) => {
+				let slot = &mut self.slots[ index as usize ];
+				slot.node = Some(node);
+				NodeId {
+					index,
+					generation: slot.generation,
+				}
+			},
+			None => {
+				self.slots.push(Slot {
+					generation: 0,
+					node: Some(node),
+				});
+				NodeId {
+					index: ( self.slots.len() - 1 ) as u32,
+					generation: 0,
+				}
+			},
+		};
+
+		self.roots.push(id);
+		self.len += 1;
+
+		id
+	}
+
+	// Adds a copy of the object as a new root, see SceneNode::from_object3d.
+	pub fn insert_object(&mut self, object: &dyn HasObject3D) -> NodeId {
+		self.insert(SceneNode::from_object3d(object))
+	}
+
+	// Copies the object and its descendants in, keeping the order of the children. The new
+	// root is returned.
+	pub fn insert_tree(&mut self, object: &Rc<RefCell<dyn HasObject3D>>) -> NodeId {
+		let object = object.borrow();
+		let id = self.insert_object(&*object);
+
+		for child in object.get_object3d().get_children() {
+			let child = self.insert_tree(child);
+			self.add(id, child);
+		}
+
+		id
+	}
+
+	pub fn to_object3d(&self, id: NodeId) -> Option<Object3D> {
+		self.get(id).map(|node| node.to_object3d())
+	}
+
+	// The node and its descendants as new linked Object3Ds.
+	pub fn to_tree(&self, id: NodeId) -> Option<Rc<RefCell<dyn HasObject3D>>> {
+		let object: Rc<RefCell<dyn HasObject3D>> = Rc::new(RefCell::new(self.to_object3d(id)?));
+
+		for child in self.get_children(id) {
+			if let Some(child) = self.to_tree(*child) {
+				Object3D::add(&object, &child);
+			}
+		}
+
+		Some(object)
+	}
+
+	// Removes the node together with its descendants and returns it.
+	pub fn remove(&mut self, id: NodeId) -> Option<SceneNode> {
+		if !self.contains(id) {
+			return None;
+		}
+
+		self.remove_from_parent(id);
+		self.roots.retain(|root| *root != id);
+
+		let mut stack = vec![ id ];
+		let mut object = None;
+
+		while let Some(current) = stack.pop() {
+			let slot = &mut self.slots[ current.index as usize ];
+			let node = slot.node.take().unwrap();
+
+			slot.generation = slot.generation.wrapping_add(1);
+			self.free.push(current.index);
+			self.len -= 1;
+
+			stack.extend(node.children);
+
+			if current == id {
+				object = Some(node.data);
+			}
+		}
+
+		object
+	}
+
+	// One pass over the slots. The generations are bumped as in remove, so handles from
+	// before don't alias the nodes inserted after.
+	pub fn clear(&mut self) {
+		self.free.clear();
+
+		for (index, slot) in self.slots.iter_mut().enumerate() {
+			if slot.node.take().is_some() {
+				slot.generation = slot.generation.wrapping_add(1);
+			}
+
+			self.free.push(index as u32);
+		}
+
+		self.roots.clear();
+		self.len = 0;
+	}
+
+	pub fn get(&self, id: NodeId) -> Option<&SceneNode> {
+		self.node(id).map(|node| &node.data)
+	}
+
+	pub fn get_mut(&mut self, id: NodeId) -> Option<&mut SceneNode> {
+		self.node_mut(id).map(|node| &mut node.data)
+	}
+
+	pub fn get_parent(&self, id: NodeId) -> Option<NodeId> {
+		self.node(id).and_then(|node| node.parent)
+	}
+
+	pub fn get_children(&self, id: NodeId) -> &[NodeId] {
+		match self.node(id) {
+			Some(node) => &node.children,
+			None => &[],
+		}
+	}
+
+	pub fn get_roots(&self) -> &[NodeId] {
+		&self.roots
+	}
+
+	// Makes child the last child of parent, detaching it from its previous parent. Fails if
+	// either id is stale or if parent is child itself or one of its descendants.
+	pub fn add(&mut self, parent: NodeId, child: NodeId) -> bool {
+		if !self.contains(parent) || !self.contains(child) {
+			return false;
+		}
+
+		let mut ancestor = Some(parent);
+
+		while let Some(current) = ancestor {
+			if current == child {
+				return false;
+			}
+			ancestor = self.get_parent(current);
+		}
+
+		self.detach(child);
+
+		self.node_mut(child).unwrap().parent = Some(parent);
+		self.node_mut(parent).unwrap().children.push(child);
+
+		true
+	}
+
+	// Turns the node back into a root. Returns false if it wasn't attached to anything.
+	pub fn remove_from_parent(&mut self, id: NodeId) -> bool {
+		if self.get_parent(id).is_none() {
+			return false;
+		}
+
+		self.detach(id);
+		self.roots.push(id);
+
+		true
+	}
+
+	// Unlinks the node from its parent, or from the roots, keeping the order of its siblings.
+	fn detach(&mut self, id: NodeId) {
+		// the world matrix follows the new parent on the next update
+		self.node_mut(id).unwrap().data.set_matrix_world_needs_update(true);
+
+		match self.get_parent(id) {
+			Some(parent) => {
+				self.node_mut(parent).unwrap().children.retain(|c| *c != id);
+				self.node_mut(id).unwrap().parent = None;
+			},
+			None => self.roots.retain(|root| *root != id),
+		}
+	}
+
+	// All nodes, in slot order.
+	pub fn iter(&self) -> impl Iterator<Item = (NodeId, &SceneNode)> {
+		self.slots.iter().enumerate().filter_map(|(index, slot)| {
+			slot.node.as_ref().map(|node| {
+				(NodeId { index: index as u32, generation: slot.generation }, &node.data)
+			})
+		})
+	}
+
+	pub fn iter_mut(&mut self) -> impl Iterator<Item = (NodeId, &mut SceneNode)> {
+		self.slots.iter_mut().enumerate().filter_map(|(index, slot)| {
+			let generation = slot.generation;
+			slot.node.as_mut().map(|node| {
+				(NodeId { index: index as u32, generation }, &mut node.data)
+			})
+		})
+	}
+
+	// Depth first from id, parents before their children.
+	pub fn traverse<F: FnMut(NodeId, &SceneNode)>(&self, id: NodeId, callback: &mut F) {
+		let mut stack = vec![ id ];
+
+		while let Some(current) = stack.pop() {
+			if let Some(node) = self.node(current) {
+				callback(current, &node.data);
+				stack.extend(node.children.iter().rev());
+			}
+		}
+	}
+
+	pub fn traverse_mut<F: FnMut(NodeId, &mut SceneNode)>(&mut self, id: NodeId, callback: &mut F) {
+		let mut stack = vec![ id ];
+
+		while let Some(current) = stack.pop() {
+			if let Some(node) = self.node_mut(current) {
+				callback(current, &mut node.data);
+				stack.extend(node.children.iter().rev());
+			}
+		}
+	}
+
+	// See SceneNode::look_at. The world matrices are taken as they are, so update them first
+	// if the node or its ancestors moved. Returns false for a stale id.
+	pub fn look_at(&mut self, id: NodeId, vector: &Vector3) -> bool {
+		let parent_matrix_world = self.get_parent(id).and_then(|parent| self.get(parent)).map(|parent| *parent.get_matrix_world());
+
+		match self.get_mut(id) {
+			Some(node) => {
+				node.look_at(vector, parent_matrix_world.as_ref());
+				true
+			},
+			None => false,
+		}
+	}
+
+	// Same rules as Object3D::update_matrix_world, over every root.
+	pub fn update_matrix_world(&mut self, force: bool) {
+		let mut stack: Vec<(NodeId, Option<Matrix4>, bool)> = self.roots.iter().map(|root| (*root, None, force)).collect();
+
+		while let Some((id, parent_matrix_world, force)) = stack.pop() {
+			let node = match self.node_mut(id) {
+				Some(node) => node,
+				None => continue,
+			};
+
+			let force = node.data.update_matrix_world_with_parent(parent_matrix_world.as_ref(), force) || force;
+			let matrix_world = *node.data.get_matrix_world();
+
+			for child in &node.children {
+				stack.push((*child, Some(matrix_world), force));
+			}
+		}
+	}
+
+	// Gives the same matrices as update_matrix_world, but composes the local matrices and
+	// multiplies the world matrices on the rayon thread pool. Nodes are processed breadth
+	// first, one level at a time, so every parent is done before its children start.
+	// The transforms are gathered into one flat array in that order first, so every level is
+	// a contiguous run the pool can split, and the results are copied back afterwards.
+	#[cfg(feature = "rayon")]
+	pub fn update_matrix_world_parallel(&mut self, force: bool) {
+		let mut ids: Vec<NodeId> = vec![];
+		let mut entries: Vec<WorldMatrixEntry> = vec![];
+		let mut levels: Vec<usize> = vec![ 0 ];
+
+		for root in &self.roots {
+			if let Some(node) = self.node(*root) {
+				ids.push(*root);
+				entries.push(WorldMatrixEntry::new(&node.data, None, force));
+			}
+		}
+
+		let mut start = 0;
+
+		while start < ids.len() {
+			let end = ids.len();
+
+			for i in start..end {
+				let dirty = entries[ i ].dirty;
+
+				for child in &self.node(ids[ i ]).unwrap().children {
+					if let Some(node) = self.node(*child) {
+						ids.push(*child);
+						entries.push(WorldMatrixEntry::new(&node.data, Some(i), dirty));
+					}
+				}
+			}
+
+			levels.push(end);
+			start = end;
+		}
+
+		entries.par_iter_mut().for_each(|entry| {
+			if entry.auto_update {
+				entry.matrix.compose(&entry.position, &entry.quaternion, &entry.scale);
+			}
+		});
+
+		for level in levels.windows(2) {
+			let (parents, rest) = entries.split_at_mut(level[ 0 ]);
+			let parents: &[WorldMatrixEntry] = parents;
+
+			rest[ .. level[ 1 ] - level[ 0 ] ].par_iter_mut().for_each(|entry| {
+				if entry.dirty {
+					entry.matrix_world = transform::world_matrix(&entry.matrix, entry.parent.map(|parent| &parents[ parent ].matrix_world));
+				}
+			});
+		}
+
+		for (id, entry) in ids.iter().zip(entries.iter()) {
+			if !entry.dirty {
+				continue;
+			}
+
+			let object = &mut self.node_mut(*id).unwrap().data;
+
+			if entry.auto_update {
+				object.set_composed_matrix(&entry.matrix);
+			}
+
+			object.set_matrix_world(&entry.matrix_world);
+		}
+	}
+
+	fn node(&self, id: NodeId) -> Option<&Node> {
+		match self.slots.get(id.index as usize) {
+			Some(slot) if slot.generation == id.generation => slot.node.as_ref(),
+			_ => None,
+		}
+	}
+
+	fn node_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+		match self.slots.get_mut(id.index as usize) {
+			Some(slot) if slot.generation == id.generation => slot.node.as_mut(),
+			_ => None,
+		}
+	}
+}
+
+// A copy of what the world matrix of one node depends on. dirty follows the same rules as in
+// Object3D::update_matrix_world: auto-updated, flagged or forced by an updated parent.
+#[cfg(feature = "rayon")]
+#[derive(Clone, Copy)]
+struct WorldMatrixEntry {
+	parent: Option<usize>,
+	auto_update: bool,
+	dirty: bool,
+	position: Vector3,
+	quaternion: Quaternion,
+	scale: Vector3,
+	matrix: Matrix4,
+	matrix_world: Matrix4,
+}
+
+#[cfg(feature = "rayon")]
+impl WorldMatrixEntry {
+	fn new(object: &SceneNode, parent: Option<usize>, force: bool) -> WorldMatrixEntry {
+		let auto_update = object.get_matrix_auto_update();
+
+		WorldMatrixEntry {
+			parent,
+			auto_update,
+			dirty: auto_update || object.get_matrix_world_needs_update() || force,
+			position: *object.get_position(),
+			quaternion: *object.get_quaternion(),
+			scale: *object.get_scale(),
+			matrix: *object.get_matrix(),
+			matrix_world: *object.get_matrix_world(),
+		}
+	}
+}
+
+impl Default for SceneGraph {
+	fn default() -> SceneGraph {
+		SceneGraph::new()
+	}
+}
+
+// Building a graph on a worker thread is what SceneGraph is for, so keep it Send.
+#[allow(dead_code)]
+fn assert_send<T: Send>() {}
+
+#[allow(dead_code)]
+fn assert_scene_graph_is_send() {
+	assert_send::<SceneGraph>();
+}
+
+#[cfg(test)]
+mod tests {
+	use std::cell::RefCell;
+	use std::rc::Rc;
+	use super::{SceneGraph, NodeId};
+	use super::super::scene_node::SceneNode;
+	use super::super::object3d::{Object3D, HasObject3D};
+	use super::super::user_data::UserData;
+	use super::super::super::math::vector3::Vector3;
+
+	fn named(name: &str) -> SceneNode {
+		let mut node = SceneNode::new();
+		node.name = name.to_string();
+		node
+	}
+
+	fn names(graph: &SceneGraph, ids: &[NodeId]) -> Vec<String> {
+		ids.iter().map(|id| graph.get(*id).unwrap().name.clone()).collect()
+	}
+
+	#[test]
+	fn add_links_parent_and_children() {
+		let mut graph = SceneGraph::new();
+		let root = graph.insert(named("root"));
+		let a = graph.insert(named("a"));
+		let b = graph.insert(named("b"));
+
+		assert!(graph.add(root, a));
+		assert!(graph.add(root, b));
+
+		assert_eq!(graph.len(), 3);
+		assert_eq!(graph.get_roots(), &[ root ]);
+		assert_eq!(graph.get_parent(a), Some(root));
+		assert_eq!(names(&graph, graph.get_children(root)), vec![ "a", "b" ]);
+	}
+
+	#[test]
+	fn remove_takes_the_subtree() {
+		let mut graph = SceneGraph::new();
+		let root = graph.insert(named("root"));
+		let a = graph.insert(named("a"));
+		let b = graph.insert(named("b"));
+		let c = graph.insert(named("c"));
+		graph.add(root, a);
+		graph.add(a, b);
+		graph.add(root, c);
+
+		assert_eq!(graph.remove(a).unwrap().name, "a");
+
+		assert_eq!(graph.len(), 2);
+		assert!(!graph.contains(a));
+		assert!(!graph.contains(b));
+		assert_eq!(graph.get_children(root), &[ c ]);
+		assert!(graph.remove(a).is_none());
+	}
+
+	#[test]
+	fn stale_ids_do_not_alias_reused_slots() {
+		let mut graph = SceneGraph::new();
+		let root = graph.insert(named("root"));
+		let old = graph.insert(named("old"));
+		graph.remove(old);
+
+		let new = graph.insert(named("new"));
+
+		assert_eq!(new.get_index(), old.get_index());
+		assert_ne!(new.get_generation(), old.get_generation());
+		assert!(graph.get(old).is_none());
+		assert!(graph.get_mut(old).is_none());
+		assert!(graph.get_children(old).is_empty());
+		assert!(!graph.add(root, old));
+		assert!(!graph.add(old, new));
+		assert_eq!(graph.get(new).unwrap().name, "new");
+	}
+
+	#[test]
+	fn reparenting_moves_the_node() {
+		let mut graph = SceneGraph::new();
+		let a = graph.insert(named("a"));
+		let b = graph.insert(named("b"));
+		let x = graph.insert(named("x"));
+		let y = graph.insert(named("y"));
+		let z = graph.insert(named("z"));
+		graph.add(a, x);
+		graph.add(a, y);
+		graph.add(a, z);
+
+		assert!(graph.add(b, y));
+		assert_eq!(graph.get_parent(y), Some(b));
+		assert_eq!(names(&graph, graph.get_children(a)), vec![ "x", "z" ]);
+		assert_eq!(graph.get_children(b), &[ y ]);
+
+		assert!(graph.remove_from_parent(y));
+		assert!(!graph.remove_from_parent(y));
+		assert_eq!(graph.get_parent(y), None);
+		assert!(graph.get_children(b).is_empty());
+		assert_eq!(names(&graph, graph.get_roots()), vec![ "a", "b", "y" ]);
+	}
+
+	#[test]
+	fn rejects_cycles() {
+		let mut graph = SceneGraph::new();
+		let a = graph.insert(named("a"));
+		let b = graph.insert(named("b"));
+		let c = graph.insert(named("c"));
+		graph.add(a, b);
+		graph.add(b, c);
+
+		assert!(!graph.add(a, a));
+		assert!(!graph.add(c, a));
+		assert!(!graph.add(b, a));
+
+		assert_eq!(graph.get_parent(a), None);
+		assert_eq!(graph.get_roots(), &[ a ]);
+	}
+
+	#[test]
+	fn update_matrix_world_follows_parents() {
+		let mut graph = SceneGraph::new();
+		let parent = graph.insert(SceneNode::new());
+		let child = graph.insert(SceneNode::new());
+		graph.add(parent, child);
+
+		graph.get_mut(parent).unwrap().set_position(&Vector3 { x: 1.0, y: 2.0, z: 3.0 });
+		graph.get_mut(child).unwrap().set_position(&Vector3 { x: 1.0, y: 0.0, z: 0.0 });
+		graph.update_matrix_world(false);
+
+		let e = graph.get(child).unwrap().get_matrix_world().elements;
+		assert_eq!(( e[ 12 ], e[ 13 ], e[ 14 ] ), ( 2.0, 2.0, 3.0 ));
+	}
+
+	#[test]
+	fn copies_object3d_trees_in_and_out() {
+		let root: Rc<RefCell<dyn HasObject3D>> = Rc::new(RefCell::new(Object3D::new()));
+		root.borrow_mut().get_object3d_mut().set_name("root");
+
+		for name in &[ "a", "b" ] {
+			let child: Rc<RefCell<dyn HasObject3D>> = Rc::new(RefCell::new(Object3D::new()));
+			child.borrow_mut().get_object3d_mut().set_name(name);
+			child.borrow_mut().get_object3d_mut().set_position(&Vector3 { x: 1.0, y: 0.0, z: 0.0 });
+			Object3D::add(&root, &child);
+		}
+
+		let mut graph = SceneGraph::new();
+		let id = graph.insert_tree(&root);
+		assert_eq!(graph.len(), 3);
+		assert_eq!(names(&graph, graph.get_children(id)), vec![ "a", "b" ]);
+		assert_eq!(graph.get(graph.get_children(id)[ 1 ]).unwrap().get_position().x, 1.0);
+
+		let copy = graph.to_tree(id).unwrap();
+		let copy = copy.borrow();
+		let children = copy.get_object3d().get_children();
+		assert_eq!(copy.get_object3d().get_name(), "root");
+		assert_eq!(children.len(), 2);
+		assert_eq!(children[ 1 ].borrow().get_object3d().get_name(), "b");
+		assert_eq!(children[ 1 ].borrow().get_object3d().get_position().x, 1.0);
+	}
+
+	#[test]
+	fn round_trips_every_field_and_the_versions() {
+		let mut object = Object3D::new();
+		object.set_name("object");
+		object.set_visible(false);
+		object.set_cast_shadow(true);
+		object.set_receive_shadow(true);
+		object.set_frustum_culled(false);
+		object.get_layers_mut().set(3).unwrap();
+		object.set_render_order(7);
+		object.set_user_data(UserData::String("data".to_string()));
+		object.set_up(&Vector3 { x: 0.0, y: 0.0, z: 1.0 });
+		object.set_position(&Vector3 { x: 1.0, y: 2.0, z: 3.0 });
+		object.rotate_y(0.4);
+		object.set_scale(&Vector3 { x: 2.0, y: 1.0, z: 0.5 });
+		object.update_matrix_world(false);
+
+		let mut graph = SceneGraph::new();
+		let id = graph.insert_object(&object);
+		let copy = graph.to_object3d(id).unwrap();
+
+		assert_eq!(copy.get_name(), "object");
+		assert!(!copy.is_visible());
+		assert!(copy.get_cast_shadow());
+		assert!(copy.get_receive_shadow());
+		assert!(!copy.get_frustum_culled());
+		assert_eq!(copy.get_layers().mask, object.get_layers().mask);
+		assert_eq!(copy.get_render_order(), 7);
+		assert_eq!(copy.get_user_data(), object.get_user_data());
+		assert!(copy.get_up().equals(object.get_up()));
+		assert!(copy.get_position().equals(object.get_position()));
+		assert!(copy.get_quaternion().equals(object.get_quaternion()));
+		assert!(copy.get_scale().equals(object.get_scale()));
+		assert_eq!(copy.get_matrix().elements, object.get_matrix().elements);
+		assert_eq!(copy.get_matrix_world().elements, object.get_matrix_world().elements);
+		assert_eq!(copy.get_matrix_auto_update(), object.get_matrix_auto_update());
+		assert_eq!(copy.get_matrix_world_needs_update(), object.get_matrix_world_needs_update());
+		assert_eq!(copy.get_transform_version(), object.get_transform_version());
+		assert_eq!(copy.get_matrix_world_version(), object.get_matrix_world_version());
+	}
+
+	#[test]
+	fn clear_empties_the_graph_and_stales_the_ids() {
+		let mut graph = SceneGraph::new();
+		let root = graph.insert(named("root"));
+		let child = graph.insert(named("child"));
+		graph.add(root, child);
+		graph.remove(child);
+		let other = graph.insert(named("other"));
+
+		graph.clear();
+		assert!(graph.is_empty());
+		assert!(graph.get_roots().is_empty());
+
+		let new = graph.insert(named("new"));
+		assert!(!graph.contains(root));
+		assert!(!graph.contains(other));
+		assert_eq!(names(&graph, &[ new ]), vec![ "new" ]);
+		assert_eq!(graph.get_roots(), &[ new ]);
+		assert_eq!(graph.len(), 1);
+	}
+
+	#[test]
+	fn look_at_matches_object3d() {
+		let parent: Rc<RefCell<dyn HasObject3D>> = Rc::new(RefCell::new(Object3D::new()));
+		let child: Rc<RefCell<dyn HasObject3D>> = Rc::new(RefCell::new(Object3D::new()));
+		parent.borrow_mut().get_object3d_mut().rotate_z(0.8);
+		parent.borrow_mut().get_object3d_mut().set_position(&Vector3 { x: 1.0, y: - 1.0, z: 0.0 });
+		child.borrow_mut().get_object3d_mut().set_position(&Vector3 { x: 0.0, y: 2.0, z: 1.0 });
+		Object3D::add(&parent, &child);
+		parent.borrow_mut().get_object3d_mut().update_matrix_world(false);
+
+		let mut graph = SceneGraph::new();
+		let root = graph.insert_tree(&parent);
+		let id = graph.get_children(root)[ 0 ];
+
+		let target = Vector3 { x: 4.0, y: 3.0, z: - 2.0 };
+		child.borrow_mut().get_object3d_mut().look_at(&target);
+		assert!(graph.look_at(id, &target));
+
+		let expected = *child.borrow().get_object3d().get_quaternion();
+		let actual = graph.get(id).unwrap().get_quaternion();
+		assert!(( actual.dot(&expected).abs() - 1.0 ).abs() < 1e-5, "{:?} != {:?}", actual, expected);
+	}
+}
+
+#[cfg(all(test, feature = "rayon"))]
+mod parallel_tests {
+	use super::{SceneGraph, NodeId};
+	use super::super::scene_node::SceneNode;
+	use super::super::change_tracker::current_version;
+	use super::super::super::math::vector3::Vector3;
+
+	fn object(i: usize) -> SceneNode {
+		let f = i as f32;
+		let mut object = SceneNode::new();
+		object.translate_on_axis(&Vector3 { x: 1.0, y: 0.5, z: - 0.25 }, 0.1 + ( f * 0.37 ) % 1.0);
+		object.rotate_on_axis(&Vector3 { x: 1.0, y: 0.0, z: 0.0 }, ( f * 0.13 ) % 1.0);
+		object.rotate_on_axis(&Vector3 { x: 0.0, y: 1.0, z: 0.0 }, ( f * 0.71 ) % 1.0);
+		object
+	}
+
+	// Builds the same hierarchy twice, parent_of gives the parent of node i > 0.
+	fn build<F: Fn(usize) -> usize>(count: usize, parent_of: F) -> (SceneGraph, SceneGraph, Vec<NodeId>) {
+		let mut serial = SceneGraph::new();
+		let mut parallel = SceneGraph::new();
+		let mut ids = vec![];
+
+		for i in 0..count {
+			let id = serial.insert(object(i));
+			assert_eq!(id, parallel.insert(object(i)));
+
+			if i > 0 {
+				let parent = ids[ parent_of(i) ];
+				serial.add(parent, id);
+				parallel.add(parent, id);
+			}
+
+			ids.push(id);
+		}
+
+		(serial, parallel, ids)
+	}
+
+	// The two scenes are stamped from the same clock at different times, so versions are
+	// compared by whether they moved past the frame each update started in.
+	fn assert_identical(serial: &SceneGraph, serial_frame: u64, parallel: &SceneGraph, parallel_frame: u64, ids: &[NodeId]) {
+		for id in ids {
+			let a = serial.get(*id).unwrap();
+			let b = parallel.get(*id).unwrap();
+			assert_eq!(a.get_matrix().elements, b.get_matrix().elements);
+			assert_eq!(a.get_matrix_world().elements, b.get_matrix_world().elements);
+			assert_eq!(a.get_matrix_world_needs_update(), b.get_matrix_world_needs_update());
+			assert_eq!(a.get_transform_version() > serial_frame, b.get_transform_version() > parallel_frame);
+			assert_eq!(a.get_matrix_world_version() > serial_frame, b.get_matrix_world_version() > parallel_frame);
+		}
+	}
+
+	// Updates both scenes the same way and compares the results.
+	fn update_both(serial: &mut SceneGraph, parallel: &mut SceneGraph, ids: &[NodeId], force: bool) {
+		let serial_frame = current_version();
+		serial.update_matrix_world(force);
+		let parallel_frame = current_version();
+		parallel.update_matrix_world_parallel(force);
+
+		assert_identical(serial, serial_frame, parallel, parallel_frame, ids);
+
+		// an update on its own never changes a transform
+		for id in ids {
+			assert!(parallel.get(*id).unwrap().get_transform_version() <= parallel_frame);
+		}
+	}
+
+	#[test]
+	fn deep_chain() {
+		let (mut serial, mut parallel, ids) = build(2000, |i| i - 1);
+
+		update_both(&mut serial, &mut parallel, &ids, false);
+	}
+
+	#[test]
+	fn wide_fan() {
+		let (mut serial, mut parallel, ids) = build(20000, |_| 0);
+
+		update_both(&mut serial, &mut parallel, &ids, false);
+	}
+
+	#[test]
+	fn mixed_tree() {
+		let (mut serial, mut parallel, ids) = build(5000, |i| ( i - 1 ) / 3);
+
+		update_both(&mut serial, &mut parallel, &ids, false);
+	}
+
+	#[test]
+	fn honors_update_flags() {
+		let (mut serial, mut parallel, ids) = build(500, |i| ( i - 1 ) / 2);
+
+		update_both(&mut serial, &mut parallel, &ids, false);
+
+		// freeze part of the tree, then move objects both above and below the frozen nodes
+		for scene in [ &mut serial, &mut parallel ].iter_mut() {
+			for (n, id) in ids.iter().enumerate() {
+				let object = scene.get_mut(*id).unwrap();
+
+				if n % 3 == 0 {
+					object.set_matrix_auto_update(false);
+				}
+
+				object.translate_on_axis(&Vector3 { x: 0.0, y: 1.0, z: 0.0 }, 1.0);
+			}
+		}
+
+		update_both(&mut serial, &mut parallel, &ids, false);
+		update_both(&mut serial, &mut parallel, &ids, true);
+	}
+}
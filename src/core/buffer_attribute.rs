@@ -0,0 +1,374 @@
+use std::any::Any;
+use std::fmt::Debug;
+use super::super::math::vector3::Vector3;
+use super::super::math::matrix3::Matrix3;
+use super::super::math::matrix4::Matrix4;
+use super::super::math::data_utils::{pack_unorm8, unpack_unorm8, pack_snorm8, unpack_snorm8, pack_unorm16, unpack_unorm16};
+use super::super::math::math_static::clamp;
+use super::change_tracker::next_version;
+
+// The element types an attribute can be stored as. Normalized integers map to [0, 1] for the
+// unsigned types and [-1, 1] for the signed ones, like in WebGL.
+pub trait BufferElement: Copy + Default + PartialEq + Debug + 'static {
+	fn to_f32(self, normalized: bool) -> f32;
+	fn from_f32(value: f32, normalized: bool) -> Self;
+}
+
+impl BufferElement for f32 {
+	fn to_f32(self, _normalized: bool) -> f32 {
+		self
+	}
+
+	fn from_f32(value: f32, _normalized: bool) -> f32 {
+		value
+	}
+}
+
+impl BufferElement for u8 {
+	fn to_f32(self, normalized: bool) -> f32 {
+		if normalized { unpack_unorm8(self) } else { self as f32 }
+	}
+
+	fn from_f32(value: f32, normalized: bool) -> u8 {
+		if normalized { pack_unorm8(value) } else { value as u8 }
+	}
+}
+
+impl BufferElement for i8 {
+	fn to_f32(self, normalized: bool) -> f32 {
+		if normalized { unpack_snorm8(self) } else { self as f32 }
+	}
+
+	fn from_f32(value: f32, normalized: bool) -> i8 {
+		if normalized { pack_snorm8(value) } else { value as i8 }
+	}
+}
+
+impl BufferElement for u16 {
+	fn to_f32(self, normalized: bool) -> f32 {
+		if normalized { unpack_unorm16(self) } else { self as f32 }
+	}
+
+	fn from_f32(value: f32, normalized: bool) -> u16 {
+		if normalized { pack_unorm16(value) } else { value as u16 }
+	}
+}
+
+impl BufferElement for u32 {
+	fn to_f32(self, normalized: bool) -> f32 {
+		if normalized { ( self as f64 / 4_294_967_295.0 ) as f32 } else { self as f32 }
+	}
+
+	fn from_f32(value: f32, normalized: bool) -> u32 {
+		if normalized {
+			( clamp(value, 0.0, 1.0) as f64 * 4_294_967_295.0 ).round() as u32
+		} else {
+			value as u32
+		}
+	}
+}
+
+// A part of an array that changed, in elements rather than items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpdateRange {
+	pub start: usize,
+	pub count: usize,
+}
+
+// Per vertex data, read and written as f32 whatever the storage. BufferGeometry keeps its
+// attributes behind this so plain and interleaved ones can be mixed.
+pub trait Attribute {
+	fn get_item_size(&self) -> usize;
+	fn get_count(&self) -> usize;
+	fn is_normalized(&self) -> bool;
+
+	fn get_component(&self, index: usize, component: usize) -> f32;
+	fn set_component(&mut self, index: usize, component: usize, value: f32);
+
+	// Stamped by needs_update from the core::change_tracker clock, so users of the data can
+	// tell it changed since a frame.
+	fn get_version(&self) -> u64;
+	fn needs_update(&mut self);
+
+	fn clone_attribute(&self) -> Box<dyn Attribute>;
+	fn as_any(&self) -> &dyn Any;
+	fn as_any_mut(&mut self) -> &mut dyn Any;
+
+	// True when the data lives in an InterleavedBuffer shared with other attributes.
+	fn is_interleaved(&self) -> bool {
+		false
+	}
+
+	fn get_x(&self, index: usize) -> f32 {
+		self.get_component(index, 0)
+	}
+
+	fn set_x(&mut self, index: usize, x: f32) {
+		self.set_component(index, 0, x);
+	}
+
+	fn get_y(&self, index: usize) -> f32 {
+		self.get_component(index, 1)
+	}
+
+	fn set_y(&mut self, index: usize, y: f32) {
+		self.set_component(index, 1, y);
+	}
+
+	fn get_z(&self, index: usize) -> f32 {
+		self.get_component(index, 2)
+	}
+
+	fn set_z(&mut self, index: usize, z: f32) {
+		self.set_component(index, 2, z);
+	}
+
+	fn get_w(&self, index: usize) -> f32 {
+		self.get_component(index, 3)
+	}
+
+	fn set_w(&mut self, index: usize, w: f32) {
+		self.set_component(index, 3, w);
+	}
+
+	fn set_xy(&mut self, index: usize, x: f32, y: f32) {
+		self.set_component(index, 0, x);
+		self.set_component(index, 1, y);
+	}
+
+	fn set_xyz(&mut self, index: usize, x: f32, y: f32, z: f32) {
+		self.set_component(index, 0, x);
+		self.set_component(index, 1, y);
+		self.set_component(index, 2, z);
+	}
+
+	fn set_xyzw(&mut self, index: usize, x: f32, y: f32, z: f32, w: f32) {
+		self.set_component(index, 0, x);
+		self.set_component(index, 1, y);
+		self.set_component(index, 2, z);
+		self.set_component(index, 3, w);
+	}
+
+	fn get_vector3(&self, index: usize, target: &mut Vector3) {
+		target.set(self.get_x(index), self.get_y(index), self.get_z(index));
+	}
+
+	fn set_vector3(&mut self, index: usize, v: &Vector3) {
+		self.set_xyz(index, v.x, v.y, v.z);
+	}
+
+	// All items, item_size components each.
+	fn to_f32_array(&self) -> Vec<f32> {
+		let item_size = self.get_item_size();
+		let mut array = Vec::with_capacity(self.get_count() * item_size);
+
+		for i in 0..self.get_count() {
+			for c in 0..item_size {
+				array.push(self.get_component(i, c));
+			}
+		}
+
+		array
+	}
+
+	// Two component items are treated as 2D points in homogeneous coordinates.
+	fn apply_matrix3(&mut self, m: &Matrix3) {
+		if self.get_item_size() == 2 {
+			let e = m.get_elements();
+
+			for i in 0..self.get_count() {
+				let x = self.get_x(i);
+				let y = self.get_y(i);
+				self.set_xy(i, e[ 0 ] * x + e[ 3 ] * y + e[ 6 ], e[ 1 ] * x + e[ 4 ] * y + e[ 7 ]);
+			}
+		} else if self.get_item_size() >= 3 {
+			let mut v1 = Vector3::new();
+
+			for i in 0..self.get_count() {
+				self.get_vector3(i, &mut v1);
+				v1.apply_matrix3(m);
+				self.set_vector3(i, &v1);
+			}
+		}
+	}
+
+	fn apply_matrix4(&mut self, m: &Matrix4) {
+		map_vector3(self, |v| v.apply_matrix4(m));
+	}
+
+	fn apply_normal_matrix(&mut self, m: &Matrix3) {
+		map_vector3(self, |v| {
+			v.apply_matrix3(m);
+			v.normalize();
+		});
+	}
+
+	fn transform_direction(&mut self, m: &Matrix4) {
+		map_vector3(self, |v| v.transform_direction(m));
+	}
+}
+
+// Runs f on every item as a Vector3. Two component items are read with z = 0 and only get
+// x and y back, one component items are left alone.
+fn map_vector3<A: Attribute + ?Sized, F: FnMut(&mut Vector3)>(attribute: &mut A, mut f: F) {
+	let item_size = attribute.get_item_size();
+	let mut v1 = Vector3::new();
+
+	if item_size < 2 {
+		return;
+	}
+
+	for i in 0..attribute.get_count() {
+		if item_size == 2 {
+			v1.set(attribute.get_x(i), attribute.get_y(i), 0.0);
+			f(&mut v1);
+			attribute.set_xy(i, v1.x, v1.y);
+		} else {
+			attribute.get_vector3(i, &mut v1);
+			f(&mut v1);
+			attribute.set_vector3(i, &v1);
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct BufferAttribute<T: BufferElement = f32> {
+	pub name: String,
+	pub array: Vec<T>,
+	pub item_size: usize,
+	pub normalized: bool,
+	update_ranges: Vec<UpdateRange>,
+	version: u64,
+}
+
+impl<T: BufferElement> BufferAttribute<T> {
+	pub fn new(array: Vec<T>, item_size: usize, normalized: bool) -> BufferAttribute<T> {
+		assert!(item_size > 0, "item_size must be at least 1");
+
+		BufferAttribute {
+			name: String::new(),
+			array,
+			item_size,
+			normalized,
+			update_ranges: vec![],
+			version: next_version(),
+		}
+	}
+
+	pub fn get_count(&self) -> usize {
+		self.array.len() / self.item_size
+	}
+
+	// Copies values into the array, starting at element offset.
+	pub fn set(&mut self, values: &[T], offset: Option<usize>) {
+		let offset = offset.unwrap_or(0);
+		self.array[ offset .. offset + values.len() ].copy_from_slice(values);
+	}
+
+	pub fn copy_array(&mut self, array: &[T]) {
+		self.array.clear();
+		self.array.extend_from_slice(array);
+	}
+
+	// Copies one item from another attribute with the same item size.
+	pub fn copy_at(&mut self, index1: usize, attribute: &BufferAttribute<T>, index2: usize) {
+		let index1 = index1 * self.item_size;
+		let index2 = index2 * attribute.item_size;
+
+		self.array[ index1 .. index1 + self.item_size ].copy_from_slice(&attribute.array[ index2 .. index2 + self.item_size ]);
+	}
+
+	pub fn add_update_range(&mut self, start: usize, count: usize) {
+		self.update_ranges.push(UpdateRange {
+			start,
+			count,
+		});
+	}
+
+	pub fn clear_update_ranges(&mut self) {
+		self.update_ranges.clear();
+	}
+
+	pub fn get_update_ranges(&self) -> &[UpdateRange] {
+		&self.update_ranges
+	}
+}
+
+impl<T: BufferElement> Attribute for BufferAttribute<T> {
+	fn get_item_size(&self) -> usize {
+		self.item_size
+	}
+
+	fn get_count(&self) -> usize {
+		BufferAttribute::get_count(self)
+	}
+
+	fn is_normalized(&self) -> bool {
+		self.normalized
+	}
+
+	fn get_component(&self, index: usize, component: usize) -> f32 {
+		self.array[ index * self.item_size + component ].to_f32(self.normalized)
+	}
+
+	fn set_component(&mut self, index: usize, component: usize, value: f32) {
+		self.array[ index * self.item_size + component ] = T::from_f32(value, self.normalized);
+	}
+
+	fn get_version(&self) -> u64 {
+		self.version
+	}
+
+	fn needs_update(&mut self) {
+		self.version = next_version();
+	}
+
+	fn clone_attribute(&self) -> Box<dyn Attribute> {
+		Box::new(self.clone())
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+
+	fn as_any_mut(&mut self) -> &mut dyn Any {
+		self
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn apply_matrix4_keeps_two_component_items_apart() {
+		let mut attribute = BufferAttribute::new(vec![ 1.0, 2.0, 3.0, 4.0 ], 2, false);
+		let mut m = Matrix4::new();
+		m.make_translation(10.0, 20.0, 30.0);
+
+		attribute.apply_matrix4(&m);
+		assert_eq!(attribute.array, vec![ 11.0, 22.0, 13.0, 24.0 ]);
+
+		attribute.transform_direction(&m);
+		let length = ( attribute.get_x(0) * attribute.get_x(0) + attribute.get_y(0) * attribute.get_y(0) ).sqrt();
+		assert!(( length - 1.0 ).abs() < 1e-6);
+	}
+
+	#[test]
+	fn one_component_items_are_left_alone() {
+		let mut attribute = BufferAttribute::new(vec![ 1.0, 2.0, 3.0 ], 1, false);
+		let mut m = Matrix4::new();
+		m.make_scale(2.0, 2.0, 2.0);
+
+		attribute.apply_matrix4(&m);
+		attribute.apply_normal_matrix(&Matrix3::new());
+		attribute.transform_direction(&m);
+		assert_eq!(attribute.array, vec![ 1.0, 2.0, 3.0 ]);
+	}
+
+	#[test]
+	#[should_panic]
+	fn rejects_item_size_zero() {
+		BufferAttribute::new(vec![ 1.0f32 ], 0, false);
+	}
+}
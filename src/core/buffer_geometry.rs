@@ -0,0 +1,511 @@
+use std::collections::HashMap;
+use std::f32;
+use super::buffer_attribute::{Attribute, BufferAttribute};
+use super::super::math::vector3::Vector3;
+use super::super::math::quaternion::Quaternion;
+use super::super::math::matrix3::Matrix3;
+use super::super::math::matrix4::Matrix4;
+use super::super::math::box3::Box3;
+use super::super::math::sphere::Sphere;
+
+// A range of the index, or of the vertices without one, drawn with one material.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Group {
+	pub start: usize,
+	pub count: usize,
+	pub material_index: usize,
+}
+
+// count is None to draw everything from start on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrawRange {
+	pub start: usize,
+	pub count: Option<usize>,
+}
+
+pub struct BufferGeometry {
+	pub name: String,
+	index: Option<BufferAttribute<u32>>,
+	attributes: HashMap<String, Box<dyn Attribute>>,
+	groups: Vec<Group>,
+	draw_range: DrawRange,
+	bounding_box: Option<Box3>,
+	bounding_sphere: Option<Sphere>,
+}
+
+impl BufferGeometry {
+	pub fn new() -> BufferGeometry {
+		BufferGeometry {
+			name: String::new(),
+			index: None,
+			attributes: HashMap::new(),
+			groups: vec![],
+			draw_range: DrawRange {
+				start: 0,
+				count: None,
+			},
+			bounding_box: None,
+			bounding_sphere: None,
+		}
+	}
+
+	pub fn get_index(&self) -> Option<&BufferAttribute<u32>> {
+		self.index.as_ref()
+	}
+
+	pub fn get_index_mut(&mut self) -> Option<&mut BufferAttribute<u32>> {
+		self.index.as_mut()
+	}
+
+	pub fn set_index(&mut self, index: Option<BufferAttribute<u32>>) {
+		self.index = index;
+	}
+
+	pub fn get_attribute(&self, name: &str) -> Option<&dyn Attribute> {
+		self.attributes.get(name).map(|attribute| attribute.as_ref())
+	}
+
+	pub fn get_attribute_mut(&mut self, name: &str) -> Option<&mut (dyn Attribute + 'static)> {
+		self.attributes.get_mut(name).map(|attribute| attribute.as_mut())
+	}
+
+	pub fn set_attribute<A: Attribute + 'static>(&mut self, name: &str, attribute: A) {
+		self.attributes.insert(name.to_string(), Box::new(attribute));
+	}
+
+	pub fn set_attribute_boxed(&mut self, name: &str, attribute: Box<dyn Attribute>) {
+		self.attributes.insert(name.to_string(), attribute);
+	}
+
+	pub fn delete_attribute(&mut self, name: &str) -> Option<Box<dyn Attribute>> {
+		self.attributes.remove(name)
+	}
+
+	pub fn has_attribute(&self, name: &str) -> bool {
+		self.attributes.contains_key(name)
+	}
+
+	pub fn get_attribute_names(&self) -> Vec<String> {
+		let mut names: Vec<String> = self.attributes.keys().cloned().collect();
+		names.sort();
+		names
+	}
+
+	pub fn get_groups(&self) -> &[Group] {
+		&self.groups
+	}
+
+	pub fn add_group(&mut self, start: usize, count: usize, material_index: usize) {
+		self.groups.push(Group {
+			start,
+			count,
+			material_index,
+		});
+	}
+
+	pub fn clear_groups(&mut self) {
+		self.groups.clear();
+	}
+
+	pub fn get_draw_range(&self) -> &DrawRange {
+		&self.draw_range
+	}
+
+	pub fn set_draw_range(&mut self, start: usize, count: Option<usize>) {
+		self.draw_range.start = start;
+		self.draw_range.count = count;
+	}
+
+	pub fn get_bounding_box(&self) -> Option<&Box3> {
+		self.bounding_box.as_ref()
+	}
+
+	pub fn get_bounding_sphere(&self) -> Option<&Sphere> {
+		self.bounding_sphere.as_ref()
+	}
+
+	// Transforms positions as points, normals with the normal matrix and tangents as
+	// directions. Bounds that were computed before are computed again.
+	pub fn apply_matrix4(&mut self, matrix: &Matrix4) {
+		if let Some(position) = self.get_attribute_mut("position") {
+			position.apply_matrix4(matrix);
+			position.needs_update();
+		}
+
+		if let Some(normal) = self.get_attribute_mut("normal") {
+			let mut normal_matrix = Matrix3::new();
+			normal_matrix.get_normal_matrix(matrix);
+			normal.apply_normal_matrix(&normal_matrix);
+			normal.needs_update();
+		}
+
+		if let Some(tangent) = self.get_attribute_mut("tangent") {
+			tangent.transform_direction(matrix);
+			tangent.needs_update();
+		}
+
+		if self.bounding_box.is_some() {
+			self.compute_bounding_box();
+		}
+
+		if self.bounding_sphere.is_some() {
+			self.compute_bounding_sphere();
+		}
+	}
+
+	pub fn apply_quaternion(&mut self, q: &Quaternion) {
+		let mut m1 = Matrix4::new();
+		m1.make_rotation_from_quaternion(q);
+		self.apply_matrix4(&m1);
+	}
+
+	// rotate geometry around world x-axis
+	pub fn rotate_x(&mut self, angle: f32) {
+		let mut m1 = Matrix4::new();
+		m1.make_rotation_x(angle);
+		self.apply_matrix4(&m1);
+	}
+
+	// rotate geometry around world y-axis
+	pub fn rotate_y(&mut self, angle: f32) {
+		let mut m1 = Matrix4::new();
+		m1.make_rotation_y(angle);
+		self.apply_matrix4(&m1);
+	}
+
+	// rotate geometry around world z-axis
+	pub fn rotate_z(&mut self, angle: f32) {
+		let mut m1 = Matrix4::new();
+		m1.make_rotation_z(angle);
+		self.apply_matrix4(&m1);
+	}
+
+	pub fn translate(&mut self, x: f32, y: f32, z: f32) {
+		let mut m1 = Matrix4::new();
+		m1.make_translation(x, y, z);
+		self.apply_matrix4(&m1);
+	}
+
+	pub fn scale(&mut self, x: f32, y: f32, z: f32) {
+		let mut m1 = Matrix4::new();
+		m1.make_scale(x, y, z);
+		self.apply_matrix4(&m1);
+	}
+
+	// Rotates the geometry so that its z axis points at vector, as Object3D::look_at would.
+	pub fn look_at(&mut self, vector: &Vector3) {
+		let mut m1 = Matrix4::new();
+		let origin = Vector3::new();
+		let up = Vector3 {
+			x: 0.0,
+			y: 1.0,
+			z: 0.0,
+		};
+		m1.look_at(vector, &origin, &up);
+		self.apply_matrix4(&m1);
+	}
+
+	// Moves the center of the bounding box to the origin.
+	pub fn center(&mut self) {
+		let mut offset = Vector3::new();
+
+		self.compute_bounding_box();
+		self.bounding_box.unwrap().get_center(&mut offset);
+		offset.negate();
+
+		self.translate(offset.x, offset.y, offset.z);
+	}
+
+	pub fn set_from_points(&mut self, points: &[Vector3]) {
+		let mut position = Vec::with_capacity(points.len() * 3);
+
+		for point in points {
+			position.push(point.x);
+			position.push(point.y);
+			position.push(point.z);
+		}
+
+		self.set_attribute("position", BufferAttribute::new(position, 3, false));
+	}
+
+	// Left empty without a position attribute.
+	pub fn compute_bounding_box(&mut self) {
+		let mut bounding_box = Box3::new();
+
+		if let Some(position) = self.get_attribute("position") {
+			let mut v1 = Vector3::new();
+
+			for i in 0..position.get_count() {
+				position.get_vector3(i, &mut v1);
+				bounding_box.expand_by_point(&v1);
+			}
+		}
+
+		self.bounding_box = Some(bounding_box);
+	}
+
+	// Centered on the bounding box, which is tighter than the smallest sphere around the
+	// points only in odd cases but much cheaper.
+	pub fn compute_bounding_sphere(&mut self) {
+		let mut sphere = Sphere::new();
+
+		if let Some(position) = self.get_attribute("position") {
+			let mut bounding_box = Box3::new();
+			let mut v1 = Vector3::new();
+
+			for i in 0..position.get_count() {
+				position.get_vector3(i, &mut v1);
+				bounding_box.expand_by_point(&v1);
+			}
+
+			if !bounding_box.is_empty() {
+				let mut center = Vector3::new();
+				bounding_box.get_center(&mut center);
+
+				let mut max_radius_sq: f32 = 0.0;
+
+				for i in 0..position.get_count() {
+					position.get_vector3(i, &mut v1);
+					max_radius_sq = max_radius_sq.max(center.distance_to_squared(&v1));
+				}
+
+				sphere.set(&center, max_radius_sq.sqrt());
+			}
+		}
+
+		self.bounding_sphere = Some(sphere);
+	}
+
+	// Sums the face normals around each vertex. They aren't normalized before, so larger
+	// faces weigh more. Without an index every face has its own vertices, so this gives
+	// flat normals.
+	pub fn compute_vertex_normals(&mut self) {
+		let positions = match self.get_attribute("position") {
+			Some(position) => position.to_f32_array(),
+			None => return,
+		};
+
+		let vertex_count = positions.len() / 3;
+		let mut normals = vec![ 0.0f32; vertex_count * 3 ];
+
+		let mut p_a = Vector3::new();
+		let mut p_b = Vector3::new();
+		let mut p_c = Vector3::new();
+		let mut cb = Vector3::new();
+		let mut ab = Vector3::new();
+
+		let triangle_count = match self.index {
+			Some(ref index) => index.array.len() / 3,
+			None => vertex_count / 3,
+		};
+
+		for f in 0..triangle_count {
+			let (v_a, v_b, v_c) = match self.index {
+				Some(ref index) => (index.array[ f * 3 ] as usize, index.array[ f * 3 + 1 ] as usize, index.array[ f * 3 + 2 ] as usize),
+				None => (f * 3, f * 3 + 1, f * 3 + 2),
+			};
+
+			p_a.copy_from_array(&positions, Some(v_a * 3));
+			p_b.copy_from_array(&positions, Some(v_b * 3));
+			p_c.copy_from_array(&positions, Some(v_c * 3));
+
+			cb.sub_vectors(&p_c, &p_b);
+			ab.sub_vectors(&p_a, &p_b);
+			cb.cross(&ab);
+
+			for v in &[ v_a, v_b, v_c ] {
+				normals[ v * 3 ] += cb.x;
+				normals[ v * 3 + 1 ] += cb.y;
+				normals[ v * 3 + 2 ] += cb.z;
+			}
+		}
+
+		// normalize before writing, a quantized attribute would clamp the raw sums
+		for i in 0..vertex_count {
+			cb.copy_from_array(&normals, Some(i * 3));
+			cb.normalize();
+			cb.copy_to_array(&mut normals, Some(i * 3));
+		}
+
+		// reuse the attribute if it fits, so it stays the same attribute with a new version
+		let reuse = match self.get_attribute("normal") {
+			Some(normal) => normal.get_count() == vertex_count && normal.get_item_size() >= 3,
+			None => false,
+		};
+
+		if reuse {
+			let normal = self.get_attribute_mut("normal").unwrap();
+
+			for i in 0..vertex_count {
+				normal.set_xyz(i, normals[ i * 3 ], normals[ i * 3 + 1 ], normals[ i * 3 + 2 ]);
+			}
+
+			normal.needs_update();
+		} else {
+			self.set_attribute("normal", BufferAttribute::new(normals, 3, false));
+		}
+	}
+
+	pub fn normalize_normals(&mut self) {
+		if let Some(normal) = self.get_attribute_mut("normal") {
+			let mut v1 = Vector3::new();
+
+			for i in 0..normal.get_count() {
+				normal.get_vector3(i, &mut v1);
+				v1.normalize();
+				normal.set_vector3(i, &v1);
+			}
+
+			normal.needs_update();
+		}
+	}
+
+	// Gives every face its own vertices, as f32 attributes. A geometry without an index is
+	// returned as a clone.
+	pub fn to_non_indexed(&self) -> BufferGeometry {
+		let index = match self.index {
+			Some(ref index) => index,
+			None => return self.clone(),
+		};
+
+		let mut geometry = BufferGeometry::new();
+		geometry.name = self.name.clone();
+
+		for (name, attribute) in &self.attributes {
+			let item_size = attribute.get_item_size();
+			let mut array = Vec::with_capacity(index.array.len() * item_size);
+
+			for i in &index.array {
+				for c in 0..item_size {
+					array.push(attribute.get_component(*i as usize, c));
+				}
+			}
+
+			geometry.set_attribute(name, BufferAttribute::new(array, item_size, attribute.is_normalized()));
+		}
+
+		geometry.groups = self.groups.clone();
+
+		geometry
+	}
+}
+
+impl Clone for BufferGeometry {
+	fn clone(&self) -> BufferGeometry {
+		BufferGeometry {
+			name: self.name.clone(),
+			index: self.index.clone(),
+			attributes: self.attributes.iter().map(|(name, attribute)| (name.clone(), attribute.clone_attribute())).collect(),
+			groups: self.groups.clone(),
+			draw_range: self.draw_range,
+			bounding_box: self.bounding_box,
+			bounding_sphere: self.bounding_sphere,
+		}
+	}
+}
+
+impl Default for BufferGeometry {
+	fn default() -> BufferGeometry {
+		BufferGeometry::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn normal(geometry: &BufferGeometry, i: usize) -> Vector3 {
+		let mut v = Vector3::new();
+		geometry.get_attribute("normal").unwrap().get_vector3(i, &mut v);
+		v
+	}
+
+	fn assert_close(a: &Vector3, b: &Vector3) {
+		assert!(a.distance_to(b) < 1e-5, "{:?} != {:?}", a, b);
+	}
+
+	#[test]
+	fn indexed_faces_share_normals() {
+		// a quad in the xy plane and a second face folded down along x = 1, same area each
+		let mut geometry = BufferGeometry::new();
+		geometry.set_attribute("position", BufferAttribute::new(vec![
+			0.0, 0.0, 0.0,
+			1.0, 0.0, 0.0,
+			1.0, 1.0, 0.0,
+			0.0, 1.0, 0.0,
+			1.0, 0.0, - 1.0,
+		], 3, false));
+		geometry.set_index(Some(BufferAttribute::new(vec![ 0, 1, 2, 0, 2, 3, 1, 4, 2 ], 1, false)));
+
+		geometry.compute_vertex_normals();
+
+		let up = Vector3 { x: 0.0, y: 0.0, z: 1.0 };
+		assert_close(&normal(&geometry, 0), &up);
+		assert_close(&normal(&geometry, 3), &up);
+		assert_close(&normal(&geometry, 4), &Vector3 { x: 1.0, y: 0.0, z: 0.0 });
+
+		// the vertices on the fold sum the faces around them, vertex 2 is in both triangles
+		// of the quad
+		let half = ::std::f32::consts::FRAC_1_SQRT_2;
+		assert_close(&normal(&geometry, 1), &Vector3 { x: half, y: 0.0, z: half });
+
+		let mut expected = Vector3 { x: 1.0, y: 0.0, z: 2.0 };
+		expected.normalize();
+		assert_close(&normal(&geometry, 2), &expected);
+	}
+
+	#[test]
+	fn non_indexed_faces_are_flat() {
+		let mut geometry = BufferGeometry::new();
+		geometry.set_attribute("position", BufferAttribute::new(vec![
+			0.0, 0.0, 0.0,
+			1.0, 0.0, 0.0,
+			0.0, 1.0, 0.0,
+			0.0, 0.0, 0.0,
+			0.0, 0.0, 1.0,
+			1.0, 0.0, 0.0,
+		], 3, false));
+
+		geometry.compute_vertex_normals();
+
+		for i in 0..3 {
+			assert_close(&normal(&geometry, i), &Vector3 { x: 0.0, y: 0.0, z: 1.0 });
+			assert_close(&normal(&geometry, i + 3), &Vector3 { x: 0.0, y: 1.0, z: 0.0 });
+		}
+	}
+
+	#[test]
+	fn reuses_a_fitting_normal_attribute() {
+		let mut geometry = BufferGeometry::new();
+		geometry.set_attribute("position", BufferAttribute::new(vec![ 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0 ], 3, false));
+		geometry.set_attribute("normal", BufferAttribute::new(vec![ 0.0f32; 9 ], 3, false));
+		let version = geometry.get_attribute("normal").unwrap().get_version();
+
+		geometry.compute_vertex_normals();
+
+		let attribute = geometry.get_attribute("normal").unwrap();
+		assert!(attribute.as_any().downcast_ref::<BufferAttribute<f32>>().is_some());
+		assert!(attribute.get_version() > version);
+		assert_close(&normal(&geometry, 1), &Vector3 { x: 0.0, y: 0.0, z: 1.0 });
+	}
+
+	#[test]
+	fn writes_unit_normals_into_a_quantized_attribute() {
+		// large faces, the summed normals are far outside what a snorm8 can hold
+		let mut geometry = BufferGeometry::new();
+		geometry.set_attribute("position", BufferAttribute::new(vec![ 0.0, 0.0, 0.0, 20.0, - 10.0, 0.0, 0.0, 0.0, - 10.0 ], 3, false));
+		geometry.set_attribute("normal", BufferAttribute::new(vec![ 0i8; 9 ], 3, true));
+
+		geometry.compute_vertex_normals();
+
+		assert!(geometry.get_attribute("normal").unwrap().as_any().downcast_ref::<BufferAttribute<i8>>().is_some());
+
+		let mut expected = Vector3 { x: 1.0, y: 2.0, z: 0.0 };
+		expected.normalize();
+
+		for i in 0..3 {
+			let n = normal(&geometry, i);
+			assert!(n.distance_to(&expected) < 1e-2, "{:?}", n);
+		}
+	}
+}
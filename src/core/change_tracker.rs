@@ -0,0 +1,198 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use super::object3d::HasObject3D;
+use super::arena::{NodeId, SceneGraph};
+
+// The versions of objects, attributes, materials and textures are all stamped from this one
+// clock rather than counted per object, so a frame is just the clock reading at some point
+// and "changed since" is a comparison for any of them.
+static VERSION: AtomicU64 = AtomicU64::new(0);
+
+pub fn next_version() -> u64 {
+	VERSION.fetch_add(1, Ordering::Relaxed) + 1
+}
+
+pub fn current_version() -> u64 {
+	VERSION.load(Ordering::Relaxed)
+}
+
+// Collects the nodes whose transform or world matrix changed since a frame. Changes to the
+// transform show up right away, the world matrix only changes in update_matrix_world, and
+// only counts when the result is different.
+pub struct ChangeTracker {
+	frame: u64,
+}
+
+impl ChangeTracker {
+	pub fn new() -> ChangeTracker {
+		ChangeTracker {
+			frame: current_version(),
+		}
+	}
+
+	// The frame changes are collected since.
+	pub fn get_frame(&self) -> u64 {
+		self.frame
+	}
+
+	// Starts a new frame and returns the one that ended.
+	pub fn next_frame(&mut self) -> u64 {
+		let frame = self.frame;
+		self.frame = current_version();
+		frame
+	}
+
+	// The nodes changed since this tracker's frame, after which a new frame is started.
+	pub fn collect(&mut self, object: &Rc<RefCell<dyn HasObject3D>>) -> Vec<Rc<RefCell<dyn HasObject3D>>> {
+		let changed = ChangeTracker::changed_since(object, self.frame);
+		self.next_frame();
+		changed
+	}
+
+	// object and its descendants that changed after frame, in traversal order.
+	pub fn changed_since(object: &Rc<RefCell<dyn HasObject3D>>, frame: u64) -> Vec<Rc<RefCell<dyn HasObject3D>>> {
+		let mut result = vec![];
+		ChangeTracker::changed_since_into(object, frame, &mut result);
+		result
+	}
+
+	fn changed_since_into(object: &Rc<RefCell<dyn HasObject3D>>, frame: u64, result: &mut Vec<Rc<RefCell<dyn HasObject3D>>>) {
+		let o = object.borrow();
+		let o = o.get_object3d();
+
+		if o.changed_since(frame) {
+			result.push(object.clone());
+		}
+
+		for child in o.get_children() {
+			ChangeTracker::changed_since_into(child, frame, result);
+		}
+	}
+
+	pub fn collect_graph(&mut self, graph: &SceneGraph) -> Vec<NodeId> {
+		let changed = ChangeTracker::graph_changed_since(graph, self.frame);
+		self.next_frame();
+		changed
+	}
+
+	// The nodes of an arena graph that changed after frame, in slot order.
+	pub fn graph_changed_since(graph: &SceneGraph, frame: u64) -> Vec<NodeId> {
+		graph.iter().filter(|&(_, node)| node.changed_since(frame)).map(|(id, _)| id).collect()
+	}
+}
+
+impl Default for ChangeTracker {
+	fn default() -> ChangeTracker {
+		ChangeTracker::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use super::super::object3d::Object3D;
+	use super::super::scene_node::SceneNode;
+	use super::super::buffer_attribute::{Attribute, BufferAttribute};
+	use super::super::super::materials::material::Material;
+	use super::super::super::textures::texture::Texture;
+	use super::super::super::math::vector3::Vector3;
+
+	fn node() -> Rc<RefCell<dyn HasObject3D>> {
+		Rc::new(RefCell::new(Object3D::new()))
+	}
+
+	fn same(a: &[Rc<RefCell<dyn HasObject3D>>], b: &[&Rc<RefCell<dyn HasObject3D>>]) -> bool {
+		a.len() == b.len() && a.iter().zip(b).all(|(a, b)| Rc::ptr_eq(a, b))
+	}
+
+	#[test]
+	fn needs_update_stamps_increasing_versions() {
+		let mut material = Material::new();
+		let mut texture = Texture::new();
+		let mut attribute = BufferAttribute::new(vec![ 0.0f32; 3 ], 3, false);
+
+		for _ in 0..3 {
+			let frame = current_version();
+			let versions = [ material.get_version(), texture.get_version(), attribute.get_version() ];
+			assert!(versions.iter().all(|&version| version <= frame));
+
+			material.needs_update();
+			texture.needs_update();
+			attribute.needs_update();
+
+			assert!(material.get_version() > frame);
+			assert!(texture.get_version() > frame);
+			assert!(attribute.get_version() > frame);
+			assert!(material.get_version() > versions[ 0 ]);
+			assert!(texture.get_version() > versions[ 1 ]);
+			assert!(attribute.get_version() > versions[ 2 ]);
+		}
+	}
+
+	#[test]
+	fn transform_changes_reach_the_descendants() {
+		let root = node();
+		let child = node();
+		let grandchild = node();
+		Object3D::add(&root, &child);
+		Object3D::add(&child, &grandchild);
+		root.borrow_mut().get_object3d_mut().update_matrix_world(true);
+
+		let frame = current_version();
+		root.borrow_mut().get_object3d_mut().set_position(&Vector3 { x: 1.0, y: 0.0, z: 0.0 });
+
+		assert!(root.borrow().get_object3d().get_transform_version() > frame);
+		assert!(child.borrow().get_object3d().get_transform_version() <= frame);
+		assert!(same(&ChangeTracker::changed_since(&root, frame), &[ &root ]));
+
+		root.borrow_mut().get_object3d_mut().update_matrix_world(false);
+
+		for object in &[ &root, &child, &grandchild ] {
+			assert!(object.borrow().get_object3d().get_matrix_world_version() > frame);
+		}
+
+		assert!(same(&ChangeTracker::changed_since(&root, frame), &[ &root, &child, &grandchild ]));
+
+		// an update that gives the same matrices changes nothing
+		let frame = current_version();
+		root.borrow_mut().get_object3d_mut().update_matrix_world(true);
+		assert!(ChangeTracker::changed_since(&root, frame).is_empty());
+	}
+
+	#[test]
+	fn collect_starts_a_new_frame() {
+		let root = node();
+		let child = node();
+		Object3D::add(&root, &child);
+
+		let mut tracker = ChangeTracker::new();
+		assert!(tracker.collect(&root).is_empty());
+
+		child.borrow_mut().get_object3d_mut().set_position(&Vector3 { x: 0.0, y: 2.0, z: 0.0 });
+		let frame = tracker.get_frame();
+		assert!(same(&tracker.collect(&root), &[ &child ]));
+		assert!(tracker.get_frame() >= frame);
+		assert!(tracker.collect(&root).is_empty());
+	}
+
+	#[test]
+	fn collect_graph_finds_changed_nodes() {
+		let mut graph = SceneGraph::new();
+		let root = graph.insert(SceneNode::new());
+		let child = graph.insert(SceneNode::new());
+		graph.add(root, child);
+		graph.insert(SceneNode::new());
+		graph.update_matrix_world(true);
+
+		let mut tracker = ChangeTracker::new();
+		assert!(tracker.collect_graph(&graph).is_empty());
+
+		graph.get_mut(root).unwrap().set_position(&Vector3 { x: 0.0, y: 0.0, z: 3.0 });
+		assert_eq!(ChangeTracker::graph_changed_since(&graph, tracker.get_frame()), vec![ root ]);
+
+		graph.update_matrix_world(false);
+		assert_eq!(tracker.collect_graph(&graph), vec![ root, child ]);
+		assert!(tracker.collect_graph(&graph).is_empty());
+	}
+}
@@ -0,0 +1,310 @@
+use std::cell::Cell;
+use std::time::Instant;
+
+// Times are in seconds. They are kept as f64 so that long running clocks don't lose precision.
+
+pub trait TimeSource {
+	// Current time in seconds, measured from an arbitrary origin. Must never go backwards.
+	fn now(&self) -> f64;
+}
+
+impl<T: TimeSource> TimeSource for &T {
+	fn now(&self) -> f64 {
+		(**self).now()
+	}
+}
+
+// Real time, from the operating system's monotonic clock.
+#[derive(Debug, Clone, Copy)]
+pub struct MonotonicTimeSource {
+	origin: Instant,
+}
+
+impl MonotonicTimeSource {
+	pub fn new() -> MonotonicTimeSource {
+		MonotonicTimeSource {
+			origin: Instant::now(),
+		}
+	}
+}
+
+impl Default for MonotonicTimeSource {
+	fn default() -> MonotonicTimeSource {
+		MonotonicTimeSource::new()
+	}
+}
+
+impl TimeSource for MonotonicTimeSource {
+	fn now(&self) -> f64 {
+		let elapsed = self.origin.elapsed();
+		elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 * 1e-9
+	}
+}
+
+// Time that only moves when told to, for tests and deterministic simulations. With a
+// non-zero step every reading advances the time by that step after returning it.
+// Share it with a clock by reference to keep control of it from outside.
+#[derive(Debug, Clone)]
+pub struct ManualTimeSource {
+	time: Cell<f64>,
+	step: f64,
+}
+
+impl ManualTimeSource {
+	pub fn new() -> ManualTimeSource {
+		ManualTimeSource::fixed_step(0.0)
+	}
+
+	pub fn fixed_step(step: f64) -> ManualTimeSource {
+		ManualTimeSource {
+			time: Cell::new(0.0),
+			step,
+		}
+	}
+
+	pub fn get_step(&self) -> f64 {
+		self.step
+	}
+
+	pub fn set_step(&mut self, step: f64) {
+		self.step = step;
+	}
+
+	pub fn get_time(&self) -> f64 {
+		self.time.get()
+	}
+
+	pub fn set_time(&self, time: f64) {
+		self.time.set(time);
+	}
+
+	pub fn advance(&self, seconds: f64) {
+		self.time.set(self.time.get() + seconds);
+	}
+}
+
+impl Default for ManualTimeSource {
+	fn default() -> ManualTimeSource {
+		ManualTimeSource::new()
+	}
+}
+
+impl TimeSource for ManualTimeSource {
+	fn now(&self) -> f64 {
+		let time = self.time.get();
+		self.time.set(time + self.step);
+		time
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct Clock<T: TimeSource = MonotonicTimeSource> {
+	pub auto_start: bool,
+	start_time: f64,
+	old_time: f64,
+	elapsed_time: f64,
+	running: bool,
+	time_source: T,
+}
+
+impl Clock<MonotonicTimeSource> {
+	pub fn new(auto_start: bool) -> Clock<MonotonicTimeSource> {
+		Clock::with_time_source(MonotonicTimeSource::new(), auto_start)
+	}
+}
+
+impl Default for Clock<MonotonicTimeSource> {
+	fn default() -> Clock<MonotonicTimeSource> {
+		Clock::new(true)
+	}
+}
+
+impl<T: TimeSource> Clock<T> {
+	pub fn with_time_source(time_source: T, auto_start: bool) -> Clock<T> {
+		Clock {
+			auto_start,
+			start_time: 0.0,
+			old_time: 0.0,
+			elapsed_time: 0.0,
+			running: false,
+			time_source,
+		}
+	}
+
+	pub fn get_time_source(&self) -> &T {
+		&self.time_source
+	}
+
+	pub fn get_time_source_mut(&mut self) -> &mut T {
+		&mut self.time_source
+	}
+
+	pub fn is_running(&self) -> bool {
+		self.running
+	}
+
+	pub fn start(&mut self) {
+		self.start_time = self.time_source.now();
+
+		self.old_time = self.start_time;
+		self.elapsed_time = 0.0;
+		self.running = true;
+	}
+
+	pub fn stop(&mut self) {
+		self.get_elapsed_time();
+		self.running = false;
+		self.auto_start = false;
+	}
+
+	pub fn get_elapsed_time(&mut self) -> f64 {
+		self.get_delta();
+		self.elapsed_time
+	}
+
+	pub fn get_delta(&mut self) -> f64 {
+		let mut diff = 0.0;
+
+		if self.auto_start && !self.running {
+			self.start();
+			return 0.0;
+		}
+
+		if self.running {
+			let new_time = self.time_source.now();
+
+			diff = new_time - self.old_time;
+			self.old_time = new_time;
+
+			self.elapsed_time += diff;
+		}
+
+		diff
+	}
+}
+
+// Turns variable frame times into a whole number of fixed simulation steps, carrying the
+// remainder over to the next frame. At most max_steps are taken per frame; time beyond that
+// is dropped so a slow frame can't snowball into ever longer ones.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedTimestep {
+	pub step: f64,
+	pub max_steps: usize,
+	accumulator: f64,
+}
+
+impl FixedTimestep {
+	pub fn new(step: f64) -> FixedTimestep {
+		FixedTimestep {
+			step,
+			max_steps: 8,
+			accumulator: 0.0,
+		}
+	}
+
+	pub fn reset(&mut self) {
+		self.accumulator = 0.0;
+	}
+
+	// Adds the frame time and returns how many steps to simulate.
+	pub fn advance(&mut self, delta: f64) -> usize {
+		if self.step <= 0.0 {
+			return 0;
+		}
+
+		self.accumulator += delta.max(0.0);
+
+		let mut steps = 0;
+
+		while self.accumulator >= self.step && steps < self.max_steps {
+			self.accumulator -= self.step;
+			steps += 1;
+		}
+
+		if steps == self.max_steps && self.accumulator >= self.step {
+			self.accumulator %= self.step;
+		}
+
+		steps
+	}
+
+	// Calls update once per step with the step length, then returns the interpolation alpha.
+	pub fn update<F: FnMut(f64)>(&mut self, delta: f64, mut update: F) -> f64 {
+		for _ in 0..self.advance(delta) {
+			update(self.step);
+		}
+
+		self.get_alpha()
+	}
+
+	// How far the leftover time is into the next step, in [0, 1), for blending the last two states.
+	pub fn get_alpha(&self) -> f64 {
+		if self.step <= 0.0 {
+			0.0
+		} else {
+			self.accumulator / self.step
+		}
+	}
+
+	pub fn get_accumulator(&self) -> f64 {
+		self.accumulator
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn clock_follows_a_manual_time_source() {
+		let source = ManualTimeSource::new();
+		let mut clock = Clock::with_time_source(&source, true);
+
+		// the first reading starts the clock
+		assert_eq!(clock.get_delta(), 0.0);
+		assert!(clock.is_running());
+
+		source.advance(0.5);
+		assert_eq!(clock.get_delta(), 0.5);
+
+		source.advance(0.25);
+		assert_eq!(clock.get_elapsed_time(), 0.75);
+
+		clock.stop();
+		source.advance(1.0);
+		assert_eq!(clock.get_elapsed_time(), 0.75);
+		assert_eq!(clock.get_delta(), 0.0);
+	}
+
+	#[test]
+	fn manual_time_source_steps_on_every_reading() {
+		let mut clock = Clock::with_time_source(ManualTimeSource::fixed_step(0.125), false);
+		clock.start();
+
+		assert_eq!(clock.get_delta(), 0.125);
+		assert_eq!(clock.get_delta(), 0.125);
+		assert_eq!(clock.get_elapsed_time(), 0.375);
+	}
+
+	#[test]
+	fn fixed_timestep_clamps_to_max_steps() {
+		let mut timestep = FixedTimestep::new(0.25);
+		timestep.max_steps = 3;
+
+		assert_eq!(timestep.advance(0.6), 2);
+		assert!(( timestep.get_accumulator() - 0.1 ).abs() < 1e-9);
+
+		// a long frame takes max_steps and drops whole steps beyond that
+		assert_eq!(timestep.advance(2.0), 3);
+		assert!(timestep.get_accumulator() < timestep.step);
+		assert!(( timestep.get_accumulator() - 0.1 ).abs() < 1e-9);
+
+		let mut steps = 0;
+		let alpha = timestep.update(0.15, |step| {
+			assert_eq!(step, 0.25);
+			steps += 1;
+		});
+		assert_eq!(steps, 1);
+		assert!(alpha.abs() < 1e-9);
+	}
+}
@@ -0,0 +1,157 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+// Anything that can be stored in Components. Implemented for every 'static Clone type.
+pub trait Component: Any {
+	fn clone_box(&self) -> Box<dyn Component>;
+	fn as_any(&self) -> &dyn Any;
+	fn as_any_mut(&mut self) -> &mut dyn Any;
+	fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T: Any + Clone> Component for T {
+	fn clone_box(&self) -> Box<dyn Component> {
+		Box::new(self.clone())
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+
+	fn as_any_mut(&mut self) -> &mut dyn Any {
+		self
+	}
+
+	fn into_any(self: Box<Self>) -> Box<dyn Any> {
+		self
+	}
+}
+
+// Holds at most one value per type, for app state that belongs to a node.
+pub struct Components {
+	map: HashMap<TypeId, Box<dyn Component>>,
+}
+
+impl Components {
+	pub fn new() -> Components {
+		Components {
+			map: HashMap::new(),
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+
+	// Returns the value of the same type that was stored before, if any.
+	pub fn insert<T: Any + Clone>(&mut self, value: T) -> Option<T> {
+		self.map.insert(TypeId::of::<T>(), Box::new(value)).and_then(downcast)
+	}
+
+	pub fn get<T: Any + Clone>(&self) -> Option<&T> {
+		self.map.get(&TypeId::of::<T>()).and_then(|value| ( **value ).as_any().downcast_ref::<T>())
+	}
+
+	pub fn get_mut<T: Any + Clone>(&mut self) -> Option<&mut T> {
+		self.map.get_mut(&TypeId::of::<T>()).and_then(|value| ( **value ).as_any_mut().downcast_mut::<T>())
+	}
+
+	pub fn remove<T: Any + Clone>(&mut self) -> Option<T> {
+		self.map.remove(&TypeId::of::<T>()).and_then(downcast)
+	}
+
+	pub fn has<T: Any + Clone>(&self) -> bool {
+		self.map.contains_key(&TypeId::of::<T>())
+	}
+
+	pub fn clear(&mut self) {
+		self.map.clear();
+	}
+}
+
+impl Clone for Components {
+	fn clone(&self) -> Components {
+		Components {
+			map: self.map.iter().map(|(key, value)| (*key, ( **value ).clone_box())).collect(),
+		}
+	}
+}
+
+impl Default for Components {
+	fn default() -> Components {
+		Components::new()
+	}
+}
+
+fn downcast<T: Any>(value: Box<dyn Component>) -> Option<T> {
+	value.into_any().downcast::<T>().ok().map(|value| *value)
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Debug, Clone, PartialEq)]
+	struct Health(u32);
+
+	#[derive(Debug, Clone, PartialEq)]
+	struct Tags(Vec<String>);
+
+	#[test]
+	fn keeps_one_value_per_type() {
+		let mut components = Components::new();
+		assert!(components.is_empty());
+
+		assert_eq!(components.insert(Health(10)), None);
+		assert_eq!(components.insert(Tags(vec![ "enemy".to_string() ])), None);
+		assert_eq!(components.len(), 2);
+
+		// a second value of the same type replaces the first and hands it back
+		assert_eq!(components.insert(Health(20)), Some(Health(10)));
+		assert_eq!(components.len(), 2);
+		assert_eq!(components.get::<Health>(), Some(&Health(20)));
+
+		// types are told apart even when they hold the same data
+		components.insert(7u32);
+		assert_eq!(components.get::<u32>(), Some(&7));
+		assert!(!components.has::<i32>());
+		assert_eq!(components.get::<i32>(), None);
+	}
+
+	#[test]
+	fn get_mut_and_remove() {
+		let mut components = Components::new();
+		components.insert(Health(10));
+
+		components.get_mut::<Health>().unwrap().0 -= 3;
+		assert_eq!(components.get::<Health>(), Some(&Health(7)));
+		assert!(components.get_mut::<Tags>().is_none());
+
+		assert_eq!(components.remove::<Health>(), Some(Health(7)));
+		assert_eq!(components.remove::<Health>(), None);
+		assert!(!components.has::<Health>());
+		assert!(components.is_empty());
+
+		components.insert(Health(1));
+		components.insert(1.5f32);
+		components.clear();
+		assert!(components.is_empty());
+	}
+
+	#[test]
+	fn clones_are_deep() {
+		let mut components = Components::new();
+		components.insert(Tags(vec![ "a".to_string() ]));
+
+		let mut copy = components.clone();
+		copy.get_mut::<Tags>().unwrap().0.push("b".to_string());
+
+		assert_eq!(components.get::<Tags>(), Some(&Tags(vec![ "a".to_string() ])));
+		assert_eq!(copy.get::<Tags>().unwrap().0.len(), 2);
+	}
+}
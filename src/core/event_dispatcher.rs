@@ -0,0 +1,189 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+pub struct Event<'a> {
+	pub event_type: &'a str,
+	pub data: Option<&'a dyn Any>,
+}
+
+impl<'a> Event<'a> {
+	pub fn new(event_type: &'a str) -> Event<'a> {
+		Event {
+			event_type,
+			data: None,
+		}
+	}
+
+	pub fn with_data(event_type: &'a str, data: &'a dyn Any) -> Event<'a> {
+		Event {
+			event_type,
+			data: Some(data),
+		}
+	}
+
+	// The payload, if there is one and it has type T.
+	pub fn get_data<T: Any>(&self) -> Option<&'a T> {
+		self.data.and_then(|data| data.downcast_ref::<T>())
+	}
+}
+
+pub type Listener = Rc<dyn Fn(&Event)>;
+
+// Listeners are told apart by the Rc they were added with, keep a clone of it around to
+// remove them again.
+pub struct EventDispatcher {
+	listeners: HashMap<String, Vec<Listener>>,
+}
+
+impl EventDispatcher {
+	pub fn new() -> EventDispatcher {
+		EventDispatcher {
+			listeners: HashMap::new(),
+		}
+	}
+
+	pub fn add_event_listener(&mut self, event_type: &str, listener: &Listener) {
+		let listeners = self.listeners.entry(event_type.to_string()).or_default();
+
+		if !listeners.iter().any(|l| same_listener(l, listener)) {
+			listeners.push(listener.clone());
+		}
+	}
+
+	pub fn has_event_listener(&self, event_type: &str, listener: &Listener) -> bool {
+		match self.listeners.get(event_type) {
+			Some(listeners) => listeners.iter().any(|l| same_listener(l, listener)),
+			None => false,
+		}
+	}
+
+	pub fn remove_event_listener(&mut self, event_type: &str, listener: &Listener) {
+		let empty = match self.listeners.get_mut(event_type) {
+			Some(listeners) => {
+				listeners.retain(|l| !same_listener(l, listener));
+				listeners.is_empty()
+			},
+			None => false,
+		};
+
+		if empty {
+			self.listeners.remove(event_type);
+		}
+	}
+
+	// Calls the listeners in the order they were added.
+	pub fn dispatch_event(&self, event: &Event) {
+		if let Some(listeners) = self.listeners.get(event.event_type) {
+			for listener in listeners {
+				listener(event);
+			}
+		}
+	}
+}
+
+// Listeners belong to whoever registered them, a copy of the owner starts without any.
+impl Clone for EventDispatcher {
+	fn clone(&self) -> EventDispatcher {
+		EventDispatcher::new()
+	}
+}
+
+impl Default for EventDispatcher {
+	fn default() -> EventDispatcher {
+		EventDispatcher::new()
+	}
+}
+
+// Compares the closures only, the vtable part of the fat pointer isn't guaranteed to be unique.
+fn same_listener(a: &Listener, b: &Listener) -> bool {
+	Rc::as_ptr(a) as *const () == Rc::as_ptr(b) as *const ()
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::cell::RefCell;
+
+	fn counter(count: &Rc<RefCell<usize>>) -> Listener {
+		let count = count.clone();
+		Rc::new(move |_: &Event| *count.borrow_mut() += 1)
+	}
+
+	#[test]
+	fn listeners_are_called_once_per_dispatch() {
+		let mut dispatcher = EventDispatcher::new();
+		let count = Rc::new(RefCell::new(0));
+		let listener = counter(&count);
+
+		// adding the same listener twice keeps one
+		dispatcher.add_event_listener("change", &listener);
+		dispatcher.add_event_listener("change", &listener);
+		assert!(dispatcher.has_event_listener("change", &listener));
+		assert!(!dispatcher.has_event_listener("other", &listener));
+
+		dispatcher.dispatch_event(&Event::new("change"));
+		dispatcher.dispatch_event(&Event::new("other"));
+		assert_eq!(*count.borrow(), 1);
+
+		dispatcher.remove_event_listener("change", &listener);
+		assert!(!dispatcher.has_event_listener("change", &listener));
+		dispatcher.dispatch_event(&Event::new("change"));
+		assert_eq!(*count.borrow(), 1);
+	}
+
+	#[test]
+	fn listeners_are_told_apart_by_their_rc() {
+		let mut dispatcher = EventDispatcher::new();
+		let count = Rc::new(RefCell::new(0));
+		let a = counter(&count);
+		let b = counter(&count);
+
+		dispatcher.add_event_listener("change", &a);
+		dispatcher.add_event_listener("change", &b);
+		dispatcher.add_event_listener("change", &a.clone());
+		dispatcher.dispatch_event(&Event::new("change"));
+		assert_eq!(*count.borrow(), 2);
+
+		dispatcher.remove_event_listener("change", &a);
+		assert!(dispatcher.has_event_listener("change", &b));
+		dispatcher.dispatch_event(&Event::new("change"));
+		assert_eq!(*count.borrow(), 3);
+	}
+
+	#[test]
+	fn listeners_run_in_order_and_see_the_data() {
+		let mut dispatcher = EventDispatcher::new();
+		let seen = Rc::new(RefCell::new(vec![]));
+
+		for i in 0..3 {
+			let seen = seen.clone();
+			let listener: Listener = Rc::new(move |event: &Event| {
+				assert_eq!(event.event_type, "change");
+				seen.borrow_mut().push((i, event.get_data::<u32>().copied()));
+			});
+			dispatcher.add_event_listener("change", &listener);
+		}
+
+		dispatcher.dispatch_event(&Event::with_data("change", &7u32));
+		dispatcher.dispatch_event(&Event::new("change"));
+		assert_eq!(*seen.borrow(), vec![ (0, Some(7)), (1, Some(7)), (2, Some(7)), (0, None), (1, None), (2, None) ]);
+
+		// a payload of another type reads as none
+		assert!(Event::with_data("change", &7u32).get_data::<i32>().is_none());
+	}
+
+	#[test]
+	fn clones_start_without_listeners() {
+		let mut dispatcher = EventDispatcher::new();
+		let count = Rc::new(RefCell::new(0));
+		let listener = counter(&count);
+		dispatcher.add_event_listener("change", &listener);
+
+		let copy = dispatcher.clone();
+		assert!(!copy.has_event_listener("change", &listener));
+		copy.dispatch_event(&Event::new("change"));
+		assert_eq!(*count.borrow(), 0);
+	}
+}
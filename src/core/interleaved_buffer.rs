@@ -0,0 +1,66 @@
+use super::buffer_attribute::{BufferElement, UpdateRange};
+use super::change_tracker::next_version;
+
+// Several attributes packed into one array, stride elements per vertex. Share it between
+// InterleavedBufferAttributes through an Rc<RefCell<..>>.
+#[derive(Debug, Clone)]
+pub struct InterleavedBuffer<T: BufferElement = f32> {
+	pub array: Vec<T>,
+	pub stride: usize,
+	update_ranges: Vec<UpdateRange>,
+	version: u64,
+}
+
+impl<T: BufferElement> InterleavedBuffer<T> {
+	pub fn new(array: Vec<T>, stride: usize) -> InterleavedBuffer<T> {
+		assert!(stride > 0, "stride must be at least 1");
+
+		InterleavedBuffer {
+			array,
+			stride,
+			update_ranges: vec![],
+			version: next_version(),
+		}
+	}
+
+	pub fn get_count(&self) -> usize {
+		self.array.len() / self.stride
+	}
+
+	// Copies values into the array, starting at element offset.
+	pub fn set(&mut self, values: &[T], offset: Option<usize>) {
+		let offset = offset.unwrap_or(0);
+		self.array[ offset .. offset + values.len() ].copy_from_slice(values);
+	}
+
+	// Copies one vertex, all of its attributes, from another buffer with the same stride.
+	pub fn copy_at(&mut self, index1: usize, buffer: &InterleavedBuffer<T>, index2: usize) {
+		let index1 = index1 * self.stride;
+		let index2 = index2 * buffer.stride;
+
+		self.array[ index1 .. index1 + self.stride ].copy_from_slice(&buffer.array[ index2 .. index2 + self.stride ]);
+	}
+
+	pub fn get_version(&self) -> u64 {
+		self.version
+	}
+
+	pub fn needs_update(&mut self) {
+		self.version = next_version();
+	}
+
+	pub fn add_update_range(&mut self, start: usize, count: usize) {
+		self.update_ranges.push(UpdateRange {
+			start,
+			count,
+		});
+	}
+
+	pub fn clear_update_ranges(&mut self) {
+		self.update_ranges.clear();
+	}
+
+	pub fn get_update_ranges(&self) -> &[UpdateRange] {
+		&self.update_ranges
+	}
+}
@@ -0,0 +1,153 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+use super::buffer_attribute::{Attribute, BufferAttribute, BufferElement};
+use super::interleaved_buffer::InterleavedBuffer;
+
+// One attribute of an InterleavedBuffer: item_size elements starting offset elements into
+// each vertex. The version is the buffer's, shared by all of its attributes.
+#[derive(Debug, Clone)]
+pub struct InterleavedBufferAttribute<T: BufferElement = f32> {
+	pub name: String,
+	pub data: Rc<RefCell<InterleavedBuffer<T>>>,
+	pub item_size: usize,
+	pub offset: usize,
+	pub normalized: bool,
+}
+
+impl<T: BufferElement> InterleavedBufferAttribute<T> {
+	pub fn new(data: &Rc<RefCell<InterleavedBuffer<T>>>, item_size: usize, offset: usize, normalized: bool) -> InterleavedBufferAttribute<T> {
+		assert!(item_size > 0, "item_size must be at least 1");
+		assert!(offset + item_size <= data.borrow().stride, "offset + item_size must fit in the stride");
+
+		InterleavedBufferAttribute {
+			name: String::new(),
+			data: data.clone(),
+			item_size,
+			offset,
+			normalized,
+		}
+	}
+
+	pub fn get_count(&self) -> usize {
+		self.data.borrow().get_count()
+	}
+
+	// A plain attribute with a copy of just this attribute's data.
+	pub fn to_buffer_attribute(&self) -> BufferAttribute<T> {
+		let data = self.data.borrow();
+		let mut array = Vec::with_capacity(data.get_count() * self.item_size);
+
+		for i in 0..data.get_count() {
+			let start = i * data.stride + self.offset;
+			array.extend_from_slice(&data.array[ start .. start + self.item_size ]);
+		}
+
+		let mut attribute = BufferAttribute::new(array, self.item_size, self.normalized);
+		attribute.name = self.name.clone();
+		attribute
+	}
+}
+
+impl<T: BufferElement> Attribute for InterleavedBufferAttribute<T> {
+	fn get_item_size(&self) -> usize {
+		self.item_size
+	}
+
+	fn get_count(&self) -> usize {
+		InterleavedBufferAttribute::get_count(self)
+	}
+
+	fn is_normalized(&self) -> bool {
+		self.normalized
+	}
+
+	// A component past item_size would read the next attribute in the vertex.
+	fn get_component(&self, index: usize, component: usize) -> f32 {
+		assert!(component < self.item_size, "component {} is out of range for item_size {}", component, self.item_size);
+		let data = self.data.borrow();
+		data.array[ index * data.stride + self.offset + component ].to_f32(self.normalized)
+	}
+
+	fn set_component(&mut self, index: usize, component: usize, value: f32) {
+		assert!(component < self.item_size, "component {} is out of range for item_size {}", component, self.item_size);
+		let mut data = self.data.borrow_mut();
+		let stride = data.stride;
+		data.array[ index * stride + self.offset + component ] = T::from_f32(value, self.normalized);
+	}
+
+	fn get_version(&self) -> u64 {
+		self.data.borrow().get_version()
+	}
+
+	fn needs_update(&mut self) {
+		self.data.borrow_mut().needs_update();
+	}
+
+	fn is_interleaved(&self) -> bool {
+		true
+	}
+
+	// De-interleaves, so the copy doesn't share the buffer with the original. Clone keeps
+	// sharing it.
+	fn clone_attribute(&self) -> Box<dyn Attribute> {
+		Box::new(self.to_buffer_attribute())
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+
+	fn as_any_mut(&mut self) -> &mut dyn Any {
+		self
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// position xyz and uv in one buffer, two vertices.
+	fn buffer() -> Rc<RefCell<InterleavedBuffer>> {
+		Rc::new(RefCell::new(InterleavedBuffer::new(vec![
+			1.0, 2.0, 3.0, 0.0, 1.0,
+			4.0, 5.0, 6.0, 1.0, 0.0,
+		], 5)))
+	}
+
+	#[test]
+	fn reads_and_writes_its_own_components() {
+		let data = buffer();
+		let position = InterleavedBufferAttribute::new(&data, 3, 0, false);
+		let mut uv = InterleavedBufferAttribute::new(&data, 2, 3, false);
+
+		assert_eq!(position.get_count(), 2);
+		assert_eq!(position.get_z(1), 6.0);
+		assert_eq!(uv.get_x(1), 1.0);
+
+		uv.set_xy(0, 0.5, 0.25);
+		assert_eq!(data.borrow().array[ 3 .. 5 ], [ 0.5, 0.25 ]);
+		assert_eq!(position.to_buffer_attribute().array, vec![ 1.0, 2.0, 3.0, 4.0, 5.0, 6.0 ]);
+	}
+
+	#[test]
+	#[should_panic]
+	fn rejects_an_attribute_past_the_stride() {
+		InterleavedBufferAttribute::new(&buffer(), 3, 3, false);
+	}
+
+	#[test]
+	#[should_panic]
+	fn get_rejects_components_past_item_size() {
+		let uv = InterleavedBufferAttribute::new(&buffer(), 2, 3, false);
+		uv.get_z(0);
+	}
+
+	#[test]
+	#[should_panic]
+	fn set_rejects_components_past_item_size() {
+		let mut position = InterleavedBufferAttribute::new(&buffer(), 3, 0, false);
+		position.set_w(0, 1.0);
+	}
+}
@@ -1,3 +1,23 @@
+use std::error::Error;
+use std::fmt;
+
+pub const LAYER_COUNT: u64 = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayersError {
+	ChannelOutOfRange(u64),
+}
+
+impl fmt::Display for LayersError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			LayersError::ChannelOutOfRange(channel) => write!(f, "layer channel {} is out of range, channels go from 0 to {}", channel, LAYER_COUNT - 1),
+		}
+	}
+}
+
+impl Error for LayersError {}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Layers {
 	pub mask: u64,
@@ -10,23 +30,115 @@ impl Layers {
 		}
 	}
 
-	pub fn set(&mut self, channel: u64) {
-		self.mask = 1u64 << channel;
+	fn channel_mask(channel: u64) -> Result<u64, LayersError> {
+		if channel < LAYER_COUNT {
+			Ok(1u64 << channel)
+		} else {
+			Err(LayersError::ChannelOutOfRange(channel))
+		}
 	}
 
-	pub fn enable(&mut self, channel: u64) {
-		self.mask |= 1u64 << channel;
+	pub fn set(&mut self, channel: u64) -> Result<(), LayersError> {
+		self.mask = Layers::channel_mask(channel)?;
+		Ok(())
 	}
 
-	pub fn toggle(&mut self, channel: u64) {
-		self.mask ^= 1u64 << channel;
+	pub fn enable(&mut self, channel: u64) -> Result<(), LayersError> {
+		self.mask |= Layers::channel_mask(channel)?;
+		Ok(())
 	}
 
-	pub fn disable(&mut self, channel: u64) {
-		self.mask &= ! (1u64 << channel);
+	pub fn enable_all(&mut self) {
+		self.mask = !0u64;
+	}
+
+	pub fn toggle(&mut self, channel: u64) -> Result<(), LayersError> {
+		self.mask ^= Layers::channel_mask(channel)?;
+		Ok(())
+	}
+
+	pub fn disable(&mut self, channel: u64) -> Result<(), LayersError> {
+		self.mask &= ! Layers::channel_mask(channel)?;
+		Ok(())
+	}
+
+	pub fn disable_all(&mut self) {
+		self.mask = 0u64;
 	}
 
 	pub fn test(&self, layers: &Layers) -> bool {
 		(self.mask & layers.mask) != 0u64
 	}
-}
\ No newline at end of file
+
+	// Channels out of range are never enabled.
+	pub fn is_enabled(&self, channel: u64) -> bool {
+		match Layers::channel_mask(channel) {
+			Ok(mask) => (self.mask & mask) != 0u64,
+			Err(_) => false,
+		}
+	}
+}
+
+impl Default for Layers {
+	fn default() -> Layers {
+		Layers::new()
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn every_channel_in_range_can_be_set() {
+		let mut layers = Layers::new();
+
+		for channel in 0..LAYER_COUNT {
+			layers.set(channel).unwrap();
+			assert_eq!(layers.mask, 1u64 << channel);
+			assert!(layers.is_enabled(channel));
+		}
+
+		layers.enable(0).unwrap();
+		layers.toggle(LAYER_COUNT - 1).unwrap();
+		layers.disable(0).unwrap();
+		assert_eq!(layers.mask, 0u64);
+	}
+
+	#[test]
+	fn channels_out_of_range_are_errors() {
+		let mut layers = Layers::new();
+
+		for channel in [ LAYER_COUNT, LAYER_COUNT + 1, u64::MAX ] {
+			let error = Err(LayersError::ChannelOutOfRange(channel));
+			assert_eq!(layers.set(channel), error);
+			assert_eq!(layers.enable(channel), error);
+			assert_eq!(layers.toggle(channel), error);
+			assert_eq!(layers.disable(channel), error);
+			assert!(!layers.is_enabled(channel));
+		}
+
+		// a failed call leaves the mask alone
+		assert_eq!(layers.mask, 1u64);
+
+		layers.enable_all();
+		assert!(!layers.is_enabled(LAYER_COUNT));
+	}
+
+	#[test]
+	fn test_needs_a_common_channel() {
+		let mut a = Layers::new();
+		let mut b = Layers::new();
+		assert!(a.test(&b));
+
+		b.set(5).unwrap();
+		assert!(!a.test(&b));
+
+		a.enable(5).unwrap();
+		assert!(a.test(&b));
+
+		a.disable_all();
+		assert!(!a.test(&b));
+	}
+}
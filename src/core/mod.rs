@@ -1,3 +1,15 @@
+pub mod arena;
+pub mod buffer_attribute;
+pub mod buffer_geometry;
+pub mod change_tracker;
 pub mod clock;
+pub mod components;
+pub mod event_dispatcher;
+pub mod interleaved_buffer;
+pub mod interleaved_buffer_attribute;
 pub mod layers;
-pub mod object3d;
\ No newline at end of file
+pub mod object3d;
+pub mod raycaster;
+pub mod scene_node;
+mod transform;
+pub mod user_data;
\ No newline at end of file
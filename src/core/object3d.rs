@@ -1,7 +1,9 @@
 extern crate uuid;
 use std::rc::{Rc, Weak};
+use std::any::Any;
 use std::cell::{Ref, RefMut, RefCell};
 use std::cmp::{Eq, PartialEq};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use self::uuid::Uuid;
 use super::super::math::vector3::Vector3;
 use super::super::math::quaternion::Quaternion;
@@ -9,6 +11,14 @@ use super::super::math::matrix4::Matrix4;
 use super::super::math::matrix3::Matrix3;
 use super::super::math::euler::Euler;
 use super::layers::Layers;
+use super::raycaster::{Raycaster, Intersection};
+use super::super::cameras::camera::Camera;
+use super::event_dispatcher::{Event, EventDispatcher, Listener};
+use super::components::Components;
+use super::user_data::{UserData, write_json_string};
+use super::change_tracker::next_version;
+use super::transform;
+use std::fmt::Write;
 
 pub static mut DEFAULT_UP: Vector3 = Vector3 {
 	x: 0.0,
@@ -17,15 +27,30 @@ pub static mut DEFAULT_UP: Vector3 = Vector3 {
 };
 pub static mut DEFAULT_MATRIX_AUTO_UPDATE: bool = true;
 
+static OBJECT3D_ID: AtomicUsize = AtomicUsize::new(0);
+
 pub trait HasObject3D {
 	fn get_object3d(&self) -> &Object3D;
 	fn get_object3d_mut(&mut self) -> &mut Object3D;
+
+	// A copy of this node alone, see Object3D::clone_detached. Types that wrap an Object3D
+	// should override this, the default only copies the Object3D part.
+	fn clone_node(&self) -> Rc<RefCell<dyn HasObject3D>> {
+		Rc::new(RefCell::new(self.get_object3d().clone_detached()))
+	}
+
+	// Adds the places where the raycaster's ray hits this object to intersects. object is the
+	// Rc this is borrowed from, for the hits to refer to. Plain objects can't be hit, types
+	// with geometry implement this, usually with the helpers on Raycaster.
+	fn raycast(&self, _raycaster: &Raycaster, _object: &Rc<RefCell<dyn HasObject3D>>, _intersects: &mut Vec<Intersection>) {
+	}
 }
 
 #[derive(Clone)]
 pub struct Object3D {
+	id: usize,
 	uuid: Uuid,
-	name: &'static str,
+	name: String,
 	children: Vec<Rc<RefCell<HasObject3D>>>,
 	up: Vector3,
 	position: Vector3,
@@ -37,6 +62,8 @@ pub struct Object3D {
 	normal_matrix: Matrix3,
 	matrix: Matrix4,
 	matrix_world: Matrix4,
+	transform_version: u64,
+	matrix_world_version: u64,
 	layers: Layers,
 	visible: bool,
 	cast_shadow: bool,
@@ -44,6 +71,9 @@ pub struct Object3D {
 	frustum_culled: bool,
 	render_order: u32,
 	parent: Option<Weak<RefCell<HasObject3D>>>,
+	dispatcher: EventDispatcher,
+	components: Components,
+	user_data: UserData,
 }
 
 impl HasObject3D for Object3D {
@@ -65,8 +95,9 @@ impl PartialEq for Object3D {
 impl Object3D {
 	pub fn new() -> Object3D {
 		Object3D {
+			id: OBJECT3D_ID.fetch_add(1, Ordering::Relaxed),
 			uuid: Uuid::new_v4(),
-			name: "",
+			name: String::new(),
 			children: vec![],
 			up: unsafe {DEFAULT_UP},
 			position: Vector3::new(),
@@ -80,6 +111,8 @@ impl Object3D {
 			normal_matrix: Matrix3::new(),
 			matrix: Matrix4::new(),
 			matrix_world: Matrix4::new(),
+			transform_version: next_version(),
+			matrix_world_version: next_version(),
 			matrix_auto_update: unsafe {DEFAULT_MATRIX_AUTO_UPDATE},
 			matrix_world_needs_update: false,
 			layers: Layers::new(),
@@ -89,10 +122,126 @@ impl Object3D {
 			frustum_culled: true,
 			render_order: 0,
 			parent: Option::None,
+			dispatcher: EventDispatcher::new(),
+			components: Components::new(),
+			user_data: UserData::new(),
 		}
 	}
 
+	pub fn get_id(&self) -> usize {
+		self.id
+	}
+
+	pub fn get_uuid(&self) -> &Uuid {
+		&self.uuid
+	}
+
+	pub fn get_name(&self) -> &str {
+		&self.name
+	}
+
+	pub fn set_name(&mut self, name: &str) {
+		self.name = name.to_string();
+	}
+
+	pub fn is_visible(&self) -> bool {
+		self.visible
+	}
+
+	pub fn set_visible(&mut self, visible: bool) {
+		self.visible = visible;
+	}
+
+	pub fn get_cast_shadow(&self) -> bool {
+		self.cast_shadow
+	}
+
+	pub fn set_cast_shadow(&mut self, cast_shadow: bool) {
+		self.cast_shadow = cast_shadow;
+	}
+
+	pub fn get_receive_shadow(&self) -> bool {
+		self.receive_shadow
+	}
+
+	pub fn set_receive_shadow(&mut self, receive_shadow: bool) {
+		self.receive_shadow = receive_shadow;
+	}
+
+	pub fn get_frustum_culled(&self) -> bool {
+		self.frustum_culled
+	}
+
+	pub fn set_frustum_culled(&mut self, frustum_culled: bool) {
+		self.frustum_culled = frustum_culled;
+	}
+
+	pub fn get_render_order(&self) -> u32 {
+		self.render_order
+	}
+
+	pub fn set_render_order(&mut self, render_order: u32) {
+		self.render_order = render_order;
+	}
+
+	pub fn get_layers(&self) -> &Layers {
+		&self.layers
+	}
+
+	pub fn get_layers_mut(&mut self) -> &mut Layers {
+		&mut self.layers
+	}
+
+	pub fn get_components(&self) -> &Components {
+		&self.components
+	}
+
+	pub fn get_components_mut(&mut self) -> &mut Components {
+		&mut self.components
+	}
+
+	// Shortcuts to the components. remove is taken by the children, so that one is
+	// remove_component.
+	pub fn insert<T: Any + Clone>(&mut self, value: T) -> Option<T> {
+		self.components.insert(value)
+	}
+
+	pub fn get<T: Any + Clone>(&self) -> Option<&T> {
+		self.components.get::<T>()
+	}
+
+	pub fn get_mut<T: Any + Clone>(&mut self) -> Option<&mut T> {
+		self.components.get_mut::<T>()
+	}
+
+	pub fn remove_component<T: Any + Clone>(&mut self) -> Option<T> {
+		self.components.remove::<T>()
+	}
+
+	pub fn has<T: Any + Clone>(&self) -> bool {
+		self.components.has::<T>()
+	}
+
+	pub fn get_user_data(&self) -> &UserData {
+		&self.user_data
+	}
+
+	pub fn get_user_data_mut(&mut self) -> &mut UserData {
+		&mut self.user_data
+	}
+
+	pub fn set_user_data(&mut self, user_data: UserData) {
+		self.user_data = user_data;
+	}
+
+	// Object3D has no shear, so any shear in the product is dropped on purpose: it is split
+	// into position, quaternion and scale by decompose. Keep such a matrix with set_matrix
+	// and matrix_auto_update off, or see Matrix4::decompose_with_shear.
 	pub fn apply_matrix(&mut self, matrix: &Matrix4) {
+		if self.matrix_auto_update {
+			self.update_matrix();
+		}
+
 		let m = self.matrix;
 		self.matrix.multiply_matrices(matrix, &m);
 		let mut p = self.position;
@@ -102,28 +251,32 @@ impl Object3D {
 		self.position = p;
 		self.quaternion = q;
 		self.scale = s;
+		self.transform_needs_update();
 	}
 
 	pub fn set_rotation_from_axis_angle(&mut self, axis: &Vector3, angle: f32) {
 		self.quaternion.set_from_axis_angle(axis, angle);
+		self.transform_needs_update();
 	}
 
 	pub fn set_rotation_from_euler(&mut self, euler: &Euler) {
 		self.quaternion.set_from_euler(euler);
+		self.transform_needs_update();
 	}
 
 	pub fn set_rotation_from_matrix(&mut self, m: &Matrix4) {
 		self.quaternion.set_from_rotation_matrix(m);
+		self.transform_needs_update();
 	}
 
 	pub fn set_rotation_from_quaternion(&mut self, q: &Quaternion) {
 		self.quaternion.copy(q);
+		self.transform_needs_update();
 	}
 
 	pub fn rotate_on_axis(&mut self, axis: &Vector3, angle: f32) {
-		let mut q1 = Quaternion::new();
-		q1.set_from_axis_angle(axis, angle);
-		self.quaternion.multiply(&q1);
+		transform::rotate_on_axis(&mut self.quaternion, axis, angle);
+		self.transform_needs_update();
 	}
 
 	pub fn rotate_x(&mut self, angle: f32) {
@@ -154,11 +307,8 @@ impl Object3D {
 	}
 
 	pub fn translate_on_axis(&mut self, axis: &Vector3, distance: f32) {
-		let mut v1 = Vector3::new();
-		v1.copy(axis);
-		v1.apply_quaternion(&self.quaternion);
-		v1.multiply_scalar(distance);
-		self.position.add(&v1);
+		transform::translate_on_axis(&mut self.position, &self.quaternion, axis, distance);
+		self.transform_needs_update();
 	}
 
 	pub fn translate_x(&mut self, distance: f32) {
@@ -188,6 +338,242 @@ impl Object3D {
 		self.translate_on_axis(&v1, distance);
 	}
 
+	pub fn get_up(&self) -> &Vector3 {
+		&self.up
+	}
+
+	pub fn set_up(&mut self, up: &Vector3) {
+		self.up.copy(up);
+	}
+
+	pub fn get_position(&self) -> &Vector3 {
+		&self.position
+	}
+
+	pub fn set_position(&mut self, position: &Vector3) {
+		self.position.copy(position);
+		self.transform_needs_update();
+	}
+
+	pub fn get_quaternion(&self) -> &Quaternion {
+		&self.quaternion
+	}
+
+	pub fn set_quaternion(&mut self, quaternion: &Quaternion) {
+		self.quaternion.copy(quaternion);
+		self.transform_needs_update();
+	}
+
+	pub fn get_rotation(&self, target: &mut Euler) {
+		target.set_from_quaternion(&self.quaternion, None);
+	}
+
+	pub fn set_rotation(&mut self, euler: &Euler) {
+		self.quaternion.set_from_euler(euler);
+		self.transform_needs_update();
+	}
+
+	pub fn get_scale(&self) -> &Vector3 {
+		&self.scale
+	}
+
+	pub fn set_scale(&mut self, scale: &Vector3) {
+		self.scale.copy(scale);
+		self.transform_needs_update();
+	}
+
+	// The world queries bring the matrices of this object and its ancestors up to date first,
+	// so none of them may be borrowed at the time.
+
+	pub fn get_world_position(&mut self, target: &mut Vector3) {
+		self.update_world_matrix(true, false);
+		target.set_from_matrix_position(&self.matrix_world);
+	}
+
+	pub fn get_world_quaternion(&mut self, target: &mut Quaternion) {
+		let mut position = Vector3::new();
+		let mut scale = Vector3::new();
+		self.update_world_matrix(true, false);
+		self.matrix_world.decompose(&mut position, target, &mut scale);
+	}
+
+	pub fn get_world_scale(&mut self, target: &mut Vector3) {
+		let mut position = Vector3::new();
+		let mut quaternion = Quaternion::new();
+		self.update_world_matrix(true, false);
+		self.matrix_world.decompose(&mut position, &mut quaternion, target);
+	}
+
+	// The world space direction of the local z axis.
+	pub fn get_world_direction(&mut self, target: &mut Vector3) {
+		self.update_world_matrix(true, false);
+		let e = &self.matrix_world.elements;
+		target.set(e[ 8 ], e[ 9 ], e[ 10 ]);
+		target.normalize();
+	}
+
+	pub fn get_matrix_auto_update(&self) -> bool {
+		self.matrix_auto_update
+	}
+
+	pub fn set_matrix_auto_update(&mut self, matrix_auto_update: bool) {
+		self.matrix_auto_update = matrix_auto_update;
+	}
+
+	pub fn get_matrix_world_needs_update(&self) -> bool {
+		self.matrix_world_needs_update
+	}
+
+	pub fn set_matrix_world_needs_update(&mut self, matrix_world_needs_update: bool) {
+		self.matrix_world_needs_update = matrix_world_needs_update;
+	}
+
+	// Stamps the transform with a new version and flags the world matrix for update. The
+	// setters do this, it only has to be called after changing the transform some other way.
+	pub fn transform_needs_update(&mut self) {
+		self.transform_version = next_version();
+		self.matrix_world_needs_update = true;
+	}
+
+	pub fn get_transform_version(&self) -> u64 {
+		self.transform_version
+	}
+
+	// Only stamped when an update actually gives a different matrix_world.
+	pub fn get_matrix_world_version(&self) -> u64 {
+		self.matrix_world_version
+	}
+
+	// For SceneNode::to_object3d, which hands the node's versions on.
+	pub(crate) fn set_versions(&mut self, transform_version: u64, matrix_world_version: u64) {
+		self.transform_version = transform_version;
+		self.matrix_world_version = matrix_world_version;
+	}
+
+	// Whether the transform or matrix_world changed after frame, see core::change_tracker.
+	pub fn changed_since(&self, frame: u64) -> bool {
+		self.transform_version > frame || self.matrix_world_version > frame
+	}
+
+	fn set_matrix_world_checked(&mut self, matrix_world: &Matrix4) {
+		if self.matrix_world.elements != matrix_world.elements {
+			self.matrix_world.copy(matrix_world);
+			self.matrix_world_version = next_version();
+		}
+	}
+
+	pub fn get_matrix(&self) -> &Matrix4 {
+		&self.matrix
+	}
+
+	// Only sticks while matrix_auto_update is off, otherwise the next update recomposes it.
+	pub fn set_matrix(&mut self, matrix: &Matrix4) {
+		self.matrix.copy(matrix);
+		self.transform_needs_update();
+	}
+
+	pub fn get_matrix_world(&self) -> &Matrix4 {
+		&self.matrix_world
+	}
+
+	// For hierarchies that compute world matrices on their own.
+	pub fn set_matrix_world(&mut self, matrix_world: &Matrix4) {
+		self.set_matrix_world_checked(matrix_world);
+		self.matrix_world_needs_update = false;
+	}
+
+	pub fn update_matrix(&mut self) {
+		self.matrix.compose(&self.position, &self.quaternion, &self.scale);
+		self.matrix_world_needs_update = true;
+	}
+
+	// What update_matrix does, for callers that composed position, quaternion and scale on
+	// their own. Unlike set_matrix this is no change to the transform, so
+	// the transform version stays.
+	pub fn set_composed_matrix(&mut self, matrix: &Matrix4) {
+		self.matrix.copy(matrix);
+		self.matrix_world_needs_update = true;
+	}
+
+	// The parent's world matrix is read through the parent link, so the parent must not be
+	// mutably borrowed while this runs. Children are updated with this object's matrix_world
+	// passed down directly instead.
+	pub fn update_matrix_world(&mut self, force: bool) {
+		let parent = self.parent.as_ref().and_then(|p| p.upgrade());
+
+		match parent {
+			Some(parent) => {
+				let parent_matrix_world = parent.borrow().get_object3d().matrix_world;
+				self.update_matrix_world_from(Some(&parent_matrix_world), force);
+			},
+			None => self.update_matrix_world_from(None, force),
+		}
+	}
+
+	fn update_matrix_world_from(&mut self, parent_matrix_world: Option<&Matrix4>, force: bool) {
+		let force = self.update_matrix_world_with_parent(parent_matrix_world, force) || force;
+
+		for child in &self.children {
+			child.borrow_mut().get_object3d_mut().update_matrix_world_from(Some(&self.matrix_world), force);
+		}
+	}
+
+	// Updates this object only, for callers that keep the hierarchy elsewhere. Returns whether
+	// matrix_world was recomputed, in which case the children have to be forced to follow.
+	pub fn update_matrix_world_with_parent(&mut self, parent_matrix_world: Option<&Matrix4>, force: bool) -> bool {
+		if self.matrix_auto_update {
+			self.update_matrix();
+		}
+
+		if self.matrix_world_needs_update || force {
+			let matrix_world = transform::world_matrix(&self.matrix, parent_matrix_world);
+			self.set_matrix_world_checked(&matrix_world);
+
+			self.matrix_world_needs_update = false;
+			return true;
+		}
+
+		false
+	}
+
+	pub fn update_world_matrix(&mut self, update_parents: bool, update_children: bool) {
+		let parent = self.parent.as_ref().and_then(|p| p.upgrade());
+
+		match parent {
+			Some(parent) => {
+				if update_parents {
+					parent.borrow_mut().get_object3d_mut().update_world_matrix(true, false);
+				}
+
+				let parent_matrix_world = parent.borrow().get_object3d().matrix_world;
+				self.update_world_matrix_from(Some(&parent_matrix_world), update_children);
+			},
+			None => self.update_world_matrix_from(None, update_children),
+		}
+	}
+
+	fn update_world_matrix_from(&mut self, parent_matrix_world: Option<&Matrix4>, update_children: bool) {
+		if self.matrix_auto_update {
+			self.update_matrix();
+		}
+
+		let mut matrix_world = self.matrix;
+
+		if let Some(m) = parent_matrix_world {
+			matrix_world.multiply_matrices(m, &self.matrix);
+		}
+
+		self.set_matrix_world_checked(&matrix_world);
+
+		self.matrix_world_needs_update = false;
+
+		if update_children {
+			for child in &self.children {
+				child.borrow_mut().get_object3d_mut().update_world_matrix_from(Some(&self.matrix_world), true);
+			}
+		}
+	}
+
 	pub fn local_to_world(&self, vector: &mut Vector3) {
 		vector.apply_matrix4(&self.matrix_world);
 	}
@@ -198,39 +584,972 @@ impl Object3D {
 		vector.apply_matrix4(&m1);
 	}
 
+	// Turns the local z axis towards a point given in world space. The parent's world rotation
+	// is taken out again, so this works for nested objects too.
 	pub fn look_at(&mut self, vector: &Vector3) {
-		let mut m1 = Matrix4::new();
-		m1.look_at(vector, &self.position, &self.up);
-		self.quaternion.set_from_rotation_matrix(&m1);
+		let mut position = Vector3::new();
+		self.get_world_position(&mut position);
+
+		let parent_matrix_world = self.get_parent().map(|parent| parent.borrow().get_object3d().matrix_world);
+		transform::look_at(&mut self.quaternion, vector, &position, &self.up, parent_matrix_world.as_ref());
+		self.transform_needs_update();
+	}
+
+	pub fn get_children(&self) -> &Vec<Rc<RefCell<dyn HasObject3D>>> {
+		&self.children
+	}
+
+	pub fn get_parent(&self) -> Option<Rc<RefCell<dyn HasObject3D>>> {
+		self.parent.as_ref().and_then(|p| p.upgrade())
+	}
+
+	// Depth first, this object before its children. The children are borrowed while the
+	// callback runs, so it must not try to borrow them mutably through their Rc.
+	pub fn traverse<F: FnMut(&Object3D)>(&self, callback: &mut F) {
+		callback(self);
+
+		for child in &self.children {
+			child.borrow().get_object3d().traverse(callback);
+		}
+	}
+
+	pub fn traverse_mut<F: FnMut(&mut Object3D)>(&mut self, callback: &mut F) {
+		callback(self);
+
+		for child in &self.children {
+			child.borrow_mut().get_object3d_mut().traverse_mut(callback);
+		}
+	}
+
+	// Like traverse, but invisible objects are skipped together with their descendants.
+	pub fn traverse_visible<F: FnMut(&Object3D)>(&self, callback: &mut F) {
+		if !self.visible {
+			return;
+		}
+
+		callback(self);
+
+		for child in &self.children {
+			child.borrow().get_object3d().traverse_visible(callback);
+		}
 	}
 
+	// Like traverse, but only calls back for objects that share a layer with the given ones,
+	// usually a camera's. Layers don't hide descendants, those are tested one by one.
+	pub fn traverse_layers<F: FnMut(&Object3D)>(&self, layers: &Layers, callback: &mut F) {
+		if self.layers.test(layers) {
+			callback(self);
+		}
+
+		for child in &self.children {
+			child.borrow().get_object3d().traverse_layers(layers, callback);
+		}
+	}
+
+	// Walks up the parent links, nearest ancestor first. This object itself is not visited.
+	pub fn traverse_ancestors<F: FnMut(&Object3D)>(&self, callback: &mut F) {
+		if let Some(parent) = self.get_parent() {
+			let parent = parent.borrow();
+			callback(parent.get_object3d());
+			parent.get_object3d().traverse_ancestors(callback);
+		}
+	}
+
+	// The lookups take the Rc of the root, like add, so that the root itself can be returned.
+
+	pub fn get_object_by_id(object: &Rc<RefCell<dyn HasObject3D>>, id: usize) -> Option<Rc<RefCell<dyn HasObject3D>>> {
+		Object3D::get_object_by(object, &mut |o| o.id == id)
+	}
+
+	pub fn get_object_by_name(object: &Rc<RefCell<dyn HasObject3D>>, name: &str) -> Option<Rc<RefCell<dyn HasObject3D>>> {
+		Object3D::get_object_by(object, &mut |o| o.name == name)
+	}
+
+	pub fn get_object_by_uuid(object: &Rc<RefCell<dyn HasObject3D>>, uuid: &Uuid) -> Option<Rc<RefCell<dyn HasObject3D>>> {
+		Object3D::get_object_by(object, &mut |o| o.uuid == *uuid)
+	}
+
+	// The objects a camera would draw: visible, with visible ancestors and a layer in common
+	// with the camera. Ordered by render_order, ties keep the traversal order.
+	pub fn get_render_list(object: &Rc<RefCell<dyn HasObject3D>>, camera: &Camera) -> Vec<Rc<RefCell<dyn HasObject3D>>> {
+		let mut result = vec![];
+		Object3D::get_render_list_into(object, camera, &mut result);
+		result.sort_by_key(|o| o.borrow().get_object3d().render_order);
+		result
+	}
+
+	fn get_render_list_into(object: &Rc<RefCell<dyn HasObject3D>>, camera: &Camera, result: &mut Vec<Rc<RefCell<dyn HasObject3D>>>) {
+		let o = object.borrow();
+		let o = o.get_object3d();
+
+		if !o.visible {
+			return;
+		}
+
+		if o.layers.test(&camera.layers) {
+			result.push(object.clone());
+		}
+
+		for child in &o.children {
+			Object3D::get_render_list_into(child, camera, result);
+		}
+	}
+
+	// First match in traversal order.
+	pub fn get_object_by<F: FnMut(&Object3D) -> bool>(object: &Rc<RefCell<dyn HasObject3D>>, predicate: &mut F) -> Option<Rc<RefCell<dyn HasObject3D>>> {
+		if predicate(object.borrow().get_object3d()) {
+			return Some(object.clone());
+		}
+
+		for child in &object.borrow().get_object3d().children {
+			let found = Object3D::get_object_by(child, predicate);
+
+			if found.is_some() {
+				return found;
+			}
+		}
+
+		None
+	}
+
+	// All matches in traversal order.
+	pub fn find_all<F: FnMut(&Object3D) -> bool>(object: &Rc<RefCell<dyn HasObject3D>>, predicate: &mut F) -> Vec<Rc<RefCell<dyn HasObject3D>>> {
+		let mut result = vec![];
+		Object3D::find_all_into(object, predicate, &mut result);
+		result
+	}
+
+	fn find_all_into<F: FnMut(&Object3D) -> bool>(object: &Rc<RefCell<dyn HasObject3D>>, predicate: &mut F, result: &mut Vec<Rc<RefCell<dyn HasObject3D>>>) {
+		if predicate(object.borrow().get_object3d()) {
+			result.push(object.clone());
+		}
+
+		for child in &object.borrow().get_object3d().children {
+			Object3D::find_all_into(child, predicate, result);
+		}
+	}
+
+	pub fn add_event_listener(&mut self, event_type: &str, listener: &Listener) {
+		self.dispatcher.add_event_listener(event_type, listener);
+	}
+
+	pub fn has_event_listener(&self, event_type: &str, listener: &Listener) -> bool {
+		self.dispatcher.has_event_listener(event_type, listener)
+	}
+
+	pub fn remove_event_listener(&mut self, event_type: &str, listener: &Listener) {
+		self.dispatcher.remove_event_listener(event_type, listener);
+	}
+
+	pub fn dispatch_event(&self, event: &Event) {
+		self.dispatcher.dispatch_event(event);
+	}
+
+	// Hierarchy changes fire "added" and "removed" on the child and "childadded" and
+	// "childremoved", with the child's Rc as data, on the parent. Listeners run while the
+	// object they listen on is borrowed, and remove and clear already have the parent
+	// borrowed mutably, so listeners should only look at the child.
+
+	// Copies everything but the identity and the place in the hierarchy: id, uuid, parent,
+	// children and event listeners are left alone.
+	fn copy_properties(&mut self, source: &Object3D) {
+		self.name = source.name.clone();
+		self.up.copy(&source.up);
+		self.position.copy(&source.position);
+		self.quaternion.copy(&source.quaternion);
+		self.scale.copy(&source.scale);
+		self.matrix.copy(&source.matrix);
+		self.matrix_world.copy(&source.matrix_world);
+		self.matrix_auto_update = source.matrix_auto_update;
+		self.matrix_world_needs_update = source.matrix_world_needs_update;
+		self.transform_needs_update();
+		self.matrix_world_version = next_version();
+		self.layers.mask = source.layers.mask;
+		self.visible = source.visible;
+		self.cast_shadow = source.cast_shadow;
+		self.receive_shadow = source.receive_shadow;
+		self.frustum_culled = source.frustum_culled;
+		self.render_order = source.render_order;
+		self.components = source.components.clone();
+		self.user_data = source.user_data.clone();
+	}
+
+	// A new object with the properties of this one, but its own id and uuid and no parent,
+	// children or listeners. Unlike clone, which shares the children with the original.
+	pub fn clone_detached(&self) -> Object3D {
+		let mut object = Object3D::new();
+		object.copy_properties(self);
+		object
+	}
+
+	// An independent copy of the whole subtree, each node copied with clone_node.
+	pub fn clone_recursive(object: &Rc<RefCell<dyn HasObject3D>>) -> Rc<RefCell<dyn HasObject3D>> {
+		let clone = object.borrow().clone_node();
+
+		for child in &object.borrow().get_object3d().children {
+			Object3D::add(&clone, &Object3D::clone_recursive(child));
+		}
+
+		clone
+	}
+
+	// Copies the properties of source onto object, and with recursive also adds copies of the
+	// children of source. Source must not be object itself.
+	pub fn copy(object: &Rc<RefCell<dyn HasObject3D>>, source: &Object3D, recursive: bool) {
+		object.borrow_mut().get_object3d_mut().copy_properties(source);
+
+		if recursive {
+			for child in &source.children {
+				Object3D::add(object, &Object3D::clone_recursive(child));
+			}
+		}
+	}
+
+	// A child that already has a parent is removed from it first. Adding an object to
+	// itself or to one of its descendants would make a cycle and is ignored.
 	pub fn add(parent: &Rc<RefCell<HasObject3D>>, child: &Rc<RefCell<HasObject3D>>) {
+		if Object3D::is_in_subtree(parent, child) {
+			return;
+		}
+
+		Object3D::remove_from_parent(child);
+
 		let weak = Rc::downgrade(parent);
-		child.borrow_mut().get_object3d_mut().parent = Some(weak);
+
+		{
+			let mut child = child.borrow_mut();
+			let child = child.get_object3d_mut();
+			child.parent = Some(weak);
+			child.matrix_world_needs_update = true;
+		}
 		parent.borrow_mut().get_object3d_mut().children.push(child.clone());
+
+		child.borrow().get_object3d().dispatch_event(&Event::new("added"));
+		parent.borrow().get_object3d().dispatch_event(&Event::with_data("childadded", child));
 	}
 
-	pub fn remove(&mut self, object: &Rc<RefCell<HasObject3D>>) -> bool {
-		let mut idx: Option<usize> = Option::None;
-		for (i, o) in self.children.iter().enumerate() {
-			if o.borrow().get_object3d() == object.borrow().get_object3d() {
-				idx = Some(i);
-				break;
+	// Like add, but keeps the child's world transform by folding the change of parent
+	// into its local transform.
+	pub fn attach(parent: &Rc<RefCell<dyn HasObject3D>>, child: &Rc<RefCell<dyn HasObject3D>>) {
+		if Object3D::is_in_subtree(parent, child) {
+			return;
+		}
+
+		let mut m1 = Matrix4::new();
+
+		{
+			let mut parent = parent.borrow_mut();
+			let parent = parent.get_object3d_mut();
+			parent.update_world_matrix(true, false);
+			m1.get_inverse(&parent.matrix_world, false);
+		}
+
+		if let Some(old_parent) = child.borrow().get_object3d().get_parent() {
+			let mut old_parent = old_parent.borrow_mut();
+			let old_parent = old_parent.get_object3d_mut();
+			old_parent.update_world_matrix(true, false);
+			m1.multiply(&old_parent.matrix_world);
+		}
+
+		child.borrow_mut().get_object3d_mut().apply_matrix(&m1);
+
+		Object3D::add(parent, child);
+
+		child.borrow_mut().get_object3d_mut().update_world_matrix(false, true);
+	}
+
+	// Whether object is root itself or one of its descendants, found by walking up from object.
+	fn is_in_subtree(object: &Rc<RefCell<dyn HasObject3D>>, root: &Rc<RefCell<dyn HasObject3D>>) -> bool {
+		let mut current = Some(object.clone());
+
+		while let Some(o) = current {
+			if Rc::ptr_eq(&o, root) {
+				return true;
 			}
+			current = o.borrow().get_object3d().get_parent();
 		}
+
+		false
+	}
+
+	// Keeps the order of the remaining children.
+	pub fn remove(&mut self, object: &Rc<RefCell<HasObject3D>>) -> bool {
+		// by identity, clones share the uuid
+		let idx = self.children.iter().position(|o| Rc::ptr_eq(o, object));
+
 		if let Some(i) = idx {
-			self.children.swap_remove(i);
-			object.borrow_mut().get_object3d_mut().parent = Option::None;
+			self.children.remove(i);
+
+			{
+				let mut object = object.borrow_mut();
+				let object = object.get_object3d_mut();
+				object.parent = Option::None;
+				object.matrix_world_needs_update = true;
+			}
+
+			object.borrow().get_object3d().dispatch_event(&Event::new("removed"));
+			self.dispatch_event(&Event::with_data("childremoved", object));
 			return true;
 		}
 		false
 	}
 
-	// pub fn remove_self(&mut self) -> bool {
-	// 	if self.parent.is_some() {
-	// 		true
-	// 	} else {
-	// 		false
-	// 	}
-	// }
-}
\ No newline at end of file
+	pub fn remove_from_parent(object: &Rc<RefCell<dyn HasObject3D>>) -> bool {
+		let parent = object.borrow().get_object3d().get_parent();
+
+		match parent {
+			Some(parent) => parent.borrow_mut().get_object3d_mut().remove(object),
+			None => {
+				object.borrow_mut().get_object3d_mut().parent = None;
+				false
+			},
+		}
+	}
+
+	pub fn clear(&mut self) {
+		let children: Vec<Rc<RefCell<dyn HasObject3D>>> = self.children.drain(..).collect();
+
+		for child in &children {
+			{
+				let mut child = child.borrow_mut();
+				let child = child.get_object3d_mut();
+				child.parent = None;
+				child.matrix_world_needs_update = true;
+			}
+
+			child.borrow().get_object3d().dispatch_event(&Event::new("removed"));
+			self.dispatch_event(&Event::with_data("childremoved", child));
+		}
+	}
+
+	// The object and its descendants in the three.js object JSON layout. Components are
+	// left out, only user_data is meant to be saved.
+	pub fn to_json(&self) -> String {
+		let mut json = String::new();
+		self.write_json(&mut json);
+		json
+	}
+
+	fn write_json(&self, json: &mut String) {
+		write!(json, "{{\"uuid\":\"{}\",\"type\":\"Object3D\"", self.uuid).unwrap();
+
+		if !self.name.is_empty() {
+			json.push_str(",\"name\":");
+			write_json_string(&self.name, json);
+		}
+
+		if self.cast_shadow {
+			json.push_str(",\"castShadow\":true");
+		}
+
+		if self.receive_shadow {
+			json.push_str(",\"receiveShadow\":true");
+		}
+
+		if !self.visible {
+			json.push_str(",\"visible\":false");
+		}
+
+		if !self.frustum_culled {
+			json.push_str(",\"frustumCulled\":false");
+		}
+
+		if self.render_order != 0 {
+			write!(json, ",\"renderOrder\":{}", self.render_order).unwrap();
+		}
+
+		if self.user_data.as_object().is_none_or(|object| !object.is_empty()) {
+			json.push_str(",\"userData\":");
+			self.user_data.write_json(json);
+		}
+
+		write!(json, ",\"layers\":{},\"matrix\":[", self.layers.mask).unwrap();
+
+		for (i, e) in self.matrix.elements.iter().enumerate() {
+			if i > 0 {
+				json.push(',');
+			}
+			write!(json, "{}", e).unwrap();
+		}
+
+		json.push(']');
+
+		if !self.children.is_empty() {
+			json.push_str(",\"children\":[");
+
+			for (i, child) in self.children.iter().enumerate() {
+				if i > 0 {
+					json.push(',');
+				}
+				child.borrow().get_object3d().write_json(json);
+			}
+
+			json.push(']');
+		}
+
+		json.push('}');
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn apply_matrix_drops_shear() {
+		let mut object = Object3D::new();
+		let position = Vector3 { x: 1.0, y: 2.0, z: 3.0 };
+		object.set_position(&position);
+
+		let mut shear = Matrix4::new();
+		shear.set(
+			1.0, 0.5, 0.0, 0.0,
+			0.0, 1.0, 0.0, 0.0,
+			0.0, 0.0, 1.0, 0.0,
+			0.0, 0.0, 0.0, 1.0
+		);
+
+		let mut translation = Matrix4::new();
+		translation.make_translation(position.x, position.y, position.z);
+		let mut expected = Matrix4::new();
+		expected.multiply_matrices(&shear, &translation);
+
+		object.apply_matrix(&shear);
+		object.update_matrix();
+
+		// translation is kept, the sheared basis isn't
+		let e = object.get_matrix().elements;
+		assert!(( e[ 12 ] - expected.elements[ 12 ] ).abs() < 1e-5);
+		assert!(( e[ 13 ] - expected.elements[ 13 ] ).abs() < 1e-5);
+		assert!(( e[ 14 ] - expected.elements[ 14 ] ).abs() < 1e-5);
+		assert!(( 0..16 ).any(|i| ( e[ i ] - expected.elements[ i ] ).abs() > 1e-3));
+	}
+
+	fn node() -> Rc<RefCell<dyn HasObject3D>> {
+		Rc::new(RefCell::new(Object3D::new()))
+	}
+
+	fn is_parent_of(parent: &Rc<RefCell<dyn HasObject3D>>, child: &Rc<RefCell<dyn HasObject3D>>) -> bool {
+		child.borrow().get_object3d().get_parent().is_some_and(|p| Rc::ptr_eq(&p, parent))
+	}
+
+	fn assert_children(parent: &Rc<RefCell<dyn HasObject3D>>, expected: &[&Rc<RefCell<dyn HasObject3D>>]) {
+		let parent = parent.borrow();
+		let children = parent.get_object3d().get_children();
+		assert_eq!(children.len(), expected.len());
+
+		for (child, expected) in children.iter().zip(expected) {
+			assert!(Rc::ptr_eq(child, expected));
+		}
+	}
+
+	#[test]
+	fn remove_keeps_the_order() {
+		let parent = node();
+		let a = node();
+		let b = node();
+		let c = node();
+
+		for child in &[ &a, &b, &c ] {
+			Object3D::add(&parent, child);
+		}
+
+		assert!(Object3D::remove_from_parent(&b));
+		assert_children(&parent, &[ &a, &c ]);
+		assert!(b.borrow().get_object3d().get_parent().is_none());
+		assert!(!Object3D::remove_from_parent(&b));
+	}
+
+	#[test]
+	fn remove_tells_clones_apart() {
+		let parent = node();
+		let c1 = Rc::new(RefCell::new(Object3D::new()));
+		let c2: Rc<RefCell<dyn HasObject3D>> = Rc::new(RefCell::new(c1.borrow().clone()));
+		let c1: Rc<RefCell<dyn HasObject3D>> = c1;
+
+		Object3D::add(&parent, &c1);
+		Object3D::add(&parent, &c2);
+
+		assert!(Object3D::remove_from_parent(&c2));
+		assert_children(&parent, &[ &c1 ]);
+		assert!(is_parent_of(&parent, &c1));
+		assert!(c2.borrow().get_object3d().get_parent().is_none());
+	}
+
+	#[test]
+	fn add_moves_the_child_to_the_new_parent() {
+		let p1 = node();
+		let p2 = node();
+		let child = node();
+
+		Object3D::add(&p1, &child);
+		Object3D::add(&p2, &child);
+
+		assert_children(&p1, &[]);
+		assert_children(&p2, &[ &child ]);
+		assert!(is_parent_of(&p2, &child));
+	}
+
+	#[test]
+	fn attach_keeps_the_world_transform() {
+		let p1 = node();
+		let p2 = node();
+		let child = node();
+
+		p1.borrow_mut().get_object3d_mut().set_position(&Vector3 { x: 1.0, y: 2.0, z: 3.0 });
+		p1.borrow_mut().get_object3d_mut().rotate_y(0.5);
+		p2.borrow_mut().get_object3d_mut().set_position(&Vector3 { x: - 4.0, y: 0.0, z: 1.0 });
+		p2.borrow_mut().get_object3d_mut().rotate_x(- 1.2);
+		p2.borrow_mut().get_object3d_mut().set_scale(&Vector3 { x: 2.0, y: 2.0, z: 2.0 });
+
+		child.borrow_mut().get_object3d_mut().set_position(&Vector3 { x: 0.5, y: - 1.0, z: 2.0 });
+		child.borrow_mut().get_object3d_mut().rotate_z(0.3);
+		Object3D::add(&p1, &child);
+		child.borrow_mut().get_object3d_mut().update_world_matrix(true, false);
+		let before = *child.borrow().get_object3d().get_matrix_world();
+
+		Object3D::attach(&p2, &child);
+		assert!(is_parent_of(&p2, &child));
+		assert_children(&p1, &[]);
+
+		child.borrow_mut().get_object3d_mut().update_world_matrix(true, false);
+		let after = *child.borrow().get_object3d().get_matrix_world();
+
+		for (x, y) in before.elements.iter().zip(after.elements.iter()) {
+			assert!(( x - y ).abs() < 1e-5, "{:?} != {:?}", before.elements, after.elements);
+		}
+	}
+
+	fn named(name: &str) -> Rc<RefCell<dyn HasObject3D>> {
+		let object = node();
+		object.borrow_mut().get_object3d_mut().set_name(name);
+		object
+	}
+
+	fn name_of(object: &Rc<RefCell<dyn HasObject3D>>) -> String {
+		object.borrow().get_object3d().get_name().to_string()
+	}
+
+	// root with children a and b, and a with a child c.
+	fn tree() -> Rc<RefCell<dyn HasObject3D>> {
+		let root = named("root");
+		let a = named("a");
+		Object3D::add(&root, &a);
+		Object3D::add(&root, &named("b"));
+		Object3D::add(&a, &named("c"));
+		root
+	}
+
+	fn assert_new_identity(copy: &Object3D, original: &Object3D) {
+		assert_ne!(copy.get_id(), original.get_id());
+		assert_ne!(copy.get_uuid(), original.get_uuid());
+	}
+
+	#[test]
+	fn clone_detached_copies_the_properties_only() {
+		let parent = tree();
+		let a = parent.borrow().get_object3d().get_children()[ 0 ].clone();
+		a.borrow_mut().get_object3d_mut().set_position(&Vector3 { x: 1.0, y: 2.0, z: 3.0 });
+		a.borrow_mut().get_object3d_mut().set_cast_shadow(true);
+
+		let copy = a.borrow().get_object3d().clone_detached();
+		let a = a.borrow();
+		let a = a.get_object3d();
+
+		assert_new_identity(&copy, a);
+		assert_eq!(copy.get_name(), "a");
+		assert!(copy.get_position().equals(a.get_position()));
+		assert!(copy.get_cast_shadow());
+		assert!(copy.get_parent().is_none());
+		assert!(copy.get_children().is_empty());
+	}
+
+	#[test]
+	fn clone_recursive_copies_the_subtree() {
+		let root = tree();
+		let a = root.borrow().get_object3d().get_children()[ 0 ].clone();
+		let copy = Object3D::clone_recursive(&a);
+
+		assert_new_identity(copy.borrow().get_object3d(), a.borrow().get_object3d());
+		assert!(copy.borrow().get_object3d().get_parent().is_none());
+		assert_eq!(name_of(&copy), "a");
+
+		let copy_c = copy.borrow().get_object3d().get_children()[ 0 ].clone();
+		let c = a.borrow().get_object3d().get_children()[ 0 ].clone();
+		assert!(!Rc::ptr_eq(&copy_c, &c));
+		assert_new_identity(copy_c.borrow().get_object3d(), c.borrow().get_object3d());
+		assert_eq!(name_of(&copy_c), "c");
+		assert!(is_parent_of(&copy, &copy_c));
+		assert!(is_parent_of(&a, &c));
+
+		// changes to the copy stay there
+		copy_c.borrow_mut().get_object3d_mut().set_name("changed");
+		Object3D::add(&copy_c, &named("d"));
+		assert_eq!(name_of(&c), "c");
+		assert!(c.borrow().get_object3d().get_children().is_empty());
+		assert_eq!(a.borrow().get_object3d().get_children().len(), 1);
+	}
+
+	#[test]
+	fn copy_keeps_the_identity() {
+		let source = tree();
+		source.borrow_mut().get_object3d_mut().set_position(&Vector3 { x: 0.0, y: 4.0, z: 0.0 });
+
+		let target = named("target");
+		let id = target.borrow().get_object3d().get_id();
+		let uuid = *target.borrow().get_object3d().get_uuid();
+
+		Object3D::copy(&target, source.borrow().get_object3d(), false);
+		assert_eq!(target.borrow().get_object3d().get_id(), id);
+		assert_eq!(*target.borrow().get_object3d().get_uuid(), uuid);
+		assert_eq!(name_of(&target), "root");
+		assert_eq!(target.borrow().get_object3d().get_position().y, 4.0);
+		assert!(target.borrow().get_object3d().get_children().is_empty());
+
+		Object3D::copy(&target, source.borrow().get_object3d(), true);
+		let names: Vec<String> = target.borrow().get_object3d().get_children().iter().map(name_of).collect();
+		assert_eq!(names, vec![ "a", "b" ]);
+
+		let source_a = source.borrow().get_object3d().get_children()[ 0 ].clone();
+		let target_a = target.borrow().get_object3d().get_children()[ 0 ].clone();
+		assert!(!Rc::ptr_eq(&source_a, &target_a));
+		assert!(is_parent_of(&target, &target_a));
+		assert_eq!(target_a.borrow().get_object3d().get_children().len(), 1);
+		assert_eq!(source.borrow().get_object3d().get_children().len(), 2);
+	}
+
+	#[test]
+	fn look_at_under_a_transformed_parent() {
+		let parent = node();
+		let child = node();
+		Object3D::add(&parent, &child);
+
+		{
+			let mut parent = parent.borrow_mut();
+			let parent = parent.get_object3d_mut();
+			parent.set_position(&Vector3 { x: 2.0, y: - 1.0, z: 0.5 });
+			parent.rotate_y(1.1);
+			parent.rotate_x(- 0.4);
+			parent.set_scale(&Vector3 { x: 2.0, y: 2.0, z: 2.0 });
+		}
+
+		child.borrow_mut().get_object3d_mut().set_position(&Vector3 { x: 1.0, y: 0.0, z: - 1.0 });
+
+		let target = Vector3 { x: - 3.0, y: 4.0, z: 2.0 };
+		child.borrow_mut().get_object3d_mut().look_at(&target);
+
+		let mut position = Vector3::new();
+		let mut direction = Vector3::new();
+		child.borrow_mut().get_object3d_mut().get_world_position(&mut position);
+		child.borrow_mut().get_object3d_mut().get_world_direction(&mut direction);
+
+		let mut expected = Vector3::new();
+		expected.sub_vectors(&target, &position);
+		expected.normalize();
+		assert!(direction.distance_to(&expected) < 1e-5, "{:?} != {:?}", direction, expected);
+
+		// the world rotation is the one look_at aims for, the parent's scale doesn't leak in
+		let mut scale = Vector3::new();
+		child.borrow_mut().get_object3d_mut().get_world_scale(&mut scale);
+		assert!(scale.distance_to(&Vector3 { x: 2.0, y: 2.0, z: 2.0 }) < 1e-5);
+	}
+
+	// root on layer 0, a on layer 1, b on both and c, under a, on layer 2.
+	fn layered_tree() -> Rc<RefCell<dyn HasObject3D>> {
+		let root = tree();
+		let a = root.borrow().get_object3d().get_children()[ 0 ].clone();
+		let b = root.borrow().get_object3d().get_children()[ 1 ].clone();
+		let c = a.borrow().get_object3d().get_children()[ 0 ].clone();
+		a.borrow_mut().get_object3d_mut().get_layers_mut().set(1).unwrap();
+		b.borrow_mut().get_object3d_mut().get_layers_mut().enable(1).unwrap();
+		c.borrow_mut().get_object3d_mut().get_layers_mut().set(2).unwrap();
+		root
+	}
+
+	#[test]
+	fn traverse_layers_tests_every_object() {
+		let root = layered_tree();
+		let mut layers = Layers::new();
+
+		let visit = |layers: &Layers| {
+			let mut names = vec![];
+			root.borrow().get_object3d().traverse_layers(layers, &mut |o| names.push(o.get_name().to_string()));
+			names
+		};
+
+		assert_eq!(visit(&layers), vec![ "root", "b" ]);
+
+		// c is found even though its parent a is filtered out
+		layers.set(2).unwrap();
+		assert_eq!(visit(&layers), vec![ "c" ]);
+
+		layers.enable(1).unwrap();
+		assert_eq!(visit(&layers), vec![ "a", "c", "b" ]);
+
+		layers.disable_all();
+		assert!(visit(&layers).is_empty());
+	}
+
+	#[test]
+	fn render_list_follows_the_camera_layers() {
+		let root = layered_tree();
+		let mut camera = Camera::new();
+
+		let names = |camera: &Camera| Object3D::get_render_list(&root, camera).iter().map(name_of).collect::<Vec<_>>();
+
+		assert_eq!(names(&camera), vec![ "root", "b" ]);
+
+		camera.layers.set(1).unwrap();
+		assert_eq!(names(&camera), vec![ "a", "b" ]);
+
+		camera.layers.enable_all();
+		assert_eq!(names(&camera), vec![ "root", "a", "c", "b" ]);
+
+		// hiding a drops c with it, whatever the layers say
+		let a = root.borrow().get_object3d().get_children()[ 0 ].clone();
+		a.borrow_mut().get_object3d_mut().set_visible(false);
+		assert_eq!(names(&camera), vec![ "root", "b" ]);
+	}
+
+	// Records the hierarchy events of an object as "<its name> <event> [<child name>]".
+	fn listen(object: &Rc<RefCell<dyn HasObject3D>>, log: &Rc<RefCell<Vec<String>>>) {
+		let name = name_of(object);
+		let log = log.clone();
+		let listener: Listener = Rc::new(move |event: &Event| {
+			let mut entry = format!("{} {}", name, event.event_type);
+
+			if let Some(child) = event.get_data::<Rc<RefCell<dyn HasObject3D>>>() {
+				entry = format!("{} {}", entry, name_of(child));
+			}
+
+			log.borrow_mut().push(entry);
+		});
+
+		let mut object = object.borrow_mut();
+		for event_type in [ "added", "removed", "childadded", "childremoved" ] {
+			object.get_object3d_mut().add_event_listener(event_type, &listener);
+		}
+	}
+
+	fn take(log: &Rc<RefCell<Vec<String>>>) -> Vec<String> {
+		log.borrow_mut().drain(..).collect()
+	}
+
+	#[test]
+	fn add_and_remove_fire_once_on_the_child_and_the_parent() {
+		let log = Rc::new(RefCell::new(vec![]));
+		let root = named("root");
+		let a = named("a");
+		let b = named("b");
+		listen(&root, &log);
+		listen(&a, &log);
+		listen(&b, &log);
+
+		Object3D::add(&root, &a);
+		assert_eq!(take(&log), vec![ "a added", "root childadded a" ]);
+
+		Object3D::add(&root, &b);
+		assert_eq!(take(&log), vec![ "b added", "root childadded b" ]);
+
+		assert!(root.borrow_mut().get_object3d_mut().remove(&a));
+		assert_eq!(take(&log), vec![ "a removed", "root childremoved a" ]);
+
+		// removing what isn't a child fires nothing
+		assert!(!root.borrow_mut().get_object3d_mut().remove(&a));
+		assert!(!Object3D::remove_from_parent(&a));
+		assert!(take(&log).is_empty());
+
+		assert!(Object3D::remove_from_parent(&b));
+		assert_eq!(take(&log), vec![ "b removed", "root childremoved b" ]);
+	}
+
+	#[test]
+	fn moving_a_child_fires_on_both_parents() {
+		let log = Rc::new(RefCell::new(vec![]));
+		let root = named("root");
+		let a = named("a");
+		let c = named("c");
+		Object3D::add(&root, &a);
+		Object3D::add(&root, &c);
+		listen(&root, &log);
+		listen(&a, &log);
+		listen(&c, &log);
+
+		Object3D::add(&a, &c);
+		assert_eq!(take(&log), vec![ "c removed", "root childremoved c", "c added", "a childadded c" ]);
+
+		// adding an object to itself is ignored
+		Object3D::add(&a, &a);
+		assert!(take(&log).is_empty());
+	}
+
+	#[test]
+	fn clear_fires_for_every_child() {
+		let log = Rc::new(RefCell::new(vec![]));
+		let root = tree();
+		let children = root.borrow().get_object3d().get_children().clone();
+		listen(&root, &log);
+		for child in &children {
+			listen(child, &log);
+		}
+
+		root.borrow_mut().get_object3d_mut().clear();
+		assert_eq!(take(&log), vec![ "a removed", "root childremoved a", "b removed", "root childremoved b" ]);
+
+		root.borrow_mut().get_object3d_mut().clear();
+		assert!(take(&log).is_empty());
+	}
+
+	#[test]
+	fn components_and_user_data_are_cloned() {
+		let mut object = Object3D::new();
+		assert_eq!(object.insert(5u32), None);
+		assert_eq!(object.insert(6u32), Some(5));
+		object.insert(vec![ 1, 2 ]);
+		object.get_user_data_mut().insert("kind", "tree");
+
+		let mut copy = object.clone_detached();
+		copy.get_mut::<Vec<i32>>().unwrap().push(3);
+		copy.get_user_data_mut().insert("kind", "rock");
+
+		assert_eq!(object.get::<Vec<i32>>(), Some(&vec![ 1, 2 ]));
+		assert_eq!(copy.get::<Vec<i32>>(), Some(&vec![ 1, 2, 3 ]));
+		assert_eq!(copy.get::<u32>(), Some(&6));
+		assert_eq!(object.get_user_data().get("kind").and_then(|v| v.as_str()), Some("tree"));
+
+		assert_eq!(object.remove_component::<u32>(), Some(6));
+		assert!(!object.has::<u32>());
+		assert!(copy.has::<u32>());
+		assert_eq!(object.get_components().len(), 1);
+	}
+
+	const IDENTITY_JSON: &str = "[1,0,0,0,0,1,0,0,0,0,1,0,0,0,0,1]";
+
+	#[test]
+	fn to_json_leaves_out_the_defaults() {
+		let object = Object3D::new();
+		let expected = format!("{{\"uuid\":\"{}\",\"type\":\"Object3D\",\"layers\":1,\"matrix\":{}}}", object.get_uuid(), IDENTITY_JSON);
+		assert_eq!(object.to_json(), expected);
+	}
+
+	#[test]
+	fn to_json_writes_the_properties_and_children() {
+		let root = named("root \"1\"");
+		let child = named("child");
+		Object3D::add(&root, &child);
+
+		{
+			let mut root = root.borrow_mut();
+			let root = root.get_object3d_mut();
+			root.set_cast_shadow(true);
+			root.set_receive_shadow(true);
+			root.set_visible(false);
+			root.set_frustum_culled(false);
+			root.set_render_order(3);
+			root.get_layers_mut().enable(1).unwrap();
+			root.get_user_data_mut().insert("level", 2);
+			// components aren't saved
+			root.insert(1u8);
+		}
+
+		let mut expected = format!("{{\"uuid\":\"{}\",\"type\":\"Object3D\",\"name\":\"root \\\"1\\\"\"", root.borrow().get_object3d().get_uuid());
+		expected.push_str(",\"castShadow\":true,\"receiveShadow\":true,\"visible\":false,\"frustumCulled\":false");
+		expected.push_str(&format!(",\"renderOrder\":3,\"userData\":{{\"level\":2}},\"layers\":3,\"matrix\":{}", IDENTITY_JSON));
+		expected.push_str(&format!(",\"children\":[{{\"uuid\":\"{}\",\"type\":\"Object3D\",\"name\":\"child\",\"layers\":1,\"matrix\":{}}}]}}", child.borrow().get_object3d().get_uuid(), IDENTITY_JSON));
+
+		assert_eq!(root.borrow().get_object3d().to_json(), expected);
+	}
+
+	fn child(parent: &Rc<RefCell<dyn HasObject3D>>, i: usize) -> Rc<RefCell<dyn HasObject3D>> {
+		parent.borrow().get_object3d().get_children()[ i ].clone()
+	}
+
+	#[test]
+	fn traverse_goes_depth_first() {
+		let root = tree();
+		let mut names = vec![];
+		root.borrow().get_object3d().traverse(&mut |o| names.push(o.get_name().to_string()));
+		assert_eq!(names, vec![ "root", "a", "c", "b" ]);
+
+		root.borrow_mut().get_object3d_mut().traverse_mut(&mut |o| o.set_render_order(7));
+		let c = child(&child(&root, 0), 0);
+		assert_eq!(c.borrow().get_object3d().get_render_order(), 7);
+	}
+
+	#[test]
+	fn traverse_visible_skips_hidden_subtrees() {
+		let root = tree();
+		child(&root, 0).borrow_mut().get_object3d_mut().set_visible(false);
+
+		let mut names = vec![];
+		root.borrow().get_object3d().traverse_visible(&mut |o| names.push(o.get_name().to_string()));
+		assert_eq!(names, vec![ "root", "b" ]);
+	}
+
+	#[test]
+	fn traverse_ancestors_walks_up_to_the_root() {
+		let root = tree();
+		let c = child(&child(&root, 0), 0);
+
+		let mut names = vec![];
+		c.borrow().get_object3d().traverse_ancestors(&mut |o| names.push(o.get_name().to_string()));
+		assert_eq!(names, vec![ "a", "root" ]);
+
+		names.clear();
+		root.borrow().get_object3d().traverse_ancestors(&mut |o| names.push(o.get_name().to_string()));
+		assert!(names.is_empty());
+	}
+
+	#[test]
+	fn lookups_return_the_first_match() {
+		let root = tree();
+		let c = child(&child(&root, 0), 0);
+		let (id, uuid) = {
+			let c = c.borrow();
+			(c.get_object3d().get_id(), *c.get_object3d().get_uuid())
+		};
+
+		assert!(Rc::ptr_eq(&Object3D::get_object_by_name(&root, "root").unwrap(), &root));
+		assert!(Rc::ptr_eq(&Object3D::get_object_by_name(&root, "c").unwrap(), &c));
+		assert!(Rc::ptr_eq(&Object3D::get_object_by_id(&root, id).unwrap(), &c));
+		assert!(Rc::ptr_eq(&Object3D::get_object_by_uuid(&root, &uuid).unwrap(), &c));
+		assert!(Object3D::get_object_by_name(&root, "d").is_none());
+
+		// only the subtree is searched
+		let b = child(&root, 1);
+		assert!(Object3D::get_object_by_id(&b, id).is_none());
+
+		// with two objects of the same name, the one found first in traverse order wins
+		let other = named("c");
+		Object3D::add(&b, &other);
+		assert!(Rc::ptr_eq(&Object3D::get_object_by_name(&root, "c").unwrap(), &c));
+		assert!(Rc::ptr_eq(&Object3D::get_object_by_name(&b, "c").unwrap(), &other));
+
+		let found = Object3D::find_all(&root, &mut |o| o.get_name() == "c");
+		assert_eq!(found.len(), 2);
+		assert!(Rc::ptr_eq(&found[ 0 ], &c));
+		assert!(Rc::ptr_eq(&found[ 1 ], &other));
+	}
+
+	#[test]
+	fn add_rejects_cycles() {
+		let root = tree();
+		let a = child(&root, 0);
+		let c = child(&a, 0);
+
+		Object3D::add(&c, &root);
+		Object3D::add(&c, &a);
+		Object3D::add(&a, &a);
+		Object3D::attach(&c, &root);
+		assert!(root.borrow().get_object3d().get_parent().is_none());
+		assert!(is_parent_of(&root, &a));
+		assert!(is_parent_of(&a, &c));
+		assert!(c.borrow().get_object3d().get_children().is_empty());
+
+		// moving an object below a sibling's subtree is fine
+		let b = child(&root, 1);
+		Object3D::add(&c, &b);
+		assert!(is_parent_of(&c, &b));
+		assert_children(&root, &[ &a ]);
+	}
+}
@@ -0,0 +1,490 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::f32;
+use std::rc::Rc;
+use super::object3d::HasObject3D;
+use super::layers::Layers;
+use super::super::cameras::camera::Camera;
+use super::super::math::vector2::Vector2;
+use super::super::math::vector3::Vector3;
+use super::super::math::matrix4::Matrix4;
+use super::super::math::ray::Ray;
+use super::super::math::triangle::Triangle;
+
+pub struct Intersection {
+	// Along the ray, from its origin to point.
+	pub distance: f32,
+	pub point: Vector3,
+	pub object: Rc<RefCell<dyn HasObject3D>>,
+	// Triangle hits: which triangle was hit, and the barycentric coordinates of the point as
+	// the weights of its second and third corner.
+	pub face_index: Option<usize>,
+	pub uv: Option<Vector2>,
+	// Line and point hits: the index of the segment start or of the point, and how far the ray
+	// passed from it.
+	pub index: Option<usize>,
+	pub distance_to_ray: Option<f32>,
+}
+
+impl Intersection {
+	pub fn new(distance: f32, point: &Vector3, object: &Rc<RefCell<dyn HasObject3D>>) -> Intersection {
+		Intersection {
+			distance,
+			point: *point,
+			object: object.clone(),
+			face_index: None,
+			uv: None,
+			index: None,
+			distance_to_ray: None,
+		}
+	}
+}
+
+pub struct Raycaster {
+	pub ray: Ray,
+	pub near: f32,
+	pub far: f32,
+	// Only objects sharing a layer with these are tested.
+	pub layers: Layers,
+	// How close, in world units, the ray has to pass to a line or a point to hit it.
+	pub line_threshold: f32,
+	pub points_threshold: f32,
+}
+
+impl Raycaster {
+	pub fn new() -> Raycaster {
+		Raycaster {
+			ray: Ray::new(),
+			near: 0.0,
+			far: f32::INFINITY,
+			layers: Layers::new(),
+			line_threshold: 1.0,
+			points_threshold: 1.0,
+		}
+	}
+
+	// direction is assumed to be normalized.
+	pub fn set(&mut self, origin: &Vector3, direction: &Vector3) {
+		self.ray.set(origin, direction);
+	}
+
+	// coords are normalized device coordinates, from -1 to 1 on both axes. A projection matrix
+	// without perspective is treated as orthographic, with the ray starting on the camera plane.
+	pub fn set_from_camera(&mut self, coords: &Vector2, camera: &Camera) {
+		let mut camera_matrix_world = Matrix4::new();
+		camera_matrix_world.get_inverse(camera.get_matrix_world_inverse(), false);
+
+		if camera.get_projection_matrix().elements[ 15 ] == 0.0 {
+			self.ray.origin.set_from_matrix_position(&camera_matrix_world);
+			self.ray.direction.set(coords.x, coords.y, 0.5);
+			self.ray.direction.unproject(camera);
+			self.ray.direction.sub(&self.ray.origin);
+			self.ray.direction.normalize();
+		} else {
+			// the depth of view space z = 0 in clip space
+			let z = camera.get_projection_matrix().elements[ 14 ];
+			self.ray.origin.set(coords.x, coords.y, z);
+			self.ray.origin.unproject(camera);
+			self.ray.direction.set(0.0, 0.0, - 1.0);
+			self.ray.direction.transform_direction(&camera_matrix_world);
+		}
+	}
+
+	// Hits sorted by distance, nearest first.
+	pub fn intersect_object(&self, object: &Rc<RefCell<dyn HasObject3D>>, recursive: bool) -> Vec<Intersection> {
+		let mut intersects = vec![];
+		self.intersect(object, recursive, &mut intersects);
+		Raycaster::sort(&mut intersects);
+		intersects
+	}
+
+	pub fn intersect_objects(&self, objects: &[Rc<RefCell<dyn HasObject3D>>], recursive: bool) -> Vec<Intersection> {
+		let mut intersects = vec![];
+
+		for object in objects {
+			self.intersect(object, recursive, &mut intersects);
+		}
+
+		Raycaster::sort(&mut intersects);
+		intersects
+	}
+
+	fn intersect(&self, object: &Rc<RefCell<dyn HasObject3D>>, recursive: bool, intersects: &mut Vec<Intersection>) {
+		let o = object.borrow();
+
+		if o.get_object3d().get_layers().test(&self.layers) {
+			o.raycast(self, object, intersects);
+		}
+
+		if recursive {
+			for child in o.get_object3d().get_children() {
+				self.intersect(child, true, intersects);
+			}
+		}
+	}
+
+	fn sort(intersects: &mut [Intersection]) {
+		intersects.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap_or(Ordering::Equal));
+	}
+
+	fn in_range(&self, distance: f32) -> bool {
+		distance >= self.near && distance <= self.far
+	}
+
+	// Building blocks for HasObject3D::raycast. They take vertex positions as xyz triples in
+	// the object's local space, optionally indexed, and add the hits to intersects.
+
+	// Three vertices per triangle.
+	#[allow(clippy::too_many_arguments)]
+	pub fn raycast_triangles(&self, object: &Rc<RefCell<dyn HasObject3D>>, matrix_world: &Matrix4, positions: &[f32], indices: Option<&[u32]>, backface_culling: bool, intersects: &mut Vec<Intersection>) {
+		let mut inverse_matrix = Matrix4::new();
+		inverse_matrix.get_inverse(matrix_world, false);
+
+		let mut ray = self.ray;
+		ray.apply_matrix4(&inverse_matrix);
+
+		let count = vertex_count(positions, indices);
+		let mut a = Vector3::new();
+		let mut b = Vector3::new();
+		let mut c = Vector3::new();
+		let mut barycoord = Vector3::new();
+
+		for face in 0..count / 3 {
+			read_vertex(positions, indices, face * 3, &mut a);
+			read_vertex(positions, indices, face * 3 + 1, &mut b);
+			read_vertex(positions, indices, face * 3 + 2, &mut c);
+
+			let local_point = match ray.intersect_triangle(&a, &b, &c, backface_culling) {
+				Some(point) => point,
+				None => continue,
+			};
+
+			let mut point = local_point;
+			point.apply_matrix4(matrix_world);
+
+			let distance = self.ray.origin.distance_to(&point);
+
+			if !self.in_range(distance) {
+				continue;
+			}
+
+			Triangle::get_barycoord_from_points(&local_point, &a, &b, &c, &mut barycoord);
+
+			let mut intersection = Intersection::new(distance, &point, object);
+			intersection.face_index = Some(face);
+			intersection.uv = Some(Vector2 { x: barycoord.y, y: barycoord.z });
+			intersects.push(intersection);
+		}
+	}
+
+	// step is 1 for a connected line strip and 2 for separate segments.
+	#[allow(clippy::too_many_arguments)]
+	pub fn raycast_line_segments(&self, object: &Rc<RefCell<dyn HasObject3D>>, matrix_world: &Matrix4, positions: &[f32], indices: Option<&[u32]>, step: usize, intersects: &mut Vec<Intersection>) {
+		let mut inverse_matrix = Matrix4::new();
+		inverse_matrix.get_inverse(matrix_world, false);
+
+		let mut ray = self.ray;
+		ray.apply_matrix4(&inverse_matrix);
+
+		let local_threshold = self.line_threshold / average_scale(matrix_world);
+		let local_threshold_sq = local_threshold * local_threshold;
+
+		let count = vertex_count(positions, indices);
+		let mut v_start = Vector3::new();
+		let mut v_end = Vector3::new();
+		let mut inter_ray = Vector3::new();
+		let mut inter_segment = Vector3::new();
+
+		let mut i = 0;
+
+		while i + 1 < count {
+			read_vertex(positions, indices, i, &mut v_start);
+			read_vertex(positions, indices, i + 1, &mut v_end);
+
+			let dist_sq = ray.distance_sq_to_segment(&v_start, &v_end, Some(&mut inter_ray), Some(&mut inter_segment));
+
+			if dist_sq <= local_threshold_sq {
+				// move back to world space for the distance calculation
+				inter_ray.apply_matrix4(matrix_world);
+
+				let distance = self.ray.origin.distance_to(&inter_ray);
+
+				if self.in_range(distance) {
+					inter_segment.apply_matrix4(matrix_world);
+
+					let mut intersection = Intersection::new(distance, &inter_segment, object);
+					intersection.index = Some(i);
+					intersection.distance_to_ray = Some(inter_ray.distance_to(&inter_segment));
+					intersects.push(intersection);
+				}
+			}
+
+			i += step.max(1);
+		}
+	}
+
+	pub fn raycast_points(&self, object: &Rc<RefCell<dyn HasObject3D>>, matrix_world: &Matrix4, positions: &[f32], indices: Option<&[u32]>, intersects: &mut Vec<Intersection>) {
+		let mut inverse_matrix = Matrix4::new();
+		inverse_matrix.get_inverse(matrix_world, false);
+
+		let mut ray = self.ray;
+		ray.apply_matrix4(&inverse_matrix);
+
+		let local_threshold = self.points_threshold / average_scale(matrix_world);
+		let local_threshold_sq = local_threshold * local_threshold;
+
+		let count = vertex_count(positions, indices);
+		let mut position = Vector3::new();
+		let mut point = Vector3::new();
+
+		for i in 0..count {
+			read_vertex(positions, indices, i, &mut position);
+
+			let ray_point_distance_sq = ray.distance_sq_to_point(&position);
+
+			if ray_point_distance_sq >= local_threshold_sq {
+				continue;
+			}
+
+			ray.closest_point_to_point(&position, &mut point);
+			point.apply_matrix4(matrix_world);
+
+			let distance = self.ray.origin.distance_to(&point);
+
+			if !self.in_range(distance) {
+				continue;
+			}
+
+			let mut intersection = Intersection::new(distance, &point, object);
+			intersection.index = Some(i);
+			intersection.distance_to_ray = Some(ray_point_distance_sq.sqrt());
+			intersects.push(intersection);
+		}
+	}
+}
+
+impl Default for Raycaster {
+	fn default() -> Raycaster {
+		Raycaster::new()
+	}
+}
+
+fn vertex_count(positions: &[f32], indices: Option<&[u32]>) -> usize {
+	match indices {
+		Some(indices) => indices.len(),
+		None => positions.len() / 3,
+	}
+}
+
+fn read_vertex(positions: &[f32], indices: Option<&[u32]>, i: usize, target: &mut Vector3) {
+	let index = match indices {
+		Some(indices) => indices[ i ] as usize,
+		None => i,
+	};
+
+	target.copy_from_array(positions, Some(index * 3));
+}
+
+// Thresholds are given in world units, this scales them into local space.
+fn average_scale(matrix_world: &Matrix4) -> f32 {
+	let mut scale = Vector3::new();
+	scale.set_from_matrix_scale(matrix_world);
+	( scale.x + scale.y + scale.z ) / 3.0
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use super::super::object3d::Object3D;
+
+	#[derive(Clone, Copy)]
+	enum Kind {
+		Triangles,
+		Lines,
+		Points,
+	}
+
+	struct Shape {
+		object: Object3D,
+		kind: Kind,
+		positions: Vec<f32>,
+	}
+
+	impl HasObject3D for Shape {
+		fn get_object3d(&self) -> &Object3D {
+			&self.object
+		}
+
+		fn get_object3d_mut(&mut self) -> &mut Object3D {
+			&mut self.object
+		}
+
+		fn raycast(&self, raycaster: &Raycaster, object: &Rc<RefCell<dyn HasObject3D>>, intersects: &mut Vec<Intersection>) {
+			let matrix_world = self.object.get_matrix_world();
+
+			match self.kind {
+				Kind::Triangles => raycaster.raycast_triangles(object, matrix_world, &self.positions, None, false, intersects),
+				Kind::Lines => raycaster.raycast_line_segments(object, matrix_world, &self.positions, None, 1, intersects),
+				Kind::Points => raycaster.raycast_points(object, matrix_world, &self.positions, None, intersects),
+			}
+		}
+	}
+
+	fn shape(kind: Kind, positions: Vec<f32>, z: f32) -> Rc<RefCell<dyn HasObject3D>> {
+		let mut object = Object3D::new();
+		object.set_position(&Vector3 { x: 0.0, y: 0.0, z });
+		object.update_matrix_world(false);
+
+		Rc::new(RefCell::new(Shape { object, kind, positions }))
+	}
+
+	// A triangle around the z axis, facing +z.
+	fn triangle(z: f32) -> Rc<RefCell<dyn HasObject3D>> {
+		shape(Kind::Triangles, vec![ - 1.0, - 1.0, 0.0, 1.0, - 1.0, 0.0, 0.0, 1.0, 0.0 ], z)
+	}
+
+	// Looks down -z from z = 5.
+	fn raycaster() -> Raycaster {
+		let mut raycaster = Raycaster::new();
+		raycaster.set(&Vector3 { x: 0.0, y: 0.0, z: 5.0 }, &Vector3 { x: 0.0, y: 0.0, z: - 1.0 });
+		raycaster
+	}
+
+	fn camera(projection_matrix: Matrix4) -> Camera {
+		let mut camera = Camera::new();
+		camera.matrix_world_inverse.make_translation(0.0, 0.0, - 5.0);
+		camera.projection_matrix = projection_matrix;
+		camera
+	}
+
+	fn assert_close(a: &Vector3, b: &Vector3) {
+		assert!(a.distance_to(b) < 1e-4, "{:?} != {:?}", a, b);
+	}
+
+	#[test]
+	fn set_from_perspective_camera() {
+		let mut projection_matrix = Matrix4::new();
+		projection_matrix.make_frustum(- 1.0, 1.0, - 1.0, 1.0, 1.0, 100.0);
+		let camera = camera(projection_matrix);
+		let mut raycaster = Raycaster::new();
+
+		raycaster.set_from_camera(&Vector2 { x: 0.0, y: 0.0 }, &camera);
+		assert_close(&raycaster.ray.origin, &Vector3 { x: 0.0, y: 0.0, z: 5.0 });
+		assert_close(&raycaster.ray.direction, &Vector3 { x: 0.0, y: 0.0, z: - 1.0 });
+
+		// the right edge of a 90 degree frustum
+		raycaster.set_from_camera(&Vector2 { x: 1.0, y: 0.0 }, &camera);
+		let half = ::std::f32::consts::FRAC_1_SQRT_2;
+		assert_close(&raycaster.ray.origin, &Vector3 { x: 0.0, y: 0.0, z: 5.0 });
+		assert_close(&raycaster.ray.direction, &Vector3 { x: half, y: 0.0, z: - half });
+	}
+
+	#[test]
+	fn set_from_orthographic_camera() {
+		let mut projection_matrix = Matrix4::new();
+		projection_matrix.make_orthographic(- 2.0, 2.0, - 2.0, 2.0, 1.0, 100.0);
+		let camera = camera(projection_matrix);
+		let mut raycaster = Raycaster::new();
+
+		raycaster.set_from_camera(&Vector2 { x: 0.5, y: - 0.25 }, &camera);
+		assert_close(&raycaster.ray.origin, &Vector3 { x: 1.0, y: - 0.5, z: 5.0 });
+		assert_close(&raycaster.ray.direction, &Vector3 { x: 0.0, y: 0.0, z: - 1.0 });
+	}
+
+	#[test]
+	fn hits_between_near_and_far() {
+		let object = triangle(0.0);
+		let mut raycaster = raycaster();
+
+		let hits = raycaster.intersect_object(&object, false);
+		assert_eq!(hits.len(), 1);
+		assert!(( hits[ 0 ].distance - 5.0 ).abs() < 1e-5);
+		assert_close(&hits[ 0 ].point, &Vector3::new());
+		assert_eq!(hits[ 0 ].face_index, Some(0));
+		assert!(Rc::ptr_eq(&hits[ 0 ].object, &object));
+
+		raycaster.near = 6.0;
+		assert!(raycaster.intersect_object(&object, false).is_empty());
+
+		raycaster.near = 0.0;
+		raycaster.far = 4.0;
+		assert!(raycaster.intersect_object(&object, false).is_empty());
+	}
+
+	#[test]
+	fn sorts_hits_by_distance() {
+		let objects = vec![ triangle(0.0), triangle(- 2.0), triangle(2.0) ];
+		let hits = raycaster().intersect_objects(&objects, false);
+
+		let distances: Vec<f32> = hits.iter().map(|hit| hit.distance).collect();
+		assert_eq!(distances, vec![ 3.0, 5.0, 7.0 ]);
+		assert!(Rc::ptr_eq(&hits[ 0 ].object, &objects[ 2 ]));
+		assert!(Rc::ptr_eq(&hits[ 2 ].object, &objects[ 1 ]));
+	}
+
+	#[test]
+	fn recursive_includes_descendants() {
+		let parent = triangle(0.0);
+		let child = triangle(- 2.0);
+		Object3D::add(&parent, &child);
+		parent.borrow_mut().get_object3d_mut().update_matrix_world(true);
+
+		let raycaster = raycaster();
+		assert_eq!(raycaster.intersect_object(&parent, false).len(), 1);
+
+		let hits = raycaster.intersect_object(&parent, true);
+		assert_eq!(hits.len(), 2);
+		assert!(Rc::ptr_eq(&hits[ 1 ].object, &child));
+	}
+
+	#[test]
+	fn lines_and_points_within_the_threshold() {
+		// both half a unit from the ray
+		let line = shape(Kind::Lines, vec![ - 1.0, 0.5, 0.0, 1.0, 0.5, 0.0 ], 0.0);
+		let points = shape(Kind::Points, vec![ 3.0, 3.0, 0.0, 0.5, 0.0, 0.0 ], 0.0);
+		let mut raycaster = raycaster();
+
+		raycaster.line_threshold = 0.4;
+		raycaster.points_threshold = 0.4;
+		assert!(raycaster.intersect_object(&line, false).is_empty());
+		assert!(raycaster.intersect_object(&points, false).is_empty());
+
+		raycaster.line_threshold = 0.6;
+		raycaster.points_threshold = 0.6;
+
+		let hits = raycaster.intersect_object(&line, false);
+		assert_eq!(hits.len(), 1);
+		assert_eq!(hits[ 0 ].index, Some(0));
+		assert!(( hits[ 0 ].distance_to_ray.unwrap() - 0.5 ).abs() < 1e-5);
+		assert_close(&hits[ 0 ].point, &Vector3 { x: 0.0, y: 0.5, z: 0.0 });
+
+		let hits = raycaster.intersect_object(&points, false);
+		assert_eq!(hits.len(), 1);
+		assert_eq!(hits[ 0 ].index, Some(1));
+		assert!(( hits[ 0 ].distance_to_ray.unwrap() - 0.5 ).abs() < 1e-5);
+		assert!(( hits[ 0 ].distance - 5.0 ).abs() < 1e-5);
+	}
+
+	#[test]
+	fn skips_objects_on_other_layers() {
+		let parent = triangle(0.0);
+		let child = triangle(- 2.0);
+		Object3D::add(&parent, &child);
+		parent.borrow_mut().get_object3d_mut().update_matrix_world(true);
+		parent.borrow_mut().get_object3d_mut().get_layers_mut().set(1).unwrap();
+
+		// the children of a skipped object are still tested
+		let mut raycaster = raycaster();
+		let hits = raycaster.intersect_object(&parent, true);
+		assert_eq!(hits.len(), 1);
+		assert!(Rc::ptr_eq(&hits[ 0 ].object, &child));
+
+		raycaster.layers.set(1).unwrap();
+		let hits = raycaster.intersect_object(&parent, true);
+		assert_eq!(hits.len(), 1);
+		assert!(Rc::ptr_eq(&hits[ 0 ].object, &parent));
+
+		raycaster.layers.enable(0).unwrap();
+		assert_eq!(raycaster.intersect_object(&parent, true).len(), 2);
+	}
+}
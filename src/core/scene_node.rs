@@ -0,0 +1,261 @@
+use super::object3d::{Object3D, HasObject3D, DEFAULT_UP};
+use super::layers::Layers;
+use super::user_data::UserData;
+use super::change_tracker::next_version;
+use super::transform;
+use super::super::math::vector3::Vector3;
+use super::super::math::quaternion::Quaternion;
+use super::super::math::matrix4::Matrix4;
+use super::super::math::euler::Euler;
+
+// The part of an Object3D a core::arena::SceneGraph keeps per node. There are no Rc links,
+// listeners or components in here, which is what lets a whole graph move between threads.
+// The transform follows the same rules as Object3D's, versions included.
+#[derive(Debug, Clone)]
+pub struct SceneNode {
+	pub name: String,
+	pub visible: bool,
+	pub cast_shadow: bool,
+	pub receive_shadow: bool,
+	pub frustum_culled: bool,
+	pub layers: Layers,
+	pub render_order: u32,
+	pub user_data: UserData,
+	up: Vector3,
+	position: Vector3,
+	quaternion: Quaternion,
+	scale: Vector3,
+	matrix: Matrix4,
+	matrix_world: Matrix4,
+	matrix_auto_update: bool,
+	matrix_world_needs_update: bool,
+	transform_version: u64,
+	matrix_world_version: u64,
+}
+
+impl SceneNode {
+	pub fn new() -> SceneNode {
+		SceneNode {
+			name: String::new(),
+			visible: true,
+			cast_shadow: false,
+			receive_shadow: false,
+			frustum_culled: true,
+			layers: Layers::new(),
+			render_order: 0,
+			user_data: UserData::new(),
+			up: unsafe {DEFAULT_UP},
+			position: Vector3::new(),
+			quaternion: Quaternion::new(),
+			scale: Vector3 {
+				x: 1.0,
+				y: 1.0,
+				z: 1.0,
+			},
+			matrix: Matrix4::new(),
+			matrix_world: Matrix4::new(),
+			matrix_auto_update: true,
+			matrix_world_needs_update: false,
+			transform_version: next_version(),
+			matrix_world_version: next_version(),
+		}
+	}
+
+	// Copies what a SceneNode has of the object, the versions included.
+	pub fn from_object3d(object: &dyn HasObject3D) -> SceneNode {
+		let object = object.get_object3d();
+
+		SceneNode {
+			name: object.get_name().to_string(),
+			visible: object.is_visible(),
+			cast_shadow: object.get_cast_shadow(),
+			receive_shadow: object.get_receive_shadow(),
+			frustum_culled: object.get_frustum_culled(),
+			layers: *object.get_layers(),
+			render_order: object.get_render_order(),
+			user_data: object.get_user_data().clone(),
+			up: *object.get_up(),
+			position: *object.get_position(),
+			quaternion: *object.get_quaternion(),
+			scale: *object.get_scale(),
+			matrix: *object.get_matrix(),
+			matrix_world: *object.get_matrix_world(),
+			matrix_auto_update: object.get_matrix_auto_update(),
+			matrix_world_needs_update: object.get_matrix_world_needs_update(),
+			transform_version: object.get_transform_version(),
+			matrix_world_version: object.get_matrix_world_version(),
+		}
+	}
+
+	// A new Object3D with this node's properties, for code written against HasObject3D. The
+	// versions are carried over as well, the object is no change to the node.
+	pub fn to_object3d(&self) -> Object3D {
+		let mut object = Object3D::new();
+		object.set_name(&self.name);
+		object.set_visible(self.visible);
+		object.set_cast_shadow(self.cast_shadow);
+		object.set_receive_shadow(self.receive_shadow);
+		object.set_frustum_culled(self.frustum_culled);
+		object.get_layers_mut().mask = self.layers.mask;
+		object.set_render_order(self.render_order);
+		object.set_user_data(self.user_data.clone());
+		object.set_up(&self.up);
+		object.set_position(&self.position);
+		object.set_quaternion(&self.quaternion);
+		object.set_scale(&self.scale);
+		object.set_matrix_auto_update(self.matrix_auto_update);
+		object.set_matrix(&self.matrix);
+		object.set_matrix_world(&self.matrix_world);
+		object.set_matrix_world_needs_update(self.matrix_world_needs_update);
+		object.set_versions(self.transform_version, self.matrix_world_version);
+		object
+	}
+
+	pub fn get_up(&self) -> &Vector3 {
+		&self.up
+	}
+
+	pub fn set_up(&mut self, up: &Vector3) {
+		self.up.copy(up);
+	}
+
+	pub fn get_position(&self) -> &Vector3 {
+		&self.position
+	}
+
+	pub fn set_position(&mut self, position: &Vector3) {
+		self.position.copy(position);
+		self.transform_needs_update();
+	}
+
+	pub fn get_quaternion(&self) -> &Quaternion {
+		&self.quaternion
+	}
+
+	pub fn set_quaternion(&mut self, quaternion: &Quaternion) {
+		self.quaternion.copy(quaternion);
+		self.transform_needs_update();
+	}
+
+	pub fn set_rotation(&mut self, euler: &Euler) {
+		self.quaternion.set_from_euler(euler);
+		self.transform_needs_update();
+	}
+
+	pub fn get_scale(&self) -> &Vector3 {
+		&self.scale
+	}
+
+	pub fn set_scale(&mut self, scale: &Vector3) {
+		self.scale.copy(scale);
+		self.transform_needs_update();
+	}
+
+	pub fn rotate_on_axis(&mut self, axis: &Vector3, angle: f32) {
+		transform::rotate_on_axis(&mut self.quaternion, axis, angle);
+		self.transform_needs_update();
+	}
+
+	pub fn translate_on_axis(&mut self, axis: &Vector3, distance: f32) {
+		transform::translate_on_axis(&mut self.position, &self.quaternion, axis, distance);
+		self.transform_needs_update();
+	}
+
+	// See Object3D::look_at. A node has no parent link, so the parent's world matrix is passed
+	// in, and the node's own is used as it is, see SceneGraph::look_at.
+	pub fn look_at(&mut self, vector: &Vector3, parent_matrix_world: Option<&Matrix4>) {
+		let mut position = Vector3::new();
+		position.set_from_matrix_position(&self.matrix_world);
+
+		transform::look_at(&mut self.quaternion, vector, &position, &self.up, parent_matrix_world);
+		self.transform_needs_update();
+	}
+
+	pub fn get_matrix_auto_update(&self) -> bool {
+		self.matrix_auto_update
+	}
+
+	pub fn set_matrix_auto_update(&mut self, matrix_auto_update: bool) {
+		self.matrix_auto_update = matrix_auto_update;
+	}
+
+	pub fn get_matrix_world_needs_update(&self) -> bool {
+		self.matrix_world_needs_update
+	}
+
+	pub fn set_matrix_world_needs_update(&mut self, matrix_world_needs_update: bool) {
+		self.matrix_world_needs_update = matrix_world_needs_update;
+	}
+
+	pub fn transform_needs_update(&mut self) {
+		self.transform_version = next_version();
+		self.matrix_world_needs_update = true;
+	}
+
+	pub fn get_transform_version(&self) -> u64 {
+		self.transform_version
+	}
+
+	pub fn get_matrix_world_version(&self) -> u64 {
+		self.matrix_world_version
+	}
+
+	pub fn changed_since(&self, frame: u64) -> bool {
+		self.transform_version > frame || self.matrix_world_version > frame
+	}
+
+	pub fn get_matrix(&self) -> &Matrix4 {
+		&self.matrix
+	}
+
+	// Only sticks while matrix_auto_update is off, otherwise the next update recomposes it.
+	pub fn set_matrix(&mut self, matrix: &Matrix4) {
+		self.matrix.copy(matrix);
+		self.transform_needs_update();
+	}
+
+	// See Object3D::set_composed_matrix.
+	pub fn set_composed_matrix(&mut self, matrix: &Matrix4) {
+		self.matrix.copy(matrix);
+		self.matrix_world_needs_update = true;
+	}
+
+	pub fn get_matrix_world(&self) -> &Matrix4 {
+		&self.matrix_world
+	}
+
+	pub fn set_matrix_world(&mut self, matrix_world: &Matrix4) {
+		if self.matrix_world.elements != matrix_world.elements {
+			self.matrix_world.copy(matrix_world);
+			self.matrix_world_version = next_version();
+		}
+
+		self.matrix_world_needs_update = false;
+	}
+
+	pub fn update_matrix(&mut self) {
+		self.matrix.compose(&self.position, &self.quaternion, &self.scale);
+		self.matrix_world_needs_update = true;
+	}
+
+	// See Object3D::update_matrix_world_with_parent.
+	pub fn update_matrix_world_with_parent(&mut self, parent_matrix_world: Option<&Matrix4>, force: bool) -> bool {
+		if self.matrix_auto_update {
+			self.update_matrix();
+		}
+
+		if self.matrix_world_needs_update || force {
+			let matrix_world = transform::world_matrix(&self.matrix, parent_matrix_world);
+			self.set_matrix_world(&matrix_world);
+			return true;
+		}
+
+		false
+	}
+}
+
+impl Default for SceneNode {
+	fn default() -> SceneNode {
+		SceneNode::new()
+	}
+}
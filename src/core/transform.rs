@@ -0,0 +1,47 @@
+use super::super::math::vector3::Vector3;
+use super::super::math::quaternion::Quaternion;
+use super::super::math::matrix4::Matrix4;
+
+// The transform arithmetic Object3D and SceneNode have in common, so the two can't drift
+// apart.
+
+pub(crate) fn rotate_on_axis(quaternion: &mut Quaternion, axis: &Vector3, angle: f32) {
+	let mut q1 = Quaternion::new();
+	q1.set_from_axis_angle(axis, angle);
+	quaternion.multiply(&q1);
+}
+
+pub(crate) fn translate_on_axis(position: &mut Vector3, quaternion: &Quaternion, axis: &Vector3, distance: f32) {
+	let mut v1 = Vector3::new();
+	v1.copy(axis);
+	v1.apply_quaternion(quaternion);
+	v1.multiply_scalar(distance);
+	position.add(&v1);
+}
+
+pub(crate) fn world_matrix(matrix: &Matrix4, parent_matrix_world: Option<&Matrix4>) -> Matrix4 {
+	let mut matrix_world = *matrix;
+
+	if let Some(m) = parent_matrix_world {
+		matrix_world.multiply_matrices(m, matrix);
+	}
+
+	matrix_world
+}
+
+// The local rotation that turns the z axis from position towards target, both in world
+// space. The parent's world rotation is taken out again.
+pub(crate) fn look_at(quaternion: &mut Quaternion, target: &Vector3, position: &Vector3, up: &Vector3, parent_matrix_world: Option<&Matrix4>) {
+	let mut m1 = Matrix4::new();
+	m1.look_at(target, position, up);
+	quaternion.set_from_rotation_matrix(&m1);
+
+	if let Some(parent_matrix_world) = parent_matrix_world {
+		let mut q1 = Quaternion::new();
+		m1.identity();
+		m1.extract_rotation(parent_matrix_world);
+		q1.set_from_rotation_matrix(&m1);
+		q1.inverse();
+		quaternion.premultiply(&q1);
+	}
+}
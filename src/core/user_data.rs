@@ -0,0 +1,248 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+// A JSON value, for data that should be saved together with the node. Objects keep their
+// keys sorted so the output is stable.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UserData {
+	Null,
+	Bool(bool),
+	Number(f64),
+	String(String),
+	Array(Vec<UserData>),
+	Object(BTreeMap<String, UserData>),
+}
+
+impl UserData {
+	pub fn new() -> UserData {
+		UserData::Object(BTreeMap::new())
+	}
+
+	pub fn is_null(&self) -> bool {
+		*self == UserData::Null
+	}
+
+	pub fn as_bool(&self) -> Option<bool> {
+		match *self {
+			UserData::Bool(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	pub fn as_f64(&self) -> Option<f64> {
+		match *self {
+			UserData::Number(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	pub fn as_str(&self) -> Option<&str> {
+		match *self {
+			UserData::String(ref value) => Some(value),
+			_ => None,
+		}
+	}
+
+	pub fn as_array(&self) -> Option<&Vec<UserData>> {
+		match *self {
+			UserData::Array(ref value) => Some(value),
+			_ => None,
+		}
+	}
+
+	pub fn as_object(&self) -> Option<&BTreeMap<String, UserData>> {
+		match *self {
+			UserData::Object(ref value) => Some(value),
+			_ => None,
+		}
+	}
+
+	// Looks up a key of an object, None for anything else.
+	pub fn get(&self, key: &str) -> Option<&UserData> {
+		self.as_object().and_then(|object| object.get(key))
+	}
+
+	pub fn get_mut(&mut self, key: &str) -> Option<&mut UserData> {
+		match *self {
+			UserData::Object(ref mut object) => object.get_mut(key),
+			_ => None,
+		}
+	}
+
+	// Sets a key, turning the value into an empty object first if it isn't one.
+	pub fn insert<V: Into<UserData>>(&mut self, key: &str, value: V) -> Option<UserData> {
+		if self.as_object().is_none() {
+			*self = UserData::new();
+		}
+
+		match *self {
+			UserData::Object(ref mut object) => object.insert(key.to_string(), value.into()),
+			_ => None,
+		}
+	}
+
+	pub fn remove(&mut self, key: &str) -> Option<UserData> {
+		match *self {
+			UserData::Object(ref mut object) => object.remove(key),
+			_ => None,
+		}
+	}
+
+	pub fn to_json(&self) -> String {
+		let mut json = String::new();
+		self.write_json(&mut json);
+		json
+	}
+
+	pub fn write_json(&self, json: &mut String) {
+		match *self {
+			UserData::Null => json.push_str("null"),
+			UserData::Bool(value) => json.push_str(if value { "true" } else { "false" }),
+			// JSON has no NaN or infinities
+			UserData::Number(value) => if value.is_finite() {
+				write!(json, "{}", value).unwrap();
+			} else {
+				json.push_str("null");
+			},
+			UserData::String(ref value) => write_json_string(value, json),
+			UserData::Array(ref values) => {
+				json.push('[');
+				for (i, value) in values.iter().enumerate() {
+					if i > 0 {
+						json.push(',');
+					}
+					value.write_json(json);
+				}
+				json.push(']');
+			},
+			UserData::Object(ref object) => {
+				json.push('{');
+				for (i, (key, value)) in object.iter().enumerate() {
+					if i > 0 {
+						json.push(',');
+					}
+					write_json_string(key, json);
+					json.push(':');
+					value.write_json(json);
+				}
+				json.push('}');
+			},
+		}
+	}
+}
+
+impl Default for UserData {
+	fn default() -> UserData {
+		UserData::new()
+	}
+}
+
+pub fn write_json_string(value: &str, json: &mut String) {
+	json.push('"');
+
+	for c in value.chars() {
+		match c {
+			'"' => json.push_str("\\\""),
+			'\\' => json.push_str("\\\\"),
+			'\n' => json.push_str("\\n"),
+			'\r' => json.push_str("\\r"),
+			'\t' => json.push_str("\\t"),
+			c if ( c as u32 ) < 0x20 => write!(json, "\\u{:04x}", c as u32).unwrap(),
+			c => json.push(c),
+		}
+	}
+
+	json.push('"');
+}
+
+impl From<bool> for UserData {
+	fn from(value: bool) -> UserData {
+		UserData::Bool(value)
+	}
+}
+
+impl From<f64> for UserData {
+	fn from(value: f64) -> UserData {
+		UserData::Number(value)
+	}
+}
+
+impl From<f32> for UserData {
+	fn from(value: f32) -> UserData {
+		UserData::Number(value as f64)
+	}
+}
+
+impl From<i32> for UserData {
+	fn from(value: i32) -> UserData {
+		UserData::Number(value as f64)
+	}
+}
+
+impl<'a> From<&'a str> for UserData {
+	fn from(value: &'a str) -> UserData {
+		UserData::String(value.to_string())
+	}
+}
+
+impl From<String> for UserData {
+	fn from(value: String) -> UserData {
+		UserData::String(value)
+	}
+}
+
+impl<T: Into<UserData>> From<Vec<T>> for UserData {
+	fn from(values: Vec<T>) -> UserData {
+		UserData::Array(values.into_iter().map(|value| value.into()).collect())
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn insert_get_and_remove() {
+		let mut data = UserData::new();
+		assert_eq!(data.insert("hp", 10), None);
+		assert_eq!(data.insert("hp", 12), Some(UserData::Number(10.0)));
+		data.insert("name", "orc");
+		data.insert("alive", true);
+
+		assert_eq!(data.get("hp").and_then(|v| v.as_f64()), Some(12.0));
+		assert_eq!(data.get("name").and_then(|v| v.as_str()), Some("orc"));
+		assert_eq!(data.get("alive").and_then(|v| v.as_bool()), Some(true));
+		assert!(data.get("missing").is_none());
+
+		*data.get_mut("hp").unwrap() = UserData::Null;
+		assert!(data.get("hp").unwrap().is_null());
+
+		assert_eq!(data.remove("name"), Some(UserData::String("orc".to_string())));
+		assert_eq!(data.as_object().unwrap().len(), 2);
+	}
+
+	#[test]
+	fn insert_turns_other_values_into_objects() {
+		let mut data = UserData::Number(1.0);
+		assert!(data.get("a").is_none());
+		assert!(data.remove("a").is_none());
+
+		data.insert("a", 1);
+		assert_eq!(data.to_json(), "{\"a\":1}");
+	}
+
+	#[test]
+	fn to_json_sorts_keys_and_escapes_strings() {
+		let mut data = UserData::new();
+		data.insert("z", vec![ 1.5, 2.0 ]);
+		data.insert("a", "say \"hi\"\n\\\u{1}");
+		data.insert("m", UserData::Null);
+		data.insert("nan", f64::NAN);
+		data.insert("empty", UserData::Array(vec![]));
+
+		assert_eq!(data.to_json(), "{\"a\":\"say \\\"hi\\\"\\n\\\\\\u0001\",\"empty\":[],\"m\":null,\"nan\":null,\"z\":[1.5,2]}");
+		assert_eq!(UserData::new().to_json(), "{}");
+		assert_eq!(UserData::Bool(false).to_json(), "false");
+	}
+}
@@ -0,0 +1,28 @@
+use super::super::super::math::vector2::Vector2;
+use super::curve::Curve;
+use super::ellipse_curve::EllipseCurve;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ArcCurve {
+	pub ellipse: EllipseCurve,
+}
+
+impl ArcCurve {
+	pub fn new(a_x: f32, a_y: f32, a_radius: f32, a_start_angle: f32, a_end_angle: f32, a_clockwise: bool) -> ArcCurve {
+		ArcCurve {
+			ellipse: EllipseCurve::new(a_x, a_y, a_radius, a_radius, a_start_angle, a_end_angle, a_clockwise, 0.0),
+		}
+	}
+}
+
+impl Curve for ArcCurve {
+	type Vector = Vector2;
+
+	fn get_resolution(&self, divisions: usize) -> usize {
+		divisions * 2
+	}
+
+	fn get_point(&self, t: f32) -> Vector2 {
+		self.ellipse.get_point(t)
+	}
+}
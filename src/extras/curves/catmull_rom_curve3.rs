@@ -0,0 +1,216 @@
+use super::super::super::math::vector3::Vector3;
+use super::curve::{Curve, LengthCache};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CatmullRomType {
+	Centripetal,
+	Chordal,
+	CatmullRom,
+}
+
+/*
+Based on an optimized c++ solution in
+ - http://stackoverflow.com/questions/9489736/catmull-rom-curve-with-no-cusps-and-no-self-intersections/
+ - http://ideone.com/NoEbVM
+*/
+#[derive(Debug, Clone, Copy)]
+struct CubicPoly {
+	c0: f32,
+	c1: f32,
+	c2: f32,
+	c3: f32,
+}
+
+impl CubicPoly {
+	/*
+	 * Compute coefficients for a cubic polynomial
+	 *   p(s) = c0 + c1*s + c2*s^2 + c3*s^3
+	 * such that
+	 *   p(0) = x0, p(1) = x1
+	 *  and
+	 *   p'(0) = t0, p'(1) = t1.
+	 */
+	fn new(x0: f32, x1: f32, t0: f32, t1: f32) -> CubicPoly {
+		CubicPoly {
+			c0: x0,
+			c1: t0,
+			c2: - 3.0 * x0 + 3.0 * x1 - 2.0 * t0 - t1,
+			c3: 2.0 * x0 - 2.0 * x1 + t0 + t1,
+		}
+	}
+
+	fn catmull_rom(x0: f32, x1: f32, x2: f32, x3: f32, tension: f32) -> CubicPoly {
+		CubicPoly::new(x1, x2, tension * ( x2 - x0 ), tension * ( x3 - x1 ))
+	}
+
+	fn nonuniform_catmull_rom(x0: f32, x1: f32, x2: f32, x3: f32, dt0: f32, dt1: f32, dt2: f32) -> CubicPoly {
+		// compute tangents when parameterized in [t1,t2]
+		let mut t1 = ( x1 - x0 ) / dt0 - ( x2 - x0 ) / ( dt0 + dt1 ) + ( x2 - x1 ) / dt1;
+		let mut t2 = ( x2 - x1 ) / dt1 - ( x3 - x1 ) / ( dt1 + dt2 ) + ( x3 - x2 ) / dt2;
+
+		// rescale tangents for parametrization in [0,1]
+		t1 *= dt1;
+		t2 *= dt1;
+
+		CubicPoly::new(x1, x2, t1, t2)
+	}
+
+	fn calc(&self, t: f32) -> f32 {
+		let t2 = t * t;
+		let t3 = t2 * t;
+		self.c0 + self.c1 * t + self.c2 * t2 + self.c3 * t3
+	}
+}
+
+// After changing the fields, call update_arc_lengths.
+#[derive(Debug, Clone)]
+pub struct CatmullRomCurve3 {
+	pub points: Vec<Vector3>,
+	pub closed: bool,
+	pub curve_type: CatmullRomType,
+	pub tension: f32,
+	arc_lengths: LengthCache,
+}
+
+impl CatmullRomCurve3 {
+	pub fn new(points: Vec<Vector3>, closed: bool, curve_type: CatmullRomType, tension: f32) -> CatmullRomCurve3 {
+		CatmullRomCurve3 {
+			points,
+			closed,
+			curve_type,
+			tension,
+			arc_lengths: LengthCache::new(),
+		}
+	}
+}
+
+impl Curve for CatmullRomCurve3 {
+	type Vector = Vector3;
+
+	fn get_arc_length_cache(&self) -> Option<&LengthCache> {
+		Some(&self.arc_lengths)
+	}
+
+	// Without two points there is nothing to interpolate, the curve is the one point or the origin.
+	fn get_point(&self, t: f32) -> Vector3 {
+		let points = &self.points;
+		let l = points.len() as i64;
+
+		if l < 2 {
+			return points.first().cloned().unwrap_or_else(Vector3::new);
+		}
+
+		let p = ( l - if self.closed { 0 } else { 1 } ) as f32 * t;
+		let mut int_point = p.floor() as i64;
+		let mut weight = p - int_point as f32;
+
+		if self.closed {
+			if int_point <= 0 {
+				int_point += ( int_point.abs() / l + 1 ) * l;
+			}
+		} else if weight == 0.0 && int_point == l - 1 {
+			int_point = l - 2;
+			weight = 1.0;
+		}
+
+		let at = |i: i64| points[i.rem_euclid(l) as usize];
+
+		// extrapolate first point
+		let p0 = if self.closed || int_point > 0 {
+			at(int_point - 1)
+		} else {
+			let mut tmp = Vector3::new();
+			tmp.sub_vectors(&points[0], &points[1]);
+			tmp.add(&points[0]);
+			tmp
+		};
+
+		let p1 = at(int_point);
+		let p2 = at(int_point + 1);
+
+		// extrapolate last point
+		let p3 = if self.closed || int_point + 2 < l {
+			at(int_point + 2)
+		} else {
+			let last = ( l - 1 ) as usize;
+			let mut tmp = Vector3::new();
+			tmp.sub_vectors(&points[last], &points[last - 1]);
+			tmp.add(&points[last]);
+			tmp
+		};
+
+		let (px, py, pz) = match self.curve_type {
+			CatmullRomType::Centripetal | CatmullRomType::Chordal => {
+				// init Centripetal / Chordal Catmull-Rom
+				let pow = if self.curve_type == CatmullRomType::Chordal { 0.5 } else { 0.25 };
+				let mut dt0 = p0.distance_to_squared(&p1).powf(pow);
+				let mut dt1 = p1.distance_to_squared(&p2).powf(pow);
+				let mut dt2 = p2.distance_to_squared(&p3).powf(pow);
+
+				// safety check for repeated points
+				if dt1 < 1e-4 {
+					dt1 = 1.0;
+				}
+				if dt0 < 1e-4 {
+					dt0 = dt1;
+				}
+				if dt2 < 1e-4 {
+					dt2 = dt1;
+				}
+
+				(
+					CubicPoly::nonuniform_catmull_rom(p0.x, p1.x, p2.x, p3.x, dt0, dt1, dt2),
+					CubicPoly::nonuniform_catmull_rom(p0.y, p1.y, p2.y, p3.y, dt0, dt1, dt2),
+					CubicPoly::nonuniform_catmull_rom(p0.z, p1.z, p2.z, p3.z, dt0, dt1, dt2),
+				)
+			},
+			CatmullRomType::CatmullRom => {
+				(
+					CubicPoly::catmull_rom(p0.x, p1.x, p2.x, p3.x, self.tension),
+					CubicPoly::catmull_rom(p0.y, p1.y, p2.y, p3.y, self.tension),
+					CubicPoly::catmull_rom(p0.z, p1.z, p2.z, p3.z, self.tension),
+				)
+			}
+		};
+
+		Vector3 {
+			x: px.calc(weight),
+			y: py.calc(weight),
+			z: pz.calc(weight),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn point(x: f32, y: f32, z: f32) -> Vector3 {
+		Vector3 { x, y, z }
+	}
+
+	#[test]
+	fn degenerate_point_lists() {
+		let empty = CatmullRomCurve3::new(vec![], false, CatmullRomType::Centripetal, 0.5);
+		assert!(empty.get_point(0.5).equals(&Vector3::new()));
+		assert_eq!(empty.get_length(), 0.0);
+
+		for &closed in &[ false, true ] {
+			let single = CatmullRomCurve3::new(vec![ point(1.0, 2.0, 3.0) ], closed, CatmullRomType::CatmullRom, 0.5);
+			assert!(single.get_point(0.0).equals(&point(1.0, 2.0, 3.0)));
+			assert!(single.get_point_at(1.0).equals(&point(1.0, 2.0, 3.0)));
+		}
+	}
+
+	#[test]
+	fn arc_lengths_are_cached_until_updated() {
+		let mut curve = CatmullRomCurve3::new(vec![ point(0.0, 0.0, 0.0), point(1.0, 0.0, 0.0) ], false, CatmullRomType::Centripetal, 0.5);
+		assert!((curve.get_length() - 1.0).abs() < 1e-4);
+
+		curve.points[1].x = 2.0;
+		assert!((curve.get_length() - 1.0).abs() < 1e-4);
+
+		curve.update_arc_lengths();
+		assert!((curve.get_length() - 2.0).abs() < 1e-4);
+	}
+}
@@ -0,0 +1,38 @@
+use super::super::super::math::vector2::Vector2;
+use super::curve::Curve;
+use super::interpolations::cubic_bezier;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CubicBezierCurve {
+	pub v0: Vector2,
+	pub v1: Vector2,
+	pub v2: Vector2,
+	pub v3: Vector2,
+}
+
+impl CubicBezierCurve {
+	pub fn new(v0: Vector2, v1: Vector2, v2: Vector2, v3: Vector2) -> CubicBezierCurve {
+		CubicBezierCurve {
+			v0,
+			v1,
+			v2,
+			v3,
+		}
+	}
+}
+
+impl Curve for CubicBezierCurve {
+	type Vector = Vector2;
+
+	fn get_point(&self, t: f32) -> Vector2 {
+		let v0 = &self.v0;
+		let v1 = &self.v1;
+		let v2 = &self.v2;
+		let v3 = &self.v3;
+
+		Vector2 {
+			x: cubic_bezier(t, v0.x, v1.x, v2.x, v3.x),
+			y: cubic_bezier(t, v0.y, v1.y, v2.y, v3.y),
+		}
+	}
+}
@@ -0,0 +1,39 @@
+use super::super::super::math::vector3::Vector3;
+use super::curve::Curve;
+use super::interpolations::cubic_bezier;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CubicBezierCurve3 {
+	pub v0: Vector3,
+	pub v1: Vector3,
+	pub v2: Vector3,
+	pub v3: Vector3,
+}
+
+impl CubicBezierCurve3 {
+	pub fn new(v0: Vector3, v1: Vector3, v2: Vector3, v3: Vector3) -> CubicBezierCurve3 {
+		CubicBezierCurve3 {
+			v0,
+			v1,
+			v2,
+			v3,
+		}
+	}
+}
+
+impl Curve for CubicBezierCurve3 {
+	type Vector = Vector3;
+
+	fn get_point(&self, t: f32) -> Vector3 {
+		let v0 = &self.v0;
+		let v1 = &self.v1;
+		let v2 = &self.v2;
+		let v3 = &self.v3;
+
+		Vector3 {
+			x: cubic_bezier(t, v0.x, v1.x, v2.x, v3.x),
+			y: cubic_bezier(t, v0.y, v1.y, v2.y, v3.y),
+			z: cubic_bezier(t, v0.z, v1.z, v2.z, v3.z),
+		}
+	}
+}
@@ -0,0 +1,373 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::rc::Rc;
+use super::super::super::math::vector2::Vector2;
+use super::super::super::math::vector3::Vector3;
+use super::super::super::math::matrix4::Matrix4;
+use super::super::super::math::math_static::clamp;
+
+pub const DEFAULT_ARC_LENGTH_DIVISIONS: usize = 200;
+
+// The vector operations a curve needs to measure and sample itself, so the same
+// arc-length code serves both the 2D and 3D curves.
+pub trait CurveVector: Copy {
+	fn new() -> Self;
+	fn sub_vectors(&mut self, a: &Self, b: &Self);
+	fn normalize(&mut self);
+	fn distance_to(&self, v: &Self) -> f32;
+	fn equals(&self, v: &Self) -> bool;
+	fn to_vector3(&self) -> Vector3;
+}
+
+impl CurveVector for Vector2 {
+	fn new() -> Vector2 {
+		Vector2::new()
+	}
+
+	fn sub_vectors(&mut self, a: &Vector2, b: &Vector2) {
+		Vector2::sub_vectors(self, a, b);
+	}
+
+	fn normalize(&mut self) {
+		Vector2::normalize(self);
+	}
+
+	fn distance_to(&self, v: &Vector2) -> f32 {
+		Vector2::distance_to(self, v)
+	}
+
+	fn equals(&self, v: &Vector2) -> bool {
+		Vector2::equals(self, v)
+	}
+
+	fn to_vector3(&self) -> Vector3 {
+		Vector3 {
+			x: self.x,
+			y: self.y,
+			z: 0.0,
+		}
+	}
+}
+
+impl CurveVector for Vector3 {
+	fn new() -> Vector3 {
+		Vector3::new()
+	}
+
+	fn sub_vectors(&mut self, a: &Vector3, b: &Vector3) {
+		Vector3::sub_vectors(self, a, b);
+	}
+
+	fn normalize(&mut self) {
+		Vector3::normalize(self);
+	}
+
+	fn distance_to(&self, v: &Vector3) -> f32 {
+		Vector3::distance_to(self, v)
+	}
+
+	fn equals(&self, v: &Vector3) -> bool {
+		Vector3::equals(self, v)
+	}
+
+	fn to_vector3(&self) -> Vector3 {
+		*self
+	}
+}
+
+// Lengths a curve measured once and keeps until it's told it changed, like cacheArcLengths
+// and needsUpdate in three.js.
+#[derive(Debug, Clone, Default)]
+pub struct LengthCache {
+	lengths: RefCell<Option<Rc<Vec<f32>>>>,
+}
+
+impl LengthCache {
+	pub fn new() -> LengthCache {
+		LengthCache {
+			lengths: RefCell::new(None),
+		}
+	}
+
+	pub fn get<F: FnOnce() -> Vec<f32>>(&self, compute: F) -> Rc<Vec<f32>> {
+		if let Some(ref lengths) = *self.lengths.borrow() {
+			return lengths.clone();
+		}
+
+		let lengths = Rc::new(compute());
+		*self.lengths.borrow_mut() = Some(lengths.clone());
+		lengths
+	}
+
+	pub fn clear(&self) {
+		*self.lengths.borrow_mut() = None;
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct FrenetFrames {
+	pub tangents: Vec<Vector3>,
+	pub normals: Vec<Vector3>,
+	pub binormals: Vec<Vector3>,
+}
+
+pub trait Curve {
+	type Vector: CurveVector;
+
+	fn get_point(&self, t: f32) -> Self::Vector;
+
+	fn get_arc_length_divisions(&self) -> usize {
+		DEFAULT_ARC_LENGTH_DIVISIONS
+	}
+
+	// How many samples this curve needs when a path is flattened with the given divisions;
+	// straight segments need none in between and ellipses need more.
+	fn get_resolution(&self, divisions: usize) -> usize {
+		divisions
+	}
+
+	fn get_point_at(&self, u: f32) -> Self::Vector {
+		let t = self.get_u_to_t_mapping(u, None);
+		self.get_point(t)
+	}
+
+	// divisions + 1 points from start to end. Zero divisions count as one, so both ends are
+	// always there.
+	fn get_points(&self, divisions: usize) -> Vec<Self::Vector> {
+		let divisions = divisions.max(1);
+		(0..divisions + 1).map(|d| self.get_point(d as f32 / divisions as f32)).collect()
+	}
+
+	fn get_spaced_points(&self, divisions: usize) -> Vec<Self::Vector> {
+		let divisions = divisions.max(1);
+		(0..divisions + 1).map(|d| self.get_point_at(d as f32 / divisions as f32)).collect()
+	}
+
+	// Curves that keep their arc lengths return the cache here, the others measure again
+	// every time.
+	fn get_arc_length_cache(&self) -> Option<&LengthCache> {
+		None
+	}
+
+	// Call after changing a curve that caches its arc lengths.
+	fn update_arc_lengths(&self) {
+		if let Some(cache) = self.get_arc_length_cache() {
+			cache.clear();
+		}
+	}
+
+	// get_lengths at the arc length divisions, cached if the curve keeps a cache.
+	fn get_arc_lengths(&self) -> Rc<Vec<f32>> {
+		let divisions = self.get_arc_length_divisions();
+
+		match self.get_arc_length_cache() {
+			Some(cache) => cache.get(|| self.get_lengths(divisions)),
+			None => Rc::new(self.get_lengths(divisions)),
+		}
+	}
+
+	fn get_length(&self) -> f32 {
+		let lengths = self.get_arc_lengths();
+		lengths[lengths.len() - 1]
+	}
+
+	// Like get_points, at least one division; get_u_to_t_mapping needs two lengths to search.
+	fn get_lengths(&self, divisions: usize) -> Vec<f32> {
+		let divisions = divisions.max(1);
+		let mut cache = Vec::with_capacity(divisions + 1);
+		let mut last = self.get_point(0.0);
+		let mut sum = 0.0;
+
+		cache.push(0.0);
+
+		for p in 1..divisions + 1 {
+			let current = self.get_point(p as f32 / divisions as f32);
+			sum += current.distance_to(&last);
+			cache.push(sum);
+			last = current;
+		}
+
+		cache
+	}
+
+	// Given u (0 .. 1), get a t to find p. This gives you points which are equidistant.
+	fn get_u_to_t_mapping(&self, u: f32, distance: Option<f32>) -> f32 {
+		let arc_lengths = self.get_arc_lengths();
+		let il = arc_lengths.len();
+
+		let target_arc_length = match distance {
+			Some(d) => d,
+			None => u * arc_lengths[il - 1],
+		};
+
+		// binary search for the index with largest value smaller than target u distance
+		let i = match arc_lengths.binary_search_by(|l| l.partial_cmp(&target_arc_length).unwrap_or(Ordering::Less)) {
+			Ok(i) => return i as f32 / (il - 1) as f32,
+			Err(i) => i.saturating_sub(1).min(il - 2),
+		};
+
+		// we could get finer grain at lengths, or use simple interpolation between two points
+		let length_before = arc_lengths[i];
+		let length_after = arc_lengths[i + 1];
+		let segment_length = length_after - length_before;
+
+		let segment_fraction = if segment_length > 0.0 {
+			(target_arc_length - length_before) / segment_length
+		} else {
+			0.0
+		};
+
+		(i as f32 + segment_fraction) / (il - 1) as f32
+	}
+
+	fn get_tangent(&self, t: f32) -> Self::Vector {
+		let delta = 0.0001;
+		let t1 = (t - delta).max(0.0);
+		let t2 = (t + delta).min(1.0);
+
+		let pt1 = self.get_point(t1);
+		let pt2 = self.get_point(t2);
+
+		let mut tangent = Self::Vector::new();
+		tangent.sub_vectors(&pt2, &pt1);
+		tangent.normalize();
+		tangent
+	}
+
+	fn get_tangent_at(&self, u: f32) -> Self::Vector {
+		let t = self.get_u_to_t_mapping(u, None);
+		self.get_tangent(t)
+	}
+
+	// see http://www.cs.indiana.edu/pub/techreports/TR425.pdf
+	fn compute_frenet_frames(&self, segments: usize, closed: bool) -> FrenetFrames {
+		let mut tangents = Vec::with_capacity(segments + 1);
+		let mut normals = vec![Vector3::new(); segments + 1];
+		let mut binormals = vec![Vector3::new(); segments + 1];
+
+		let mut vec = Vector3::new();
+		let mut mat = Matrix4::new();
+
+		// compute the tangent vectors for each segment on the curve
+
+		for i in 0..segments + 1 {
+			let u = i as f32 / segments as f32;
+			tangents.push(self.get_tangent_at(u).to_vector3());
+		}
+
+		// select an initial normal vector perpendicular to the first tangent vector,
+		// and in the direction of the minimum tangent xyz component
+
+		let mut normal = Vector3::new();
+		let mut min = f32::MAX;
+		let tx = tangents[0].x.abs();
+		let ty = tangents[0].y.abs();
+		let tz = tangents[0].z.abs();
+
+		if tx <= min {
+			min = tx;
+			normal.set(1.0, 0.0, 0.0);
+		}
+
+		if ty <= min {
+			min = ty;
+			normal.set(0.0, 1.0, 0.0);
+		}
+
+		if tz <= min {
+			normal.set(0.0, 0.0, 1.0);
+		}
+
+		vec.cross_vectors(&tangents[0], &normal);
+		vec.normalize();
+
+		normals[0].cross_vectors(&tangents[0], &vec);
+		binormals[0].cross_vectors(&tangents[0], &normals[0]);
+
+		// compute the slowly-varying normal and binormal vectors for each segment on the curve
+
+		for i in 1..segments + 1 {
+			normals[i] = normals[i - 1];
+			binormals[i] = binormals[i - 1];
+
+			vec.cross_vectors(&tangents[i - 1], &tangents[i]);
+
+			if vec.length() > f32::EPSILON {
+				vec.normalize();
+
+				let theta = clamp(tangents[i - 1].dot(&tangents[i]), - 1.0, 1.0).acos();
+
+				mat.make_rotation_axis(&vec, theta);
+				normals[i].apply_matrix4(&mat);
+			}
+
+			binormals[i].cross_vectors(&tangents[i], &normals[i]);
+		}
+
+		// if the curve is closed, postprocess the vectors so the first and last normal vectors are the same
+
+		if closed {
+			let mut theta = clamp(normals[0].dot(&normals[segments]), - 1.0, 1.0).acos();
+			theta /= segments as f32;
+
+			vec.cross_vectors(&normals[0], &normals[segments]);
+
+			if tangents[0].dot(&vec) > 0.0 {
+				theta = - theta;
+			}
+
+			for i in 1..segments + 1 {
+				// twist a little...
+				mat.make_rotation_axis(&tangents[i], theta * i as f32);
+				normals[i].apply_matrix4(&mat);
+				binormals[i].cross_vectors(&tangents[i], &normals[i]);
+			}
+		}
+
+		FrenetFrames {
+			tangents,
+			normals,
+			binormals,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use super::super::curve_path::CurvePath;
+	use super::super::line_curve::LineCurve;
+	use super::super::quadratic_bezier_curve::QuadraticBezierCurve;
+
+	fn point(x: f32, y: f32) -> Vector2 {
+		Vector2 { x, y }
+	}
+
+	#[test]
+	fn zero_divisions_count_as_one() {
+		let curve = QuadraticBezierCurve::new(point(0.0, 0.0), point(1.0, 1.0), point(2.0, 0.0));
+
+		for points in [ curve.get_points(0), curve.get_spaced_points(0) ] {
+			assert_eq!(points.len(), 2);
+			assert!(points[ 0 ].equals(&point(0.0, 0.0)));
+			assert!(points[ 1 ].equals(&point(2.0, 0.0)));
+		}
+
+		let lengths = curve.get_lengths(0);
+		assert_eq!(lengths.len(), 2);
+		assert!(( lengths[ 1 ] - 2.0 ).abs() < 1e-6);
+		assert_eq!(curve.get_points(1).len(), 2);
+	}
+
+	#[test]
+	fn curve_path_with_zero_divisions() {
+		let mut path = CurvePath::new();
+		path.add(Box::new(LineCurve::new(point(0.0, 0.0), point(1.0, 0.0))));
+		path.add(Box::new(QuadraticBezierCurve::new(point(1.0, 0.0), point(2.0, 1.0), point(3.0, 0.0))));
+
+		let points = path.get_points(0);
+		assert_eq!(points.len(), 3);
+		assert!(points[ 1 ].equals(&point(1.0, 0.0)));
+		assert!(points[ 2 ].equals(&point(3.0, 0.0)));
+	}
+}
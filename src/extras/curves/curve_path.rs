@@ -0,0 +1,122 @@
+use std::rc::Rc;
+use super::curve::{Curve, CurveVector, LengthCache};
+
+// After changing curves without add, call update_arc_lengths.
+pub struct CurvePath<V: CurveVector> {
+	pub curves: Vec<Box<dyn Curve<Vector = V>>>,
+	pub auto_close: bool,
+	cache_lengths: LengthCache,
+	arc_lengths: LengthCache,
+}
+
+impl<V: CurveVector> CurvePath<V> {
+	pub fn new() -> CurvePath<V> {
+		CurvePath {
+			curves: vec![],
+			auto_close: false,
+			cache_lengths: LengthCache::new(),
+			arc_lengths: LengthCache::new(),
+		}
+	}
+
+	pub fn add(&mut self, curve: Box<dyn Curve<Vector = V>>) {
+		self.curves.push(curve);
+		self.update_arc_lengths();
+	}
+
+	// Compute lengths and cache them
+	// We cannot overwrite get_lengths() because UtoT mapping uses it.
+	pub fn get_curve_lengths(&self) -> Rc<Vec<f32>> {
+		self.cache_lengths.get(|| {
+			let mut sums = 0.0;
+			self.curves.iter().map(|curve| {
+				sums += curve.get_length();
+				sums
+			}).collect()
+		})
+	}
+}
+
+impl<V: CurveVector> Default for CurvePath<V> {
+	fn default() -> CurvePath<V> {
+		CurvePath::new()
+	}
+}
+
+impl<V: CurveVector> Curve for CurvePath<V> {
+	type Vector = V;
+
+	// To get accurate point with reference to
+	// entire path distance at time t,
+	// following has to be done:
+
+	// 1. Length of each sub path have to be known
+	// 2. Locate and identify type of curve
+	// 3. Get t for the curve
+	// 4. Return curve.get_point_at(t')
+	fn get_point(&self, t: f32) -> V {
+		let d = t * self.get_length();
+		let curve_lengths = self.get_curve_lengths();
+
+		// To think about boundaries points.
+		for (i, curve) in self.curves.iter().enumerate() {
+			if curve_lengths[i] >= d {
+				let diff = curve_lengths[i] - d;
+				let segment_length = if i == 0 { curve_lengths[0] } else { curve_lengths[i] - curve_lengths[i - 1] };
+				let u = if segment_length == 0.0 { 0.0 } else { 1.0 - diff / segment_length };
+
+				return curve.get_point_at(u);
+			}
+		}
+
+		// loop where sum != 0, sum > d , sum+1 <d
+		match self.curves.last() {
+			Some(curve) => curve.get_point_at(1.0),
+			None => V::new(),
+		}
+	}
+
+	// We cannot use the default Curve get_length() because in Curve, get_length() depends on get_point() but in CurvePath
+	// get_point() depends on get_length
+	fn get_arc_length_cache(&self) -> Option<&LengthCache> {
+		Some(&self.arc_lengths)
+	}
+
+	fn update_arc_lengths(&self) {
+		self.cache_lengths.clear();
+		self.arc_lengths.clear();
+	}
+
+	fn get_length(&self) -> f32 {
+		match self.get_curve_lengths().last() {
+			Some(length) => *length,
+			None => 0.0,
+		}
+	}
+
+	fn get_points(&self, divisions: usize) -> Vec<V> {
+		let mut points: Vec<V> = vec![];
+
+		for curve in &self.curves {
+			let resolution = curve.get_resolution(divisions);
+			let pts = curve.get_points(resolution);
+
+			for point in pts {
+				if let Some(last) = points.last() {
+					if last.equals(&point) {
+						continue; // ensures no consecutive points are duplicates
+					}
+				}
+
+				points.push(point);
+			}
+		}
+
+		if self.auto_close && points.len() > 1 && !points[points.len() - 1].equals(&points[0]) {
+			let first = points[0];
+			points.push(first);
+		}
+
+		points
+	}
+}
@@ -0,0 +1,85 @@
+use super::super::super::math::vector2::Vector2;
+use super::curve::Curve;
+
+#[derive(Debug, Clone, Copy)]
+pub struct EllipseCurve {
+	pub a_x: f32,
+	pub a_y: f32,
+	pub x_radius: f32,
+	pub y_radius: f32,
+	pub a_start_angle: f32,
+	pub a_end_angle: f32,
+	pub a_clockwise: bool,
+	pub a_rotation: f32,
+}
+
+impl EllipseCurve {
+	#[allow(clippy::too_many_arguments)]
+	pub fn new(a_x: f32, a_y: f32, x_radius: f32, y_radius: f32, a_start_angle: f32, a_end_angle: f32, a_clockwise: bool, a_rotation: f32) -> EllipseCurve {
+		EllipseCurve {
+			a_x,
+			a_y,
+			x_radius,
+			y_radius,
+			a_start_angle,
+			a_end_angle,
+			a_clockwise,
+			a_rotation,
+		}
+	}
+}
+
+impl Curve for EllipseCurve {
+	type Vector = Vector2;
+
+	fn get_resolution(&self, divisions: usize) -> usize {
+		divisions * 2
+	}
+
+	fn get_point(&self, t: f32) -> Vector2 {
+		let two_pi = ::std::f32::consts::PI * 2.0;
+		let mut delta_angle = self.a_end_angle - self.a_start_angle;
+		let same_points = delta_angle.abs() < f32::EPSILON;
+
+		// ensures that delta_angle is 0 .. 2 PI
+		while delta_angle < 0.0 {
+			delta_angle += two_pi;
+		}
+		while delta_angle > two_pi {
+			delta_angle -= two_pi;
+		}
+
+		if delta_angle < f32::EPSILON {
+			delta_angle = if same_points { 0.0 } else { two_pi };
+		}
+
+		if self.a_clockwise && !same_points {
+			if delta_angle == two_pi {
+				delta_angle = - two_pi;
+			} else {
+				delta_angle -= two_pi;
+			}
+		}
+
+		let angle = self.a_start_angle + t * delta_angle;
+		let mut x = self.a_x + self.x_radius * angle.cos();
+		let mut y = self.a_y + self.y_radius * angle.sin();
+
+		if self.a_rotation != 0.0 {
+			let cos = self.a_rotation.cos();
+			let sin = self.a_rotation.sin();
+
+			let tx = x - self.a_x;
+			let ty = y - self.a_y;
+
+			// Rotate the point about the center of the ellipse.
+			x = tx * cos - ty * sin + self.a_x;
+			y = tx * sin + ty * cos + self.a_y;
+		}
+
+		Vector2 {
+			x,
+			y,
+		}
+	}
+}
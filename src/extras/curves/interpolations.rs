@@ -0,0 +1,17 @@
+pub fn catmull_rom(t: f32, p0: f32, p1: f32, p2: f32, p3: f32) -> f32 {
+	let v0 = ( p2 - p0 ) * 0.5;
+	let v1 = ( p3 - p1 ) * 0.5;
+	let t2 = t * t;
+	let t3 = t * t2;
+	( 2.0 * p1 - 2.0 * p2 + v0 + v1 ) * t3 + ( - 3.0 * p1 + 3.0 * p2 - 2.0 * v0 - v1 ) * t2 + v0 * t + p1
+}
+
+pub fn quadratic_bezier(t: f32, p0: f32, p1: f32, p2: f32) -> f32 {
+	let k = 1.0 - t;
+	k * k * p0 + 2.0 * k * t * p1 + t * t * p2
+}
+
+pub fn cubic_bezier(t: f32, p0: f32, p1: f32, p2: f32, p3: f32) -> f32 {
+	let k = 1.0 - t;
+	k * k * k * p0 + 3.0 * k * k * t * p1 + 3.0 * k * t * t * p2 + t * t * t * p3
+}
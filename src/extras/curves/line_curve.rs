@@ -0,0 +1,55 @@
+use super::super::super::math::vector2::Vector2;
+use super::curve::Curve;
+
+#[derive(Debug, Clone, Copy)]
+pub struct LineCurve {
+	pub v1: Vector2,
+	pub v2: Vector2,
+}
+
+impl LineCurve {
+	pub fn new(v1: Vector2, v2: Vector2) -> LineCurve {
+		LineCurve {
+			v1,
+			v2,
+		}
+	}
+}
+
+impl Curve for LineCurve {
+	type Vector = Vector2;
+
+	fn get_point(&self, t: f32) -> Vector2 {
+		let mut point = Vector2::new();
+
+		if t == 1.0 {
+			point.copy(&self.v2);
+		} else {
+			point.sub_vectors(&self.v2, &self.v1);
+			point.multiply_scalar(t);
+			point.add(&self.v1);
+		}
+
+		point
+	}
+
+	fn get_resolution(&self, _divisions: usize) -> usize {
+		1
+	}
+
+	// Line curve is linear, so we can overwrite default get_point_at
+	fn get_point_at(&self, u: f32) -> Vector2 {
+		self.get_point(u)
+	}
+
+	fn get_tangent(&self, _t: f32) -> Vector2 {
+		let mut tangent = Vector2::new();
+		tangent.sub_vectors(&self.v2, &self.v1);
+		tangent.normalize();
+		tangent
+	}
+
+	fn get_tangent_at(&self, u: f32) -> Vector2 {
+		self.get_tangent(u)
+	}
+}
@@ -0,0 +1,55 @@
+use super::super::super::math::vector3::Vector3;
+use super::curve::Curve;
+
+#[derive(Debug, Clone, Copy)]
+pub struct LineCurve3 {
+	pub v1: Vector3,
+	pub v2: Vector3,
+}
+
+impl LineCurve3 {
+	pub fn new(v1: Vector3, v2: Vector3) -> LineCurve3 {
+		LineCurve3 {
+			v1,
+			v2,
+		}
+	}
+}
+
+impl Curve for LineCurve3 {
+	type Vector = Vector3;
+
+	fn get_point(&self, t: f32) -> Vector3 {
+		let mut point = Vector3::new();
+
+		if t == 1.0 {
+			point.copy(&self.v2);
+		} else {
+			point.sub_vectors(&self.v2, &self.v1);
+			point.multiply_scalar(t);
+			point.add(&self.v1);
+		}
+
+		point
+	}
+
+	fn get_resolution(&self, _divisions: usize) -> usize {
+		1
+	}
+
+	// Line curve is linear, so we can overwrite default get_point_at
+	fn get_point_at(&self, u: f32) -> Vector3 {
+		self.get_point(u)
+	}
+
+	fn get_tangent(&self, _t: f32) -> Vector3 {
+		let mut tangent = Vector3::new();
+		tangent.sub_vectors(&self.v2, &self.v1);
+		tangent.normalize();
+		tangent
+	}
+
+	fn get_tangent_at(&self, u: f32) -> Vector3 {
+		self.get_tangent(u)
+	}
+}
@@ -0,0 +1,12 @@
+pub mod curve;
+pub mod curve_path;
+pub mod interpolations;
+pub mod line_curve;
+pub mod line_curve3;
+pub mod quadratic_bezier_curve;
+pub mod quadratic_bezier_curve3;
+pub mod cubic_bezier_curve;
+pub mod cubic_bezier_curve3;
+pub mod catmull_rom_curve3;
+pub mod ellipse_curve;
+pub mod arc_curve;
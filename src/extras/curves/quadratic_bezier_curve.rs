@@ -0,0 +1,35 @@
+use super::super::super::math::vector2::Vector2;
+use super::curve::Curve;
+use super::interpolations::quadratic_bezier;
+
+#[derive(Debug, Clone, Copy)]
+pub struct QuadraticBezierCurve {
+	pub v0: Vector2,
+	pub v1: Vector2,
+	pub v2: Vector2,
+}
+
+impl QuadraticBezierCurve {
+	pub fn new(v0: Vector2, v1: Vector2, v2: Vector2) -> QuadraticBezierCurve {
+		QuadraticBezierCurve {
+			v0,
+			v1,
+			v2,
+		}
+	}
+}
+
+impl Curve for QuadraticBezierCurve {
+	type Vector = Vector2;
+
+	fn get_point(&self, t: f32) -> Vector2 {
+		let v0 = &self.v0;
+		let v1 = &self.v1;
+		let v2 = &self.v2;
+
+		Vector2 {
+			x: quadratic_bezier(t, v0.x, v1.x, v2.x),
+			y: quadratic_bezier(t, v0.y, v1.y, v2.y),
+		}
+	}
+}
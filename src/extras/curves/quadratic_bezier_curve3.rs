@@ -0,0 +1,36 @@
+use super::super::super::math::vector3::Vector3;
+use super::curve::Curve;
+use super::interpolations::quadratic_bezier;
+
+#[derive(Debug, Clone, Copy)]
+pub struct QuadraticBezierCurve3 {
+	pub v0: Vector3,
+	pub v1: Vector3,
+	pub v2: Vector3,
+}
+
+impl QuadraticBezierCurve3 {
+	pub fn new(v0: Vector3, v1: Vector3, v2: Vector3) -> QuadraticBezierCurve3 {
+		QuadraticBezierCurve3 {
+			v0,
+			v1,
+			v2,
+		}
+	}
+}
+
+impl Curve for QuadraticBezierCurve3 {
+	type Vector = Vector3;
+
+	fn get_point(&self, t: f32) -> Vector3 {
+		let v0 = &self.v0;
+		let v1 = &self.v1;
+		let v2 = &self.v2;
+
+		Vector3 {
+			x: quadratic_bezier(t, v0.x, v1.x, v2.x),
+			y: quadratic_bezier(t, v0.y, v1.y, v2.y),
+			z: quadratic_bezier(t, v0.z, v1.z, v2.z),
+		}
+	}
+}
@@ -0,0 +1,916 @@
+/*
+ * Port from https://github.com/mapbox/earcut (v2.2.4)
+ */
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+	// vertex index in coordinates array
+	i: usize,
+
+	// vertex coordinates
+	x: f32,
+	y: f32,
+
+	// previous and next vertex nodes in a polygon ring
+	prev: usize,
+	next: usize,
+
+	// z-order curve value
+	z: u32,
+
+	// previous and next nodes in z-order
+	prev_z: Option<usize>,
+	next_z: Option<usize>,
+
+	// indicates whether this is a steiner point
+	steiner: bool,
+}
+
+struct Earcut {
+	nodes: Vec<Node>,
+	triangles: Vec<usize>,
+	dim: usize,
+	min_x: f32,
+	min_y: f32,
+	inv_size: f32,
+}
+
+pub fn triangulate(data: &[f32], hole_indices: &[usize], dim: usize) -> Vec<usize> {
+	let has_holes = !hole_indices.is_empty();
+	let outer_len = if has_holes { hole_indices[ 0 ] * dim } else { data.len() };
+
+	let mut earcut = Earcut {
+		nodes: Vec::with_capacity(data.len() / dim * 3 / 2),
+		triangles: vec![],
+		dim,
+		min_x: 0.0,
+		min_y: 0.0,
+		inv_size: 0.0,
+	};
+
+	let mut outer_node = match earcut.linked_list(data, 0, outer_len, true) {
+		Some(node) => node,
+		None => return earcut.triangles,
+	};
+
+	if earcut.nodes[ outer_node ].next == earcut.nodes[ outer_node ].prev {
+		return earcut.triangles;
+	}
+
+	if has_holes {
+		outer_node = earcut.eliminate_holes(data, hole_indices, outer_node);
+	}
+
+	// if the shape is not too simple, we'll use z-order curve hash later; calculate polygon bbox
+	if data.len() > 80 * dim {
+		let mut min_x = data[ 0 ];
+		let mut max_x = data[ 0 ];
+		let mut min_y = data[ 1 ];
+		let mut max_y = data[ 1 ];
+
+		for i in (dim..outer_len).step_by(dim) {
+			let x = data[ i ];
+			let y = data[ i + 1 ];
+			min_x = min_x.min(x);
+			min_y = min_y.min(y);
+			max_x = max_x.max(x);
+			max_y = max_y.max(y);
+		}
+
+		// min_x, min_y and inv_size are later used to transform coords into integers for z-order calculation
+		let inv_size = (max_x - min_x).max(max_y - min_y);
+		earcut.min_x = min_x;
+		earcut.min_y = min_y;
+		earcut.inv_size = if inv_size != 0.0 { 32767.0 / inv_size } else { 0.0 };
+	}
+
+	earcut.earcut_linked(Some(outer_node), 0);
+
+	earcut.triangles
+}
+
+impl Earcut {
+	// create a circular doubly linked list from polygon points in the specified winding order
+	fn linked_list(&mut self, data: &[f32], start: usize, end: usize, clockwise: bool) -> Option<usize> {
+		let mut last = None;
+
+		if start >= end {
+			return last;
+		}
+
+		if clockwise == (signed_area(data, start, end, self.dim) > 0.0) {
+			for i in (start..end).step_by(self.dim) {
+				last = Some(self.insert_node(i, data[ i ], data[ i + 1 ], last));
+			}
+		} else {
+			for i in (start..end).step_by(self.dim).rev() {
+				last = Some(self.insert_node(i, data[ i ], data[ i + 1 ], last));
+			}
+		}
+
+		if let Some(l) = last {
+			let next = self.nodes[ l ].next;
+			if self.equals(l, next) {
+				self.remove_node(l);
+				last = Some(next);
+			}
+		}
+
+		last
+	}
+
+	// eliminate colinear or duplicate points
+	fn filter_points(&mut self, start: usize, end: Option<usize>) -> usize {
+		let mut end = end.unwrap_or(start);
+		let mut p = start;
+
+		loop {
+			let mut again = false;
+			let prev = self.nodes[ p ].prev;
+			let next = self.nodes[ p ].next;
+
+			if !self.nodes[ p ].steiner && (self.equals(p, next) || self.area(prev, p, next) == 0.0) {
+				self.remove_node(p);
+				p = prev;
+				end = prev;
+				if p == self.nodes[ p ].next {
+					break;
+				}
+				again = true;
+			} else {
+				p = next;
+			}
+
+			if !again && p == end {
+				break;
+			}
+		}
+
+		end
+	}
+
+	// main ear slicing loop which triangulates a polygon (given as a linked list)
+	fn earcut_linked(&mut self, ear: Option<usize>, pass: u8) {
+		let mut ear = match ear {
+			Some(ear) => ear,
+			None => return,
+		};
+
+		// interlink polygon nodes in z-order
+		if pass == 0 && self.inv_size != 0.0 {
+			self.index_curve(ear);
+		}
+
+		let mut stop = ear;
+
+		// iterate through ears, slicing them one by one
+		while self.nodes[ ear ].prev != self.nodes[ ear ].next {
+			let prev = self.nodes[ ear ].prev;
+			let next = self.nodes[ ear ].next;
+
+			let is_ear = if self.inv_size != 0.0 { self.is_ear_hashed(ear) } else { self.is_ear(ear) };
+
+			if is_ear {
+				// cut off the triangle
+				self.triangles.push(self.nodes[ prev ].i / self.dim);
+				self.triangles.push(self.nodes[ ear ].i / self.dim);
+				self.triangles.push(self.nodes[ next ].i / self.dim);
+
+				self.remove_node(ear);
+
+				// skipping the next vertex leads to less sliver triangles
+				ear = self.nodes[ next ].next;
+				stop = ear;
+
+				continue;
+			}
+
+			ear = next;
+
+			// if we looped through the whole remaining polygon and can't find any more ears
+			if ear == stop {
+				if pass == 0 {
+					// try filtering points and slicing again
+					let filtered = self.filter_points(ear, None);
+					self.earcut_linked(Some(filtered), 1);
+				} else if pass == 1 {
+					// if this didn't work, try curing all small self-intersections locally
+					let filtered = self.filter_points(ear, None);
+					let cured = self.cure_local_intersections(filtered);
+					self.earcut_linked(Some(cured), 2);
+				} else if pass == 2 {
+					// as a last resort, try splitting the remaining polygon into two
+					self.split_earcut(ear);
+				}
+
+				break;
+			}
+		}
+	}
+
+	// check whether a polygon node forms a valid ear with adjacent nodes
+	fn is_ear(&self, ear: usize) -> bool {
+		let a = self.nodes[ ear ].prev;
+		let b = ear;
+		let c = self.nodes[ ear ].next;
+
+		if self.area(a, b, c) >= 0.0 {
+			return false; // reflex, can't be an ear
+		}
+
+		// now make sure we don't have other points inside the potential ear
+		let (x0, y0, x1, y1) = self.triangle_bbox(a, b, c);
+
+		let mut p = self.nodes[ c ].next;
+		while p != a {
+			if self.point_blocks_ear(p, a, b, c, x0, y0, x1, y1) {
+				return false;
+			}
+			p = self.nodes[ p ].next;
+		}
+
+		true
+	}
+
+	fn is_ear_hashed(&self, ear: usize) -> bool {
+		let a = self.nodes[ ear ].prev;
+		let b = ear;
+		let c = self.nodes[ ear ].next;
+
+		if self.area(a, b, c) >= 0.0 {
+			return false; // reflex, can't be an ear
+		}
+
+		let (x0, y0, x1, y1) = self.triangle_bbox(a, b, c);
+
+		// z-order range for the current triangle bbox;
+		let min_z = self.z_order(x0, y0);
+		let max_z = self.z_order(x1, y1);
+
+		let mut p = self.nodes[ ear ].prev_z;
+		let mut n = self.nodes[ ear ].next_z;
+
+		// look for points inside the triangle in both directions
+		while let (Some(pi), Some(ni)) = (p, n) {
+			if self.nodes[ pi ].z < min_z || self.nodes[ ni ].z > max_z {
+				break;
+			}
+
+			if pi != a && pi != c && self.point_blocks_ear(pi, a, b, c, x0, y0, x1, y1) {
+				return false;
+			}
+			p = self.nodes[ pi ].prev_z;
+
+			if ni != a && ni != c && self.point_blocks_ear(ni, a, b, c, x0, y0, x1, y1) {
+				return false;
+			}
+			n = self.nodes[ ni ].next_z;
+		}
+
+		// look for remaining points in decreasing z-order
+		while let Some(pi) = p {
+			if self.nodes[ pi ].z < min_z {
+				break;
+			}
+			if pi != a && pi != c && self.point_blocks_ear(pi, a, b, c, x0, y0, x1, y1) {
+				return false;
+			}
+			p = self.nodes[ pi ].prev_z;
+		}
+
+		// look for remaining points in increasing z-order
+		while let Some(ni) = n {
+			if self.nodes[ ni ].z > max_z {
+				break;
+			}
+			if ni != a && ni != c && self.point_blocks_ear(ni, a, b, c, x0, y0, x1, y1) {
+				return false;
+			}
+			n = self.nodes[ ni ].next_z;
+		}
+
+		true
+	}
+
+	// triangle bbox; min & max are calculated like this for speed
+	fn triangle_bbox(&self, a: usize, b: usize, c: usize) -> (f32, f32, f32, f32) {
+		let (ax, ay) = (self.nodes[ a ].x, self.nodes[ a ].y);
+		let (bx, by) = (self.nodes[ b ].x, self.nodes[ b ].y);
+		let (cx, cy) = (self.nodes[ c ].x, self.nodes[ c ].y);
+
+		(
+			ax.min(bx).min(cx),
+			ay.min(by).min(cy),
+			ax.max(bx).max(cx),
+			ay.max(by).max(cy),
+		)
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	fn point_blocks_ear(&self, p: usize, a: usize, b: usize, c: usize, x0: f32, y0: f32, x1: f32, y1: f32) -> bool {
+		let node = &self.nodes[ p ];
+		let (na, nb, nc) = (&self.nodes[ a ], &self.nodes[ b ], &self.nodes[ c ]);
+
+		node.x >= x0 && node.x <= x1 && node.y >= y0 && node.y <= y1 &&
+			point_in_triangle(na.x, na.y, nb.x, nb.y, nc.x, nc.y, node.x, node.y) &&
+			self.area(node.prev, p, node.next) >= 0.0
+	}
+
+	// go through all polygon nodes and cure small local self-intersections
+	fn cure_local_intersections(&mut self, start: usize) -> usize {
+		let mut start = start;
+		let mut p = start;
+
+		loop {
+			let a = self.nodes[ p ].prev;
+			let p_next = self.nodes[ p ].next;
+			let b = self.nodes[ p_next ].next;
+
+			if !self.equals(a, b) && self.intersects(a, p, p_next, b) && self.locally_inside(a, b) && self.locally_inside(b, a) {
+				self.triangles.push(self.nodes[ a ].i / self.dim);
+				self.triangles.push(self.nodes[ p ].i / self.dim);
+				self.triangles.push(self.nodes[ b ].i / self.dim);
+
+				// remove two nodes involved
+				self.remove_node(p);
+				self.remove_node(p_next);
+
+				p = b;
+				start = b;
+			}
+
+			p = self.nodes[ p ].next;
+
+			if p == start {
+				break;
+			}
+		}
+
+		self.filter_points(p, None)
+	}
+
+	// try splitting polygon into two and triangulate them independently
+	fn split_earcut(&mut self, start: usize) {
+		// look for a valid diagonal that divides the polygon into two
+		let mut a = start;
+
+		loop {
+			let mut b = self.nodes[ self.nodes[ a ].next ].next;
+
+			while b != self.nodes[ a ].prev {
+				if self.nodes[ a ].i != self.nodes[ b ].i && self.is_valid_diagonal(a, b) {
+					// split the polygon in two by the diagonal
+					let c = self.split_polygon(a, b);
+
+					// filter colinear points around the cuts
+					let a_next = self.nodes[ a ].next;
+					let a = self.filter_points(a, Some(a_next));
+					let c_next = self.nodes[ c ].next;
+					let c = self.filter_points(c, Some(c_next));
+
+					// run earcut on each half
+					self.earcut_linked(Some(a), 0);
+					self.earcut_linked(Some(c), 0);
+					return;
+				}
+
+				b = self.nodes[ b ].next;
+			}
+
+			a = self.nodes[ a ].next;
+
+			if a == start {
+				break;
+			}
+		}
+	}
+
+	// link every hole into the outer loop, producing a single-ring polygon without holes
+	fn eliminate_holes(&mut self, data: &[f32], hole_indices: &[usize], outer_node: usize) -> usize {
+		let mut queue = Vec::with_capacity(hole_indices.len());
+		let len = hole_indices.len();
+
+		for i in 0..len {
+			let start = hole_indices[ i ] * self.dim;
+			let end = if i < len - 1 { hole_indices[ i + 1 ] * self.dim } else { data.len() };
+
+			if let Some(list) = self.linked_list(data, start, end, false) {
+				if list == self.nodes[ list ].next {
+					self.nodes[ list ].steiner = true;
+				}
+				queue.push(self.get_leftmost(list));
+			}
+		}
+
+		queue.sort_by(|a, b| self.nodes[ *a ].x.partial_cmp(&self.nodes[ *b ].x).unwrap_or(::std::cmp::Ordering::Equal));
+
+		// process holes from left to right
+		let mut outer_node = outer_node;
+		for hole in queue {
+			outer_node = self.eliminate_hole(hole, outer_node);
+		}
+
+		outer_node
+	}
+
+	// find a bridge between vertices that connects hole with an outer ring and and link it
+	fn eliminate_hole(&mut self, hole: usize, outer_node: usize) -> usize {
+		let bridge = match self.find_hole_bridge(hole, outer_node) {
+			Some(bridge) => bridge,
+			None => return outer_node,
+		};
+
+		let bridge_reverse = self.split_polygon(bridge, hole);
+
+		// filter collinear points around the cuts
+		let bridge_reverse_next = self.nodes[ bridge_reverse ].next;
+		self.filter_points(bridge_reverse, Some(bridge_reverse_next));
+
+		let bridge_next = self.nodes[ bridge ].next;
+		self.filter_points(bridge, Some(bridge_next))
+	}
+
+	// David Eberly's algorithm for finding a bridge between hole and outer polygon
+	fn find_hole_bridge(&self, hole: usize, outer_node: usize) -> Option<usize> {
+		let mut p = outer_node;
+		let mut qx = f32::NEG_INFINITY;
+		let mut m = None;
+		let hx = self.nodes[ hole ].x;
+		let hy = self.nodes[ hole ].y;
+
+		// find a segment intersected by a ray from the hole's leftmost point to the left;
+		// segment's endpoint with lesser x will be potential connection point
+		loop {
+			let node = &self.nodes[ p ];
+			let next = &self.nodes[ node.next ];
+
+			if hy <= node.y && hy >= next.y && next.y != node.y {
+				let x = node.x + (hy - node.y) * (next.x - node.x) / (next.y - node.y);
+				if x <= hx && x > qx {
+					qx = x;
+					let candidate = if node.x < next.x { p } else { node.next };
+					if x == hx {
+						return Some(candidate); // hole touches outer segment; pick leftmost endpoint
+					}
+					m = Some(candidate);
+				}
+			}
+
+			p = node.next;
+
+			if p == outer_node {
+				break;
+			}
+		}
+
+		let mut m = m?;
+
+		// look for points inside the triangle of hole point, segment intersection and endpoint;
+		// if there are no points found, we have a valid connection;
+		// otherwise choose the point of the minimum angle with the ray as connection point
+
+		let stop = m;
+		let mx = self.nodes[ m ].x;
+		let my = self.nodes[ m ].y;
+		let mut tan_min = f32::INFINITY;
+
+		p = m;
+
+		loop {
+			let px = self.nodes[ p ].x;
+			let py = self.nodes[ p ].y;
+
+			if hx >= px && px >= mx && hx != px &&
+				point_in_triangle(if hy < my { hx } else { qx }, hy, mx, my, if hy < my { qx } else { hx }, hy, px, py) {
+				let tan = (hy - py).abs() / (hx - px); // tangential
+
+				if self.locally_inside(p, hole) &&
+					(tan < tan_min || (tan == tan_min && (px > self.nodes[ m ].x || (px == self.nodes[ m ].x && self.sector_contains_sector(m, p))))) {
+					m = p;
+					tan_min = tan;
+				}
+			}
+
+			p = self.nodes[ p ].next;
+
+			if p == stop {
+				break;
+			}
+		}
+
+		Some(m)
+	}
+
+	// whether sector in vertex m contains sector in vertex p in the same coordinates
+	fn sector_contains_sector(&self, m: usize, p: usize) -> bool {
+		self.area(self.nodes[ m ].prev, m, self.nodes[ p ].prev) < 0.0 &&
+			self.area(self.nodes[ p ].next, m, self.nodes[ m ].next) < 0.0
+	}
+
+	// interlink polygon nodes in z-order
+	fn index_curve(&mut self, start: usize) {
+		let mut p = start;
+
+		loop {
+			if self.nodes[ p ].z == 0 {
+				self.nodes[ p ].z = self.z_order(self.nodes[ p ].x, self.nodes[ p ].y);
+			}
+			self.nodes[ p ].prev_z = Some(self.nodes[ p ].prev);
+			self.nodes[ p ].next_z = Some(self.nodes[ p ].next);
+			p = self.nodes[ p ].next;
+
+			if p == start {
+				break;
+			}
+		}
+
+		let prev_z = self.nodes[ p ].prev;
+		self.nodes[ prev_z ].next_z = None;
+		self.nodes[ p ].prev_z = None;
+
+		self.sort_linked(p);
+	}
+
+	// Simon Tatham's linked list merge sort algorithm
+	// http://www.chiark.greenend.org.uk/~sgtatham/algorithms/listsort.html
+	fn sort_linked(&mut self, list: usize) {
+		let mut list = Some(list);
+		let mut in_size = 1;
+
+		loop {
+			let mut p = list;
+			list = None;
+			let mut tail: Option<usize> = None;
+			let mut num_merges = 0;
+
+			while let Some(p_start) = p {
+				num_merges += 1;
+				let mut q = Some(p_start);
+				let mut p_size = 0;
+
+				for _ in 0..in_size {
+					p_size += 1;
+					q = self.nodes[ q.unwrap() ].next_z;
+					if q.is_none() {
+						break;
+					}
+				}
+
+				let mut q_size = in_size;
+
+				while p_size > 0 || (q_size > 0 && q.is_some()) {
+					let e;
+
+					let take_p = match (p, q) {
+						(Some(pi), Some(qi)) => p_size != 0 && (q_size == 0 || self.nodes[ pi ].z <= self.nodes[ qi ].z),
+						(Some(_), None) => p_size != 0,
+						_ => false,
+					};
+
+					if take_p {
+						e = p.unwrap();
+						p = self.nodes[ e ].next_z;
+						p_size -= 1;
+					} else {
+						e = q.unwrap();
+						q = self.nodes[ e ].next_z;
+						q_size -= 1;
+					}
+
+					match tail {
+						Some(t) => self.nodes[ t ].next_z = Some(e),
+						None => list = Some(e),
+					}
+
+					self.nodes[ e ].prev_z = tail;
+					tail = Some(e);
+				}
+
+				p = q;
+			}
+
+			if let Some(t) = tail {
+				self.nodes[ t ].next_z = None;
+			}
+
+			in_size *= 2;
+
+			if num_merges <= 1 {
+				break;
+			}
+		}
+	}
+
+	// z-order of a point given coords and inverse of the longer side of data bbox
+	fn z_order(&self, x: f32, y: f32) -> u32 {
+		// coords are transformed into non-negative 15-bit integer range
+		let mut x = ((x - self.min_x) * self.inv_size) as u32;
+		let mut y = ((y - self.min_y) * self.inv_size) as u32;
+
+		x = (x | (x << 8)) & 0x00FF00FF;
+		x = (x | (x << 4)) & 0x0F0F0F0F;
+		x = (x | (x << 2)) & 0x33333333;
+		x = (x | (x << 1)) & 0x55555555;
+
+		y = (y | (y << 8)) & 0x00FF00FF;
+		y = (y | (y << 4)) & 0x0F0F0F0F;
+		y = (y | (y << 2)) & 0x33333333;
+		y = (y | (y << 1)) & 0x55555555;
+
+		x | (y << 1)
+	}
+
+	// find the leftmost node of a polygon ring
+	fn get_leftmost(&self, start: usize) -> usize {
+		let mut p = start;
+		let mut leftmost = start;
+
+		loop {
+			let node = &self.nodes[ p ];
+			let left = &self.nodes[ leftmost ];
+			if node.x < left.x || (node.x == left.x && node.y < left.y) {
+				leftmost = p;
+			}
+			p = node.next;
+
+			if p == start {
+				break;
+			}
+		}
+
+		leftmost
+	}
+
+	// check if a diagonal between two polygon nodes is valid (lies in polygon interior)
+	fn is_valid_diagonal(&self, a: usize, b: usize) -> bool {
+		let na = &self.nodes[ a ];
+		let nb = &self.nodes[ b ];
+
+		self.nodes[ na.next ].i != nb.i && self.nodes[ na.prev ].i != nb.i && !self.intersects_polygon(a, b) && // dones't intersect other edges
+			(self.locally_inside(a, b) && self.locally_inside(b, a) && self.middle_inside(a, b) && // locally visible
+				(self.area(na.prev, a, nb.prev) != 0.0 || self.area(a, nb.prev, b) != 0.0) || // does not create opposite-facing sectors
+				self.equals(a, b) && self.area(na.prev, a, na.next) > 0.0 && self.area(nb.prev, b, nb.next) > 0.0) // special zero-length case
+	}
+
+	// signed area of a triangle
+	fn area(&self, p: usize, q: usize, r: usize) -> f32 {
+		let (p, q, r) = (&self.nodes[ p ], &self.nodes[ q ], &self.nodes[ r ]);
+		(q.y - p.y) * (r.x - q.x) - (q.x - p.x) * (r.y - q.y)
+	}
+
+	// check if two points are equal
+	fn equals(&self, p1: usize, p2: usize) -> bool {
+		self.nodes[ p1 ].x == self.nodes[ p2 ].x && self.nodes[ p1 ].y == self.nodes[ p2 ].y
+	}
+
+	// check if two segments intersect
+	fn intersects(&self, p1: usize, q1: usize, p2: usize, q2: usize) -> bool {
+		let o1 = sign(self.area(p1, q1, p2));
+		let o2 = sign(self.area(p1, q1, q2));
+		let o3 = sign(self.area(p2, q2, p1));
+		let o4 = sign(self.area(p2, q2, q1));
+
+		if o1 != o2 && o3 != o4 {
+			return true; // general case
+		}
+
+		(o1 == 0 && self.on_segment(p1, p2, q1)) || // p1, q1 and p2 are collinear and p2 lies on p1q1
+			(o2 == 0 && self.on_segment(p1, q2, q1)) || // p1, q1 and q2 are collinear and q2 lies on p1q1
+			(o3 == 0 && self.on_segment(p2, p1, q2)) || // p2, q2 and p1 are collinear and p1 lies on p2q2
+			(o4 == 0 && self.on_segment(p2, q1, q2)) // p2, q2 and q1 are collinear and q1 lies on p2q2
+	}
+
+	// for collinear points p, q, r, check if point q lies on segment pr
+	fn on_segment(&self, p: usize, q: usize, r: usize) -> bool {
+		let (p, q, r) = (&self.nodes[ p ], &self.nodes[ q ], &self.nodes[ r ]);
+		q.x <= p.x.max(r.x) && q.x >= p.x.min(r.x) && q.y <= p.y.max(r.y) && q.y >= p.y.min(r.y)
+	}
+
+	// check if a polygon diagonal intersects any polygon segments
+	fn intersects_polygon(&self, a: usize, b: usize) -> bool {
+		let ai = self.nodes[ a ].i;
+		let bi = self.nodes[ b ].i;
+		let mut p = a;
+
+		loop {
+			let next = self.nodes[ p ].next;
+			let pi = self.nodes[ p ].i;
+			let ni = self.nodes[ next ].i;
+
+			if pi != ai && ni != ai && pi != bi && ni != bi && self.intersects(p, next, a, b) {
+				return true;
+			}
+
+			p = next;
+
+			if p == a {
+				break;
+			}
+		}
+
+		false
+	}
+
+	// check if a polygon diagonal is locally inside the polygon
+	fn locally_inside(&self, a: usize, b: usize) -> bool {
+		let prev = self.nodes[ a ].prev;
+		let next = self.nodes[ a ].next;
+
+		if self.area(prev, a, next) < 0.0 {
+			self.area(a, b, next) >= 0.0 && self.area(a, prev, b) >= 0.0
+		} else {
+			self.area(a, b, prev) < 0.0 || self.area(a, next, b) < 0.0
+		}
+	}
+
+	// check if the middle point of a polygon diagonal is inside the polygon
+	fn middle_inside(&self, a: usize, b: usize) -> bool {
+		let mut p = a;
+		let mut inside = false;
+		let px = (self.nodes[ a ].x + self.nodes[ b ].x) / 2.0;
+		let py = (self.nodes[ a ].y + self.nodes[ b ].y) / 2.0;
+
+		loop {
+			let node = &self.nodes[ p ];
+			let next = &self.nodes[ node.next ];
+
+			if ((node.y > py) != (next.y > py)) && next.y != node.y &&
+				(px < (next.x - node.x) * (py - node.y) / (next.y - node.y) + node.x) {
+				inside = !inside;
+			}
+
+			p = node.next;
+
+			if p == a {
+				break;
+			}
+		}
+
+		inside
+	}
+
+	// link two polygon vertices with a bridge; if the vertices belong to the same ring, it splits polygon into two;
+	// if one belongs to the outer ring and another to a hole, it merges it into a single ring
+	fn split_polygon(&mut self, a: usize, b: usize) -> usize {
+		let a2 = self.create_node(self.nodes[ a ].i, self.nodes[ a ].x, self.nodes[ a ].y);
+		let b2 = self.create_node(self.nodes[ b ].i, self.nodes[ b ].x, self.nodes[ b ].y);
+		let an = self.nodes[ a ].next;
+		let bp = self.nodes[ b ].prev;
+
+		self.nodes[ a ].next = b;
+		self.nodes[ b ].prev = a;
+
+		self.nodes[ a2 ].next = an;
+		self.nodes[ an ].prev = a2;
+
+		self.nodes[ b2 ].next = a2;
+		self.nodes[ a2 ].prev = b2;
+
+		self.nodes[ bp ].next = b2;
+		self.nodes[ b2 ].prev = bp;
+
+		b2
+	}
+
+	// create a node and optionally link it with previous one (in a circular doubly linked list)
+	fn insert_node(&mut self, i: usize, x: f32, y: f32, last: Option<usize>) -> usize {
+		let p = self.create_node(i, x, y);
+
+		match last {
+			None => {
+				self.nodes[ p ].prev = p;
+				self.nodes[ p ].next = p;
+			},
+			Some(last) => {
+				let last_next = self.nodes[ last ].next;
+				self.nodes[ p ].next = last_next;
+				self.nodes[ p ].prev = last;
+				self.nodes[ last_next ].prev = p;
+				self.nodes[ last ].next = p;
+			}
+		}
+
+		p
+	}
+
+	fn remove_node(&mut self, p: usize) {
+		let Node { prev, next, prev_z, next_z, .. } = self.nodes[ p ];
+
+		self.nodes[ next ].prev = prev;
+		self.nodes[ prev ].next = next;
+
+		if let Some(pz) = prev_z {
+			self.nodes[ pz ].next_z = next_z;
+		}
+
+		if let Some(nz) = next_z {
+			self.nodes[ nz ].prev_z = prev_z;
+		}
+	}
+
+	fn create_node(&mut self, i: usize, x: f32, y: f32) -> usize {
+		let index = self.nodes.len();
+
+		self.nodes.push(Node {
+			i,
+			x,
+			y,
+			prev: index,
+			next: index,
+			z: 0,
+			prev_z: None,
+			next_z: None,
+			steiner: false,
+		});
+
+		index
+	}
+}
+
+// check if a point lies within a convex triangle
+#[allow(clippy::too_many_arguments)]
+fn point_in_triangle(ax: f32, ay: f32, bx: f32, by: f32, cx: f32, cy: f32, px: f32, py: f32) -> bool {
+	(cx - px) * (ay - py) >= (ax - px) * (cy - py) &&
+		(ax - px) * (by - py) >= (bx - px) * (ay - py) &&
+		(bx - px) * (cy - py) >= (cx - px) * (by - py)
+}
+
+fn sign(num: f32) -> i8 {
+	if num > 0.0 {
+		1
+	} else if num < 0.0 {
+		- 1
+	} else {
+		0
+	}
+}
+
+fn signed_area(data: &[f32], start: usize, end: usize, dim: usize) -> f32 {
+	let mut sum = 0.0;
+	let mut j = end - dim;
+
+	for i in (start..end).step_by(dim) {
+		sum += (data[ j ] - data[ i ]) * (data[ i + 1 ] + data[ j + 1 ]);
+		j = i;
+	}
+
+	sum
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn triangle_area(data: &[f32], a: usize, b: usize, c: usize) -> f32 {
+		let ( ax, ay ) = ( data[ a * 2 ], data[ a * 2 + 1 ] );
+		let ( bx, by ) = ( data[ b * 2 ], data[ b * 2 + 1 ] );
+		let ( cx, cy ) = ( data[ c * 2 ], data[ c * 2 + 1 ] );
+		( ( bx - ax ) * ( cy - ay ) - ( cx - ax ) * ( by - ay ) ).abs() * 0.5
+	}
+
+	#[test]
+	fn square_with_a_hole() {
+		let data = [
+			// outer, counter-clockwise
+			0.0, 0.0, 10.0, 0.0, 10.0, 10.0, 0.0, 10.0,
+			// hole, clockwise
+			3.0, 3.0, 3.0, 7.0, 7.0, 7.0, 7.0, 3.0,
+		];
+
+		let triangles = triangulate(&data, &[ 4 ], 2);
+
+		// n + 2h - 2 triangles for n vertices and h holes
+		assert_eq!(triangles.len(), 8 * 3);
+
+		let area: f32 = triangles.chunks(3).map(|t| triangle_area(&data, t[ 0 ], t[ 1 ], t[ 2 ])).sum();
+		assert!(( area - 84.0 ).abs() < 1e-4);
+
+		// no triangle covers the hole
+		for t in triangles.chunks(3) {
+			let cx = ( data[ t[ 0 ] * 2 ] + data[ t[ 1 ] * 2 ] + data[ t[ 2 ] * 2 ] ) / 3.0;
+			let cy = ( data[ t[ 0 ] * 2 + 1 ] + data[ t[ 1 ] * 2 + 1 ] + data[ t[ 2 ] * 2 + 1 ] ) / 3.0;
+			assert!(!( cx > 3.0 && cx < 7.0 && cy > 3.0 && cy < 7.0 ));
+		}
+	}
+
+	#[test]
+	fn two_holes() {
+		let data = [
+			0.0, 0.0, 20.0, 0.0, 20.0, 10.0, 0.0, 10.0,
+			2.0, 2.0, 2.0, 8.0, 8.0, 8.0, 8.0, 2.0,
+			12.0, 2.0, 12.0, 8.0, 18.0, 8.0, 18.0, 2.0,
+		];
+
+		// the bridges line up with hole edges, and collinear points are dropped, so there are
+		// fewer than n + 2h - 2 triangles here
+		let triangles = triangulate(&data, &[ 4, 8 ], 2);
+		assert_eq!(triangles.len() % 3, 0);
+
+		for t in triangles.chunks(3) {
+			assert!(triangle_area(&data, t[ 0 ], t[ 1 ], t[ 2 ]) > 0.0);
+		}
+
+		let area: f32 = triangles.chunks(3).map(|t| triangle_area(&data, t[ 0 ], t[ 1 ], t[ 2 ])).sum();
+		assert!(( area - 128.0 ).abs() < 1e-3);
+	}
+}
@@ -0,0 +1,4 @@
+pub mod curves;
+pub mod earcut;
+pub mod shape;
+pub mod shape_utils;
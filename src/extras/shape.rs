@@ -0,0 +1,216 @@
+use super::super::math::vector2::Vector2;
+use super::curves::curve::{Curve, LengthCache};
+use super::curves::curve_path::CurvePath;
+use super::curves::line_curve::LineCurve;
+use super::curves::quadratic_bezier_curve::QuadraticBezierCurve;
+use super::curves::cubic_bezier_curve::CubicBezierCurve;
+use super::curves::ellipse_curve::EllipseCurve;
+
+pub struct Path {
+	pub curve_path: CurvePath<Vector2>,
+	pub current_point: Vector2,
+}
+
+impl Path {
+	pub fn new() -> Path {
+		Path {
+			curve_path: CurvePath::new(),
+			current_point: Vector2::new(),
+		}
+	}
+
+	pub fn set_from_points(&mut self, points: &[Vector2]) {
+		if let Some((first, rest)) = points.split_first() {
+			self.move_to(first.x, first.y);
+
+			for point in rest {
+				self.line_to(point.x, point.y);
+			}
+		}
+	}
+
+	pub fn move_to(&mut self, x: f32, y: f32) {
+		self.current_point.set(x, y);
+	}
+
+	pub fn line_to(&mut self, x: f32, y: f32) {
+		let curve = LineCurve::new(self.current_point, Vector2 { x, y });
+		self.curve_path.add(Box::new(curve));
+
+		self.current_point.set(x, y);
+	}
+
+	pub fn quadratic_curve_to(&mut self, a_cpx: f32, a_cpy: f32, a_x: f32, a_y: f32) {
+		let curve = QuadraticBezierCurve::new(
+			self.current_point,
+			Vector2 { x: a_cpx, y: a_cpy },
+			Vector2 { x: a_x, y: a_y }
+		);
+		self.curve_path.add(Box::new(curve));
+
+		self.current_point.set(a_x, a_y);
+	}
+
+	pub fn bezier_curve_to(&mut self, a_cp1x: f32, a_cp1y: f32, a_cp2x: f32, a_cp2y: f32, a_x: f32, a_y: f32) {
+		let curve = CubicBezierCurve::new(
+			self.current_point,
+			Vector2 { x: a_cp1x, y: a_cp1y },
+			Vector2 { x: a_cp2x, y: a_cp2y },
+			Vector2 { x: a_x, y: a_y }
+		);
+		self.curve_path.add(Box::new(curve));
+
+		self.current_point.set(a_x, a_y);
+	}
+
+	pub fn arc(&mut self, a_x: f32, a_y: f32, a_radius: f32, a_start_angle: f32, a_end_angle: f32, a_clockwise: bool) {
+		let x0 = self.current_point.x;
+		let y0 = self.current_point.y;
+
+		self.absarc(a_x + x0, a_y + y0, a_radius, a_start_angle, a_end_angle, a_clockwise);
+	}
+
+	pub fn absarc(&mut self, a_x: f32, a_y: f32, a_radius: f32, a_start_angle: f32, a_end_angle: f32, a_clockwise: bool) {
+		self.absellipse(a_x, a_y, a_radius, a_radius, a_start_angle, a_end_angle, a_clockwise, 0.0);
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn ellipse(&mut self, a_x: f32, a_y: f32, x_radius: f32, y_radius: f32, a_start_angle: f32, a_end_angle: f32, a_clockwise: bool, a_rotation: f32) {
+		let x0 = self.current_point.x;
+		let y0 = self.current_point.y;
+
+		self.absellipse(a_x + x0, a_y + y0, x_radius, y_radius, a_start_angle, a_end_angle, a_clockwise, a_rotation);
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn absellipse(&mut self, a_x: f32, a_y: f32, x_radius: f32, y_radius: f32, a_start_angle: f32, a_end_angle: f32, a_clockwise: bool, a_rotation: f32) {
+		let curve = EllipseCurve::new(a_x, a_y, x_radius, y_radius, a_start_angle, a_end_angle, a_clockwise, a_rotation);
+
+		if !self.curve_path.curves.is_empty() {
+			// if a previous curve is present, attempt to join
+			let first_point = curve.get_point(0.0);
+
+			if !first_point.equals(&self.current_point) {
+				self.line_to(first_point.x, first_point.y);
+			}
+		}
+
+		self.curve_path.add(Box::new(curve));
+
+		let last_point = curve.get_point(1.0);
+		self.current_point.copy(&last_point);
+	}
+
+	pub fn close_path(&mut self) {
+		// Add a line curve if start and end of lines are not connected
+		if self.curve_path.curves.is_empty() {
+			return;
+		}
+
+		let start_point = self.curve_path.curves[0].get_point(0.0);
+		let end_point = self.curve_path.curves[self.curve_path.curves.len() - 1].get_point(1.0);
+
+		if !start_point.equals(&end_point) {
+			self.curve_path.add(Box::new(LineCurve::new(end_point, start_point)));
+			self.current_point.copy(&start_point);
+		}
+	}
+}
+
+impl Default for Path {
+	fn default() -> Path {
+		Path::new()
+	}
+}
+
+impl Curve for Path {
+	type Vector = Vector2;
+
+	fn get_point(&self, t: f32) -> Vector2 {
+		self.curve_path.get_point(t)
+	}
+
+	fn get_arc_length_cache(&self) -> Option<&LengthCache> {
+		self.curve_path.get_arc_length_cache()
+	}
+
+	fn update_arc_lengths(&self) {
+		self.curve_path.update_arc_lengths();
+	}
+
+	fn get_length(&self) -> f32 {
+		self.curve_path.get_length()
+	}
+
+	fn get_points(&self, divisions: usize) -> Vec<Vector2> {
+		self.curve_path.get_points(divisions)
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct ShapePoints {
+	pub shape: Vec<Vector2>,
+	pub holes: Vec<Vec<Vector2>>,
+}
+
+pub struct Shape {
+	pub path: Path,
+	pub holes: Vec<Path>,
+}
+
+impl Shape {
+	pub fn new() -> Shape {
+		Shape {
+			path: Path::new(),
+			holes: vec![],
+		}
+	}
+
+	pub fn from_points(points: &[Vector2]) -> Shape {
+		let mut shape = Shape::new();
+		shape.path.set_from_points(points);
+		shape
+	}
+
+	pub fn get_points_holes(&self, divisions: usize) -> Vec<Vec<Vector2>> {
+		self.holes.iter().map(|hole| hole.get_points(divisions)).collect()
+	}
+
+	// get points of shape and holes (keypoints based on segments parameter)
+	pub fn extract_points(&self, divisions: usize) -> ShapePoints {
+		ShapePoints {
+			shape: self.path.get_points(divisions),
+			holes: self.get_points_holes(divisions),
+		}
+	}
+}
+
+impl Default for Shape {
+	fn default() -> Shape {
+		Shape::new()
+	}
+}
+
+impl Curve for Shape {
+	type Vector = Vector2;
+
+	fn get_point(&self, t: f32) -> Vector2 {
+		self.path.get_point(t)
+	}
+
+	fn get_arc_length_cache(&self) -> Option<&LengthCache> {
+		self.path.get_arc_length_cache()
+	}
+
+	fn update_arc_lengths(&self) {
+		self.path.update_arc_lengths();
+	}
+
+	fn get_length(&self) -> f32 {
+		self.path.get_length()
+	}
+
+	fn get_points(&self, divisions: usize) -> Vec<Vector2> {
+		self.path.get_points(divisions)
+	}
+}
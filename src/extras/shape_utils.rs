@@ -0,0 +1,67 @@
+use super::super::math::vector2::Vector2;
+use super::earcut;
+
+pub struct ShapeUtils;
+
+impl ShapeUtils {
+	// calculate area of the contour polygon
+	pub fn area(contour: &[Vector2]) -> f32 {
+		let n = contour.len();
+		let mut a = 0.0;
+
+		if n == 0 {
+			return a;
+		}
+
+		let mut p = n - 1;
+		for q in 0..n {
+			a += contour[ p ].x * contour[ q ].y - contour[ q ].x * contour[ p ].y;
+			p = q;
+		}
+
+		a * 0.5
+	}
+
+	pub fn is_clock_wise(pts: &[Vector2]) -> bool {
+		ShapeUtils::area(pts) < 0.0
+	}
+
+	// Returns the triangles as indices into the contour followed by each hole, in order.
+	pub fn triangulate_shape(contour: &[Vector2], holes: &[Vec<Vector2>]) -> Vec<[usize; 3]> {
+		let mut vertices = vec![]; // flat array of vertices like [ x0,y0, x1,y1, x2,y2, ... ]
+		let mut hole_indices = vec![]; // array of hole indices
+
+		let contour = remove_dup_end_pts(contour);
+		add_contour(&mut vertices, contour);
+
+		let mut hole_index = contour.len();
+
+		for hole in holes {
+			let hole = remove_dup_end_pts(hole);
+			hole_indices.push(hole_index);
+			hole_index += hole.len();
+			add_contour(&mut vertices, hole);
+		}
+
+		let triangles = earcut::triangulate(&vertices, &hole_indices, 2);
+
+		triangles.chunks(3).map(|face| [face[ 0 ], face[ 1 ], face[ 2 ]]).collect()
+	}
+}
+
+fn remove_dup_end_pts(points: &[Vector2]) -> &[Vector2] {
+	let l = points.len();
+
+	if l > 2 && points[ l - 1 ].equals(&points[ 0 ]) {
+		&points[..l - 1]
+	} else {
+		points
+	}
+}
+
+fn add_contour(vertices: &mut Vec<f32>, contour: &[Vector2]) {
+	for point in contour {
+		vertices.push(point.x);
+		vertices.push(point.y);
+	}
+}
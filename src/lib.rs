@@ -1,6 +1,30 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+// The test harness links std, which brings back the inherent f32 methods that math::float stands in for.
+#![cfg_attr(all(not(feature = "std"), test), allow(unused_imports))]
+
+// Without the std feature only math is built, on top of core, alloc and libm. Aliasing
+// core as std keeps the ::std paths in math valid in both builds.
+#[cfg(not(feature = "std"))]
+extern crate core as std;
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
+
+#[cfg(feature = "std")]
 pub mod core;
 pub mod math;
+#[cfg(feature = "std")]
 pub mod cameras;
+#[cfg(feature = "std")]
+pub mod extras;
+#[cfg(feature = "std")]
+pub mod materials;
+#[cfg(feature = "std")]
+pub mod scenes;
+#[cfg(feature = "std")]
+pub mod textures;
+#[cfg(feature = "std")]
+pub mod utils;
 
 #[cfg(test)]
 mod tests {
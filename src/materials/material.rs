@@ -0,0 +1,59 @@
+extern crate uuid;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use self::uuid::Uuid;
+use super::super::core::change_tracker::next_version;
+
+static MATERIAL_ID: AtomicUsize = AtomicUsize::new(0);
+
+// The properties every material has. Nothing keeps track of changes to the fields, call
+// needs_update after changing them so whatever mirrors the material picks them up.
+#[derive(Debug, Clone)]
+pub struct Material {
+	id: usize,
+	uuid: Uuid,
+	pub name: String,
+	pub opacity: f32,
+	pub transparent: bool,
+	pub visible: bool,
+	pub depth_test: bool,
+	pub depth_write: bool,
+	version: u64,
+}
+
+impl Material {
+	pub fn new() -> Material {
+		Material {
+			id: MATERIAL_ID.fetch_add(1, Ordering::Relaxed),
+			uuid: Uuid::new_v4(),
+			name: String::new(),
+			opacity: 1.0,
+			transparent: false,
+			visible: true,
+			depth_test: true,
+			depth_write: true,
+			version: next_version(),
+		}
+	}
+
+	pub fn get_id(&self) -> usize {
+		self.id
+	}
+
+	pub fn get_uuid(&self) -> &Uuid {
+		&self.uuid
+	}
+
+	pub fn get_version(&self) -> u64 {
+		self.version
+	}
+
+	pub fn needs_update(&mut self) {
+		self.version = next_version();
+	}
+}
+
+impl Default for Material {
+	fn default() -> Material {
+		Material::new()
+	}
+}
@@ -0,0 +1 @@
+pub mod material;
\ No newline at end of file
@@ -0,0 +1,202 @@
+use super::vector3::Vector3;
+use super::matrix4::Matrix4;
+use super::ray::Ray;
+use super::sphere::Sphere;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Box3 {
+	pub min: Vector3,
+	pub max: Vector3,
+}
+
+impl Box3 {
+	pub fn new() -> Box3 {
+		Box3 {
+			min: Vector3 {
+				x: f32::INFINITY,
+				y: f32::INFINITY,
+				z: f32::INFINITY,
+			},
+			max: Vector3 {
+				x: f32::NEG_INFINITY,
+				y: f32::NEG_INFINITY,
+				z: f32::NEG_INFINITY,
+			},
+		}
+	}
+
+	pub fn set(&mut self, min: &Vector3, max: &Vector3) {
+		self.min.copy(min);
+		self.max.copy(max);
+	}
+
+	pub fn set_from_points(&mut self, points: &[Vector3]) {
+		self.make_empty();
+
+		for point in points {
+			self.expand_by_point(point);
+		}
+	}
+
+	pub fn set_from_center_and_size(&mut self, center: &Vector3, size: &Vector3) {
+		let mut half_size = *size;
+		half_size.multiply_scalar(0.5);
+
+		self.min.sub_vectors(center, &half_size);
+		self.max.add_vectors(center, &half_size);
+	}
+
+	pub fn copy(&mut self, box3: &Box3) {
+		self.min.copy(&box3.min);
+		self.max.copy(&box3.max);
+	}
+
+	pub fn make_empty(&mut self) {
+		self.min.set_scalar(f32::INFINITY);
+		self.max.set_scalar(f32::NEG_INFINITY);
+	}
+
+	pub fn is_empty(&self) -> bool {
+		// this is a more robust check for empty than ( volume <= 0 ) because volume can get positive with two negative axes
+		self.max.x < self.min.x || self.max.y < self.min.y || self.max.z < self.min.z
+	}
+
+	pub fn get_center(&self, target: &mut Vector3) {
+		if self.is_empty() {
+			target.set(0.0, 0.0, 0.0);
+		} else {
+			target.add_vectors(&self.min, &self.max);
+			target.multiply_scalar(0.5);
+		}
+	}
+
+	pub fn get_size(&self, target: &mut Vector3) {
+		if self.is_empty() {
+			target.set(0.0, 0.0, 0.0);
+		} else {
+			target.sub_vectors(&self.max, &self.min);
+		}
+	}
+
+	pub fn expand_by_point(&mut self, point: &Vector3) {
+		self.min.min(point);
+		self.max.max(point);
+	}
+
+	pub fn contains_point(&self, point: &Vector3) -> bool {
+		!(point.x < self.min.x || point.x > self.max.x ||
+			point.y < self.min.y || point.y > self.max.y ||
+			point.z < self.min.z || point.z > self.max.z)
+	}
+
+	pub fn intersects_box(&self, box3: &Box3) -> bool {
+		// using 6 splitting planes to rule out intersections.
+		!(box3.max.x < self.min.x || box3.min.x > self.max.x ||
+			box3.max.y < self.min.y || box3.min.y > self.max.y ||
+			box3.max.z < self.min.z || box3.min.z > self.max.z)
+	}
+
+	pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+		// Find the point on the AABB closest to the sphere center.
+		let mut closest_point = Vector3::new();
+		self.clamp_point(&sphere.center, &mut closest_point);
+
+		// If that point is inside the sphere, the AABB and sphere intersect.
+		closest_point.distance_to_squared(&sphere.center) <= sphere.radius * sphere.radius
+	}
+
+	pub fn clamp_point(&self, point: &Vector3, target: &mut Vector3) {
+		target.copy(point);
+		target.clamp(&self.min, &self.max);
+	}
+
+	pub fn distance_to_point(&self, point: &Vector3) -> f32 {
+		let mut clamped_point = Vector3::new();
+		self.clamp_point(point, &mut clamped_point);
+		clamped_point.sub(point);
+		clamped_point.length()
+	}
+
+	// Slab test; returns the closest point where the ray enters the box, or the ray origin if it starts inside.
+	pub fn intersect_ray(&self, ray: &Ray) -> Option<Vector3> {
+		let origin = ray.origin;
+		let direction = ray.direction;
+
+		let mut t_min = f32::NEG_INFINITY;
+		let mut t_max = f32::INFINITY;
+
+		for &(o, d, min, max) in &[
+			(origin.x, direction.x, self.min.x, self.max.x),
+			(origin.y, direction.y, self.min.y, self.max.y),
+			(origin.z, direction.z, self.min.z, self.max.z),
+		] {
+			if d == 0.0 {
+				// parallel to the slab, so the origin has to lie between the planes
+				if o < min || o > max {
+					return None;
+				}
+				continue;
+			}
+
+			let inv_dir = 1.0 / d;
+			let (t0, t1) = if inv_dir >= 0.0 {
+				(( min - o ) * inv_dir, ( max - o ) * inv_dir)
+			} else {
+				(( max - o ) * inv_dir, ( min - o ) * inv_dir)
+			};
+
+			t_min = t_min.max(t0);
+			t_max = t_max.min(t1);
+
+			if t_min > t_max {
+				return None;
+			}
+		}
+
+		// return if box is behind ray
+		if t_max < 0.0 {
+			return None;
+		}
+
+		let mut target = Vector3::new();
+		ray.at(t_min.max(0.0), &mut target);
+		Some(target)
+	}
+
+	pub fn intersects_ray(&self, ray: &Ray) -> bool {
+		self.intersect_ray(ray).is_some()
+	}
+
+	pub fn apply_matrix4(&mut self, matrix: &Matrix4) {
+		// transform of empty box is an empty box.
+		if self.is_empty() {
+			return;
+		}
+
+		let min = self.min;
+		let max = self.max;
+
+		self.make_empty();
+
+		// NOTE: I am using a binary pattern to specify all 2^3 combinations below
+		for i in 0..8 {
+			let mut point = Vector3 {
+				x: if i & 4 == 0 { min.x } else { max.x },
+				y: if i & 2 == 0 { min.y } else { max.y },
+				z: if i & 1 == 0 { min.z } else { max.z },
+			};
+			point.apply_matrix4(matrix);
+			self.expand_by_point(&point);
+		}
+	}
+
+	pub fn equals(&self, box3: &Box3) -> bool {
+		box3.min.equals(&self.min) && box3.max.equals(&self.max)
+	}
+}
+
+impl Default for Box3 {
+	fn default() -> Box3 {
+		Box3::new()
+	}
+}
@@ -0,0 +1,80 @@
+#[cfg(not(feature = "std"))]
+use super::float::Float;
+use super::math_static::clamp;
+
+// Linear rgb, each channel from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+	pub r: f32,
+	pub g: f32,
+	pub b: f32,
+}
+
+impl Color {
+	pub fn new() -> Color {
+		Color {
+			r: 1.0,
+			g: 1.0,
+			b: 1.0,
+		}
+	}
+
+	pub fn from_hex(hex: u32) -> Color {
+		let mut color = Color::new();
+		color.set_hex(hex);
+		color
+	}
+
+	pub fn set_rgb(&mut self, r: f32, g: f32, b: f32) {
+		self.r = r;
+		self.g = g;
+		self.b = b;
+	}
+
+	pub fn set_scalar(&mut self, scalar: f32) {
+		self.r = scalar;
+		self.g = scalar;
+		self.b = scalar;
+	}
+
+	// 0xrrggbb
+	pub fn set_hex(&mut self, hex: u32) {
+		self.r = ( hex >> 16 & 255 ) as f32 / 255.0;
+		self.g = ( hex >> 8 & 255 ) as f32 / 255.0;
+		self.b = ( hex & 255 ) as f32 / 255.0;
+	}
+
+	pub fn get_hex(&self) -> u32 {
+		let channel = |c: f32| ( clamp(c, 0.0, 1.0) * 255.0 ).round() as u32;
+		channel(self.r) << 16 | channel(self.g) << 8 | channel(self.b)
+	}
+
+	pub fn copy(&mut self, color: &Color) {
+		self.r = color.r;
+		self.g = color.g;
+		self.b = color.b;
+	}
+
+	pub fn lerp(&mut self, color: &Color, alpha: f32) {
+		self.r += ( color.r - self.r ) * alpha;
+		self.g += ( color.g - self.g ) * alpha;
+		self.b += ( color.b - self.b ) * alpha;
+	}
+
+	pub fn lerp_colors(&mut self, color1: &Color, color2: &Color, alpha: f32) {
+		self.copy(color1);
+		self.lerp(color2, alpha);
+	}
+
+	pub fn multiply_scalar(&mut self, s: f32) {
+		self.r *= s;
+		self.g *= s;
+		self.b *= s;
+	}
+}
+
+impl Default for Color {
+	fn default() -> Color {
+		Color::new()
+	}
+}
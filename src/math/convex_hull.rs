@@ -0,0 +1,1055 @@
+use super::vector3::Vector3;
+use super::ray::Ray;
+use super::line3::Line3;
+use super::triangle::Triangle;
+#[cfg(feature = "std")]
+use super::super::core::object3d::Object3D;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use super::float::Float;
+
+/*
+ * Ported from: https://github.com/maurizzzio/quickhull3d/ by Mauricio Poppe (https://github.com/maurizzzio)
+ *
+ * Vertices, half edges and faces live in arenas owned by the hull and refer to each other by index.
+ */
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum FaceMark {
+	Visible,
+	Deleted,
+}
+
+// A vertex as a double linked list node.
+#[derive(Debug, Clone, Copy)]
+struct VertexNode {
+	point: Vector3,
+	prev: Option<usize>,
+	next: Option<usize>,
+	face: Option<usize>, // the face that is able to see this vertex
+}
+
+// Entity for a Doubly-Connected Edge List (DCEL).
+#[derive(Debug, Clone, Copy)]
+struct HalfEdge {
+	vertex: usize,
+	prev: usize,
+	next: usize,
+	twin: Option<usize>,
+	face: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Face {
+	normal: Vector3,
+	midpoint: Vector3,
+	area: f32,
+	constant: f32, // signed distance from face to the origin
+	outside: Option<usize>, // reference to a vertex in a vertex list this face can see
+	mark: FaceMark,
+	edge: usize,
+}
+
+impl Face {
+	fn distance_to_point(&self, point: &Vector3) -> f32 {
+		self.normal.dot(point) - self.constant
+	}
+}
+
+// A double linked list that contains vertex nodes.
+#[derive(Debug, Clone, Copy)]
+struct VertexList {
+	head: Option<usize>,
+	tail: Option<usize>,
+}
+
+impl VertexList {
+	fn new() -> VertexList {
+		VertexList {
+			head: None,
+			tail: None,
+		}
+	}
+
+	fn clear(&mut self) {
+		self.head = None;
+		self.tail = None;
+	}
+
+	// Inserts a vertex before the target vertex
+	fn insert_before(&mut self, vertices: &mut [VertexNode], target: usize, vertex: usize) {
+		let target_prev = vertices[ target ].prev;
+		vertices[ vertex ].prev = target_prev;
+		vertices[ vertex ].next = Some(target);
+
+		match target_prev {
+			None => self.head = Some(vertex),
+			Some(prev) => vertices[ prev ].next = Some(vertex),
+		}
+
+		vertices[ target ].prev = Some(vertex);
+	}
+
+	// Appends a vertex to the end of the linked list
+	fn append(&mut self, vertices: &mut [VertexNode], vertex: usize) {
+		match self.tail {
+			Some(tail) if self.head.is_some() => vertices[ tail ].next = Some(vertex),
+			_ => self.head = Some(vertex),
+		}
+
+		vertices[ vertex ].prev = self.tail;
+		vertices[ vertex ].next = None; // the tail has no subsequent vertex
+
+		self.tail = Some(vertex);
+	}
+
+	// Appends a chain of vertices where 'vertex' is the head.
+	fn append_chain(&mut self, vertices: &mut [VertexNode], vertex: usize) {
+		match self.tail {
+			Some(tail) if self.head.is_some() => vertices[ tail ].next = Some(vertex),
+			_ => self.head = Some(vertex),
+		}
+
+		vertices[ vertex ].prev = self.tail;
+
+		// ensure that the 'tail' reference points to the last vertex of the chain
+		let mut last = vertex;
+		while let Some(next) = vertices[ last ].next {
+			last = next;
+		}
+
+		self.tail = Some(last);
+	}
+
+	// Removes a vertex from the linked list
+	fn remove(&mut self, vertices: &mut [VertexNode], vertex: usize) {
+		self.remove_sub_list(vertices, vertex, vertex);
+	}
+
+	// Removes a list of vertices whose 'head' is 'a' and whose 'tail' is b
+	fn remove_sub_list(&mut self, vertices: &mut [VertexNode], a: usize, b: usize) {
+		let a_prev = vertices[ a ].prev;
+		let b_next = vertices[ b ].next;
+
+		match a_prev {
+			None => self.head = b_next,
+			Some(prev) => vertices[ prev ].next = b_next,
+		}
+
+		match b_next {
+			None => self.tail = a_prev,
+			Some(next) => vertices[ next ].prev = a_prev,
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct ConvexHull {
+	epsilon: f32,
+	tolerance: f32,
+	faces: Vec<usize>, // the generated faces of the convex hull
+	new_faces: Vec<usize>, // this array holds the faces that are generated within a single iteration
+	assigned: VertexList,
+	unassigned: VertexList,
+	vertices: Vec<VertexNode>, // vertices of the hull (internal representation of given geometry data)
+	face_pool: Vec<Face>,
+	edges: Vec<HalfEdge>,
+}
+
+impl ConvexHull {
+	pub fn new() -> ConvexHull {
+		ConvexHull {
+			epsilon: f32::EPSILON,
+			tolerance: - 1.0,
+			faces: vec![],
+			new_faces: vec![],
+			assigned: VertexList::new(),
+			unassigned: VertexList::new(),
+			vertices: vec![],
+			face_pool: vec![],
+			edges: vec![],
+		}
+	}
+
+	pub fn get_epsilon(&self) -> f32 {
+		self.epsilon
+	}
+
+	// The tolerance used for every plane test is derived from this epsilon and the extent of the input.
+	pub fn set_epsilon(&mut self, epsilon: f32) {
+		self.epsilon = epsilon;
+	}
+
+	pub fn get_tolerance(&self) -> f32 {
+		self.tolerance
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.faces.is_empty()
+	}
+
+	// Leaves the hull empty when the points don't span a volume (fewer than four points,
+	// or all of them coincident, collinear or coplanar).
+	pub fn set_from_points(&mut self, points: &[Vector3]) {
+		self.make_empty();
+
+		if points.len() >= 4 {
+			for point in points {
+				self.vertices.push(VertexNode {
+					point: *point,
+					prev: None,
+					next: None,
+					face: None,
+				});
+			}
+
+			self.compute();
+		}
+	}
+
+	// Builds the hull of the given object-space points placed by the object's world matrix.
+	#[cfg(feature = "std")]
+	pub fn set_from_object(&mut self, object: &Object3D, points: &[Vector3]) {
+		let matrix_world = object.get_matrix_world();
+		let world_points: Vec<Vector3> = points.iter().map(|point| {
+			let mut p = *point;
+			p.apply_matrix4(matrix_world);
+			p
+		}).collect();
+
+		self.set_from_points(&world_points);
+	}
+
+	pub fn contains_point(&self, point: &Vector3) -> bool {
+		if self.faces.is_empty() {
+			return false;
+		}
+
+		for &face in &self.faces {
+			// compute signed distance and check on what half space the point lies
+			if self.face_pool[ face ].distance_to_point(point) > self.tolerance {
+				return false;
+			}
+		}
+
+		true
+	}
+
+	pub fn intersect_ray(&self, ray: &Ray) -> Option<Vector3> {
+		// based on "Fast Ray-Convex Polyhedron Intersection" by Eric Haines, GRAPHICS GEMS II
+
+		if self.faces.is_empty() {
+			return None;
+		}
+
+		let mut t_near = f32::NEG_INFINITY;
+		let mut t_far = f32::INFINITY;
+
+		for &face in &self.faces {
+			let face = &self.face_pool[ face ];
+
+			// interpret faces as planes for the further computation
+
+			let v_n = face.distance_to_point(&ray.origin);
+			let v_d = face.normal.dot(&ray.direction);
+
+			// if the origin is on the positive side of a plane (so the plane can "see" the origin) and
+			// the ray is turned away or parallel to the plane, there is no intersection
+
+			if v_n > 0.0 && v_d >= 0.0 {
+				return None;
+			}
+
+			// compute the distance from the ray’s origin to the intersection with the plane
+
+			let t = if v_d != 0.0 { - v_n / v_d } else { 0.0 };
+
+			// only proceed if the distance is positive. a negative distance means the intersection point
+			// lies "behind" the origin
+
+			if t <= 0.0 {
+				continue;
+			}
+
+			// now categorized plane as front-facing or back-facing
+
+			if v_d > 0.0 {
+				//  plane faces away from the ray, so this plane is a back-face
+				t_far = t.min(t_far);
+			} else {
+				// front-face
+				t_near = t.max(t_near);
+			}
+
+			if t_near > t_far {
+				// if t_near ever is greater than t_far, the ray must miss the convex hull
+				return None;
+			}
+		}
+
+		// evaluate intersection point
+
+		// always try t_near first since its the closer intersection point
+
+		let mut target = Vector3::new();
+
+		if t_near != f32::NEG_INFINITY {
+			ray.at(t_near, &mut target);
+		} else if t_far != f32::INFINITY {
+			ray.at(t_far, &mut target);
+		} else {
+			// the origin lies inside the hull and every plane was parallel or behind it
+			target.copy(&ray.origin);
+		}
+
+		Some(target)
+	}
+
+	pub fn intersects_ray(&self, ray: &Ray) -> bool {
+		self.intersect_ray(ray).is_some()
+	}
+
+	pub fn make_empty(&mut self) {
+		self.faces.clear();
+		self.new_faces.clear();
+		self.vertices.clear();
+		self.face_pool.clear();
+		self.edges.clear();
+		self.assigned.clear();
+		self.unassigned.clear();
+		self.tolerance = - 1.0;
+	}
+
+	// Triangles of the hull, wound counter-clockwise so that get_normal points outwards.
+	pub fn get_faces(&self) -> Vec<Triangle> {
+		self.faces.iter().map(|&face| {
+			let edge = self.face_pool[ face ].edge;
+			Triangle {
+				a: self.vertices[ self.tail(edge) ].point,
+				b: self.vertices[ self.head(edge) ].point,
+				c: self.vertices[ self.head(self.edges[ edge ].next) ].point,
+			}
+		}).collect()
+	}
+
+	pub fn get_face_normals(&self) -> Vec<Vector3> {
+		self.faces.iter().map(|&face| self.face_pool[ face ].normal).collect()
+	}
+
+	// Each input point that ended up on the hull, once.
+	pub fn get_vertices(&self) -> Vec<Vector3> {
+		let mut used = vec![false; self.vertices.len()];
+		let mut vertices = vec![];
+
+		for &face in &self.faces {
+			let start = self.face_pool[ face ].edge;
+			let mut edge = start;
+
+			loop {
+				let vertex = self.head(edge);
+				if !used[ vertex ] {
+					used[ vertex ] = true;
+					vertices.push(self.vertices[ vertex ].point);
+				}
+
+				edge = self.edges[ edge ].next;
+
+				if edge == start {
+					break;
+				}
+			}
+		}
+
+		vertices
+	}
+
+	// Each edge of the hull once, rather than once per adjacent face.
+	pub fn get_edges(&self) -> Vec<Line3> {
+		let mut edges = vec![];
+
+		for &face in &self.faces {
+			let start = self.face_pool[ face ].edge;
+			let mut edge = start;
+
+			loop {
+				let keep = match self.edges[ edge ].twin {
+					Some(twin) => edge < twin,
+					None => true,
+				};
+
+				if keep {
+					edges.push(Line3 {
+						start: self.vertices[ self.tail(edge) ].point,
+						end: self.vertices[ self.head(edge) ].point,
+					});
+				}
+
+				edge = self.edges[ edge ].next;
+
+				if edge == start {
+					break;
+				}
+			}
+		}
+
+		edges
+	}
+
+	// Returns the destination vertex
+	fn head(&self, edge: usize) -> usize {
+		self.edges[ edge ].vertex
+	}
+
+	// Returns the origin vertex
+	fn tail(&self, edge: usize) -> usize {
+		self.edges[ self.edges[ edge ].prev ].vertex
+	}
+
+	fn set_twin(&mut self, a: usize, b: usize) {
+		self.edges[ a ].twin = Some(b);
+		self.edges[ b ].twin = Some(a);
+	}
+
+	fn get_edge(&self, face: usize, i: i32) -> usize {
+		let mut edge = self.face_pool[ face ].edge;
+		let mut i = i;
+
+		while i > 0 {
+			edge = self.edges[ edge ].next;
+			i -= 1;
+		}
+
+		while i < 0 {
+			edge = self.edges[ edge ].prev;
+			i += 1;
+		}
+
+		edge
+	}
+
+	fn create_face(&mut self, a: usize, b: usize, c: usize) -> usize {
+		let face = self.face_pool.len();
+		let e0 = self.edges.len();
+		let e1 = e0 + 1;
+		let e2 = e0 + 2;
+
+		// join edges
+
+		self.edges.push(HalfEdge { vertex: a, prev: e2, next: e1, twin: None, face });
+		self.edges.push(HalfEdge { vertex: b, prev: e0, next: e2, twin: None, face });
+		self.edges.push(HalfEdge { vertex: c, prev: e1, next: e0, twin: None, face });
+
+		// main half edge reference
+
+		self.face_pool.push(Face {
+			normal: Vector3::new(),
+			midpoint: Vector3::new(),
+			area: 0.0,
+			constant: 0.0,
+			outside: None,
+			mark: FaceMark::Visible,
+			edge: e0,
+		});
+
+		self.compute_face(face);
+
+		face
+	}
+
+	fn compute_face(&mut self, face: usize) {
+		let edge = self.face_pool[ face ].edge;
+		let triangle = Triangle {
+			a: self.vertices[ self.tail(edge) ].point,
+			b: self.vertices[ self.head(edge) ].point,
+			c: self.vertices[ self.head(self.edges[ edge ].next) ].point,
+		};
+
+		let face = &mut self.face_pool[ face ];
+		triangle.get_normal(&mut face.normal);
+		triangle.get_midpoint(&mut face.midpoint);
+		face.area = triangle.get_area();
+		face.constant = face.normal.dot(&face.midpoint);
+	}
+
+	// Adds a vertex to the 'assigned' list of vertices and assigns it to the given face
+	fn add_vertex_to_face(&mut self, vertex: usize, face: usize) {
+		self.vertices[ vertex ].face = Some(face);
+
+		match self.face_pool[ face ].outside {
+			None => self.assigned.append(&mut self.vertices, vertex),
+			Some(outside) => self.assigned.insert_before(&mut self.vertices, outside, vertex),
+		}
+
+		self.face_pool[ face ].outside = Some(vertex);
+	}
+
+	// Removes a vertex from the 'assigned' list of vertices and from the given face
+	fn remove_vertex_from_face(&mut self, vertex: usize, face: usize) {
+		if Some(vertex) == self.face_pool[ face ].outside {
+			// fix face.outside link
+			self.face_pool[ face ].outside = match self.vertices[ vertex ].next {
+				// face has at least 2 outside vertices, move the 'outside' reference
+				Some(next) if self.vertices[ next ].face == Some(face) => Some(next),
+				// vertex was the only outside vertex that face had
+				_ => None,
+			};
+		}
+
+		self.assigned.remove(&mut self.vertices, vertex);
+	}
+
+	// Removes all the visible vertices that a given face is able to see which are stored in the 'assigned' vertex list
+	fn remove_all_vertices_from_face(&mut self, face: usize) -> Option<usize> {
+		let start = self.face_pool[ face ].outside?;
+
+		// reference to the first and last vertex of this face
+		let mut end = start;
+
+		while let Some(next) = self.vertices[ end ].next {
+			if self.vertices[ next ].face != Some(face) {
+				break;
+			}
+			end = next;
+		}
+
+		self.assigned.remove_sub_list(&mut self.vertices, start, end);
+
+		// fix references
+		self.vertices[ start ].prev = None;
+		self.vertices[ end ].next = None;
+		self.face_pool[ face ].outside = None;
+
+		Some(start)
+	}
+
+	// Removes all the visible vertices that 'face' is able to see
+	fn delete_face_vertices(&mut self, face: usize, absorbing_face: Option<usize>) {
+		let face_vertices = match self.remove_all_vertices_from_face(face) {
+			Some(vertices) => vertices,
+			None => return,
+		};
+
+		match absorbing_face {
+			None => {
+				// mark the vertices to be reassigned to some other face
+				self.unassigned.append_chain(&mut self.vertices, face_vertices);
+			},
+			Some(absorbing_face) => {
+				// if there's an absorbing face try to assign as many vertices as possible to it
+				let mut vertex = Some(face_vertices);
+
+				while let Some(v) = vertex {
+					// we need to buffer the subsequent vertex at this point because the 'vertex.next' reference
+					// will be changed by upcoming method calls
+					let next_vertex = self.vertices[ v ].next;
+					let distance = self.face_pool[ absorbing_face ].distance_to_point(&self.vertices[ v ].point);
+
+					// check if 'vertex' is able to see 'absorbing_face'
+					if distance > self.tolerance {
+						self.add_vertex_to_face(v, absorbing_face);
+					} else {
+						self.unassigned.append(&mut self.vertices, v);
+					}
+
+					// now assign next vertex
+					vertex = next_vertex;
+				}
+			}
+		}
+	}
+
+	// Reassigns as many vertices as possible from the unassigned list to the new faces
+	fn resolve_unassigned_points(&mut self) {
+		let mut vertex = self.unassigned.head;
+
+		while let Some(v) = vertex {
+			// buffer 'next' reference, see delete_face_vertices()
+			let next_vertex = self.vertices[ v ].next;
+			let mut max_distance = self.tolerance;
+			let mut max_face = None;
+
+			for &face in &self.new_faces {
+				let face_ref = &self.face_pool[ face ];
+
+				if face_ref.mark == FaceMark::Visible {
+					let distance = face_ref.distance_to_point(&self.vertices[ v ].point);
+
+					if distance > max_distance {
+						max_distance = distance;
+						max_face = Some(face);
+					}
+
+					if max_distance > 1000.0 * self.tolerance {
+						break;
+					}
+				}
+			}
+
+			// 'max_face' can be None e.g. if there are identical vertices
+			if let Some(face) = max_face {
+				self.add_vertex_to_face(v, face);
+			}
+
+			vertex = next_vertex;
+		}
+	}
+
+	// Computes the extremes of a simplex which will be the initial hull
+	fn compute_extremes(&mut self) -> ([usize; 3], [usize; 3]) {
+		let mut min_vertices = [0usize; 3];
+		let mut max_vertices = [0usize; 3];
+
+		// initially assume that the first vertex is the min/max
+		let mut min = self.vertices[ 0 ].point;
+		let mut max = self.vertices[ 0 ].point;
+
+		// compute the min/max vertex on all six directions
+
+		for (i, vertex) in self.vertices.iter().enumerate() {
+			let mut point = vertex.point;
+
+			for j in 0..3 {
+				// update the min coordinates
+				if point.get_component(j) < min.get_component(j) {
+					min.set_component(j, point.get_component(j));
+					min_vertices[ j as usize ] = i;
+				}
+
+				// update the max coordinates
+				if point.get_component(j) > max.get_component(j) {
+					max.set_component(j, point.get_component(j));
+					max_vertices[ j as usize ] = i;
+				}
+			}
+		}
+
+		// use min/max vectors to compute an optimal epsilon
+
+		self.tolerance = 3.0 * self.epsilon * (
+			min.x.abs().max(max.x.abs()) +
+			min.y.abs().max(max.y.abs()) +
+			min.z.abs().max(max.z.abs())
+		);
+
+		(min_vertices, max_vertices)
+	}
+
+	// Computes the initial simplex assigning to its faces all the points
+	// that are candidates to form part of the hull
+	fn compute_initial_hull(&mut self) -> bool {
+		let (min, max) = self.compute_extremes();
+
+		// 1. Find the two vertices 'v0' and 'v1' with the greatest 1d separation
+		// (max.x - min.x)
+		// (max.y - min.y)
+		// (max.z - min.z)
+
+		let mut max_distance = 0.0;
+		let mut index = 0;
+
+		for i in 0..3 {
+			let distance = self.vertices[ max[ i ] ].point.get_component(i as i32) - self.vertices[ min[ i ] ].point.get_component(i as i32);
+
+			if distance > max_distance {
+				max_distance = distance;
+				index = i;
+			}
+		}
+
+		// all points coincide
+		if max_distance <= self.tolerance {
+			return false;
+		}
+
+		let v0 = min[ index ];
+		let v1 = max[ index ];
+
+		// 2. The next vertex 'v2' is the one farthest to the line formed by 'v0' and 'v1'
+
+		let mut line3 = Line3::new();
+		let mut closest_point = Vector3::new();
+		line3.set(&self.vertices[ v0 ].point, &self.vertices[ v1 ].point);
+
+		let mut max_distance = 0.0;
+		let mut v2 = None;
+
+		for (i, vertex) in self.vertices.iter().enumerate() {
+			if i != v0 && i != v1 {
+				line3.closest_point_to_point(&vertex.point, true, &mut closest_point);
+
+				let distance = closest_point.distance_to_squared(&vertex.point);
+
+				if distance > max_distance {
+					max_distance = distance;
+					v2 = Some(i);
+				}
+			}
+		}
+
+		// all points are collinear
+		let v2 = match v2 {
+			Some(v2) if max_distance.sqrt() > self.tolerance => v2,
+			_ => return false,
+		};
+
+		// 3. The next vertex 'v3' is the one farthest to the plane 'v0', 'v1', 'v2'
+
+		let mut plane_normal = Vector3::new();
+		Triangle::get_normal_from_points(&self.vertices[ v0 ].point, &self.vertices[ v1 ].point, &self.vertices[ v2 ].point, &mut plane_normal);
+		let plane_constant = - plane_normal.dot(&self.vertices[ v0 ].point);
+		let plane_distance = |point: &Vector3| plane_normal.dot(point) + plane_constant;
+
+		let mut max_distance = - 1.0;
+		let mut v3 = v0;
+
+		for (i, vertex) in self.vertices.iter().enumerate() {
+			if i != v0 && i != v1 && i != v2 {
+				let distance = plane_distance(&vertex.point).abs();
+
+				if distance > max_distance {
+					max_distance = distance;
+					v3 = i;
+				}
+			}
+		}
+
+		// all points are coplanar
+		if max_distance <= self.tolerance {
+			return false;
+		}
+
+		let mut faces = [0usize; 4];
+
+		if plane_distance(&self.vertices[ v3 ].point) < 0.0 {
+			// the face is not able to see the point so 'plane.normal' is pointing outside the tetrahedron
+
+			faces[ 0 ] = self.create_face(v0, v1, v2);
+			faces[ 1 ] = self.create_face(v3, v1, v0);
+			faces[ 2 ] = self.create_face(v3, v2, v1);
+			faces[ 3 ] = self.create_face(v3, v0, v2);
+
+			// set the twin edge
+
+			for i in 0..3 {
+				let j = ( i + 1 ) % 3;
+
+				// join face[ i ] i > 0, with the first face
+
+				let a = self.get_edge(faces[ i + 1 ], 2);
+				let b = self.get_edge(faces[ 0 ], j as i32);
+				self.set_twin(a, b);
+
+				// join face[ i ] with face[ i + 1 ], 1 <= i <= 3
+
+				let a = self.get_edge(faces[ i + 1 ], 1);
+				let b = self.get_edge(faces[ j + 1 ], 0);
+				self.set_twin(a, b);
+			}
+		} else {
+			// the face is able to see the point so 'plane.normal' is pointing inside the tetrahedron
+
+			faces[ 0 ] = self.create_face(v0, v2, v1);
+			faces[ 1 ] = self.create_face(v3, v0, v1);
+			faces[ 2 ] = self.create_face(v3, v1, v2);
+			faces[ 3 ] = self.create_face(v3, v2, v0);
+
+			// set the twin edge
+
+			for i in 0..3 {
+				let j = ( i + 1 ) % 3;
+
+				// join face[ i ] i > 0, with the first face
+
+				let a = self.get_edge(faces[ i + 1 ], 2);
+				let b = self.get_edge(faces[ 0 ], ( ( 3 - i ) % 3 ) as i32);
+				self.set_twin(a, b);
+
+				// join face[ i ] with face[ i + 1 ]
+
+				let a = self.get_edge(faces[ i + 1 ], 0);
+				let b = self.get_edge(faces[ j + 1 ], 1);
+				self.set_twin(a, b);
+			}
+		}
+
+		// the initial hull is the tetrahedron
+
+		self.faces.extend_from_slice(&faces);
+
+		// initial assignment of vertices to the faces of the tetrahedron
+
+		for vertex in 0..self.vertices.len() {
+			if vertex != v0 && vertex != v1 && vertex != v2 && vertex != v3 {
+				let mut max_distance = self.tolerance;
+				let mut max_face = None;
+
+				for &face in &faces {
+					let distance = self.face_pool[ face ].distance_to_point(&self.vertices[ vertex ].point);
+
+					if distance > max_distance {
+						max_distance = distance;
+						max_face = Some(face);
+					}
+				}
+
+				if let Some(face) = max_face {
+					self.add_vertex_to_face(vertex, face);
+				}
+			}
+		}
+
+		true
+	}
+
+	// Removes inactive faces
+	fn reindex_faces(&mut self) {
+		let face_pool = &self.face_pool;
+		self.faces.retain(|&face| face_pool[ face ].mark == FaceMark::Visible);
+	}
+
+	// Finds the next vertex to create faces with the current hull
+	fn next_vertex_to_add(&self) -> Option<usize> {
+		// if the 'assigned' list of vertices is empty, no vertices are left. return with 'None'
+
+		let first = self.assigned.head?;
+
+		let mut eye_vertex = None;
+		let mut max_distance = 0.0;
+
+		// grap the first available face and start with the first visible vertex of that face
+
+		let eye_face = self.vertices[ first ].face?;
+		let mut vertex = self.face_pool[ eye_face ].outside;
+
+		// now calculate the farthest vertex that face can see
+
+		while let Some(v) = vertex {
+			if self.vertices[ v ].face != Some(eye_face) {
+				break;
+			}
+
+			let distance = self.face_pool[ eye_face ].distance_to_point(&self.vertices[ v ].point);
+
+			if distance > max_distance {
+				max_distance = distance;
+				eye_vertex = Some(v);
+			}
+
+			vertex = self.vertices[ v ].next;
+		}
+
+		eye_vertex
+	}
+
+	// Computes a chain of half edges in CCW order called the 'horizon'.
+	// For an edge to be part of the horizon it must join a face that can see
+	// 'eye_point' and a face that cannot see 'eye_point'.
+	fn compute_horizon(&mut self, eye_point: &Vector3, cross_edge: Option<usize>, face: usize, horizon: &mut Vec<usize>) {
+		// moves face's vertices to the 'unassigned' vertex list
+		self.delete_face_vertices(face, None);
+
+		self.face_pool[ face ].mark = FaceMark::Deleted;
+
+		let (mut edge, cross_edge) = match cross_edge {
+			None => {
+				let edge = self.get_edge(face, 0);
+				(edge, edge)
+			},
+			// start from the next edge since 'cross_edge' was already analyzed
+			// (actually 'cross_edge.twin' was the edge who called this method recursively)
+			Some(cross_edge) => (self.edges[ cross_edge ].next, cross_edge),
+		};
+
+		loop {
+			if let Some(twin_edge) = self.edges[ edge ].twin {
+				let opposite_face = self.edges[ twin_edge ].face;
+
+				if self.face_pool[ opposite_face ].mark == FaceMark::Visible {
+					if self.face_pool[ opposite_face ].distance_to_point(eye_point) > self.tolerance {
+						// the opposite face can see the vertex, so proceed with next edge
+						self.compute_horizon(eye_point, Some(twin_edge), opposite_face, horizon);
+					} else {
+						// the opposite face can't see the vertex, so this edge is part of the horizon
+						horizon.push(edge);
+					}
+				}
+			}
+
+			edge = self.edges[ edge ].next;
+
+			if edge == cross_edge {
+				break;
+			}
+		}
+	}
+
+	// Creates a face with the vertices 'eye_vertex.point', 'horizon_edge.tail' and 'horizon_edge.head' in CCW order
+	fn add_adjoining_face(&mut self, eye_vertex: usize, horizon_edge: usize) -> usize {
+		// all the half edges are created in ccw order thus the face is always pointing outside the hull
+
+		let tail = self.tail(horizon_edge);
+		let head = self.head(horizon_edge);
+		let face = self.create_face(eye_vertex, tail, head);
+
+		self.faces.push(face);
+
+		// join face.get_edge( - 1 ) with the horizon's opposite edge face.get_edge( - 1 ) = face.get_edge( 2 )
+
+		if let Some(twin) = self.edges[ horizon_edge ].twin {
+			let edge = self.get_edge(face, - 1);
+			self.set_twin(edge, twin);
+		}
+
+		self.get_edge(face, 0) // the half edge whose vertex is the eye_vertex
+	}
+
+	//  Adds 'horizon.len()' faces to the hull, each face will be linked with the
+	//  horizon opposite face and the face on the left/right
+	fn add_new_faces(&mut self, eye_vertex: usize, horizon: &[usize]) {
+		self.new_faces.clear();
+
+		let mut first_side_edge = None;
+		let mut previous_side_edge = None;
+
+		for &horizon_edge in horizon {
+			// returns the right side edge
+
+			let side_edge = self.add_adjoining_face(eye_vertex, horizon_edge);
+
+			match (first_side_edge, previous_side_edge) {
+				(None, _) => first_side_edge = Some(side_edge),
+				(Some(_), Some(previous)) => {
+					// joins face.get_edge( 1 ) with previous_face.get_edge( 0 )
+					let next = self.edges[ side_edge ].next;
+					self.set_twin(next, previous);
+				},
+				_ => {}
+			}
+
+			self.new_faces.push(self.edges[ side_edge ].face);
+			previous_side_edge = Some(side_edge);
+		}
+
+		// perform final join of new faces
+
+		if let (Some(first), Some(previous)) = (first_side_edge, previous_side_edge) {
+			let next = self.edges[ first ].next;
+			self.set_twin(next, previous);
+		}
+	}
+
+	// Adds a vertex to the hull
+	fn add_vertex_to_hull(&mut self, eye_vertex: usize) {
+		let mut horizon = vec![];
+
+		self.unassigned.clear();
+
+		let eye_face = match self.vertices[ eye_vertex ].face {
+			Some(face) => face,
+			None => return,
+		};
+
+		// remove 'eye_vertex' from 'eye_vertex.face' so that it can't be added to the 'unassigned' vertex list
+
+		self.remove_vertex_from_face(eye_vertex, eye_face);
+
+		let eye_point = self.vertices[ eye_vertex ].point;
+		self.compute_horizon(&eye_point, None, eye_face, &mut horizon);
+
+		self.add_new_faces(eye_vertex, &horizon);
+
+		// reassign 'unassigned' vertices to the new faces
+
+		self.resolve_unassigned_points();
+	}
+
+	fn cleanup(&mut self) {
+		self.assigned.clear();
+		self.unassigned.clear();
+		self.new_faces.clear();
+	}
+
+	fn compute(&mut self) {
+		if !self.compute_initial_hull() {
+			self.faces.clear();
+			self.cleanup();
+			return;
+		}
+
+		// add all available vertices gradually to the hull
+
+		while let Some(vertex) = self.next_vertex_to_add() {
+			self.add_vertex_to_hull(vertex);
+		}
+
+		self.reindex_faces();
+
+		self.cleanup();
+	}
+}
+
+impl Default for ConvexHull {
+	fn default() -> ConvexHull {
+		ConvexHull::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn cube_with_interior_points() {
+		let mut points = vec![];
+
+		// interior points first, so the hull has to drop them rather than never see them
+		for &x in &[ - 0.5, 0.0, 0.5 ] {
+			for &y in &[ - 0.5, 0.0, 0.5 ] {
+				for &z in &[ - 0.5, 0.0, 0.5 ] {
+					points.push(Vector3 { x, y, z });
+				}
+			}
+		}
+
+		for &x in &[ - 1.0, 1.0 ] {
+			for &y in &[ - 1.0, 1.0 ] {
+				for &z in &[ - 1.0, 1.0 ] {
+					points.push(Vector3 { x, y, z });
+				}
+			}
+		}
+
+		points.push(Vector3 { x: 0.9, y: - 0.3, z: 0.7 });
+		points.push(Vector3 { x: - 0.99, y: 0.99, z: 0.0 });
+
+		let mut hull = ConvexHull::new();
+		hull.set_from_points(&points);
+
+		let vertices = hull.get_vertices();
+		assert_eq!(vertices.len(), 8);
+		for v in &vertices {
+			assert_eq!(v.x.abs(), 1.0);
+			assert_eq!(v.y.abs(), 1.0);
+			assert_eq!(v.z.abs(), 1.0);
+		}
+
+		let faces = hull.get_faces();
+		assert_eq!(faces.len(), 12);
+
+		let area: f32 = faces.iter().map(|face| face.get_area()).sum();
+		assert!(( area - 24.0 ).abs() < 1e-4);
+
+		// normals point away from the center
+		let mut normal = Vector3::new();
+		let mut midpoint = Vector3::new();
+		for face in &faces {
+			face.get_normal(&mut normal);
+			face.get_midpoint(&mut midpoint);
+			assert!(normal.dot(&midpoint) > 0.0);
+		}
+
+		assert!(hull.contains_point(&Vector3 { x: 0.5, y: 0.5, z: 0.5 }));
+		assert!(!hull.contains_point(&Vector3 { x: 1.5, y: 0.0, z: 0.0 }));
+	}
+}
@@ -0,0 +1,402 @@
+use super::vector2::Vector2;
+use super::vector3::Vector3;
+use super::math_static::clamp;
+#[cfg(not(feature = "std"))]
+use super::float::Float;
+
+// Packing helpers for quantized vertex data and HDR colors.
+
+// Converts to IEEE 754 binary16, rounding to nearest even. Values too large for a half
+// become infinity, values too small become (signed) zero, and NaN stays NaN.
+pub fn to_half_float(val: f32) -> u16 {
+	let x = val.to_bits();
+
+	let sign = ( ( x >> 16 ) & 0x8000 ) as u16;
+	let exponent = ( ( x >> 23 ) & 0xff ) as i32;
+	let mantissa = x & 0x007f_ffff;
+
+	// Inf and NaN, keep a quiet NaN bit so the payload can't collapse into Inf
+	if exponent == 0xff {
+		let nan = if mantissa != 0 { 0x0200 | ( mantissa >> 13 ) as u16 } else { 0 };
+		return sign | 0x7c00 | nan;
+	}
+
+	let half_exponent = exponent - 127 + 15;
+
+	// overflow
+	if half_exponent >= 0x1f {
+		return sign | 0x7c00;
+	}
+
+	if half_exponent <= 0 {
+		// below half of the smallest denormal, round to zero
+		if half_exponent < - 10 {
+			return sign;
+		}
+
+		// denormal: shift the mantissa, including the implicit leading bit, into place
+		let m = mantissa | 0x0080_0000;
+		let shift = ( 14 - half_exponent ) as u32;
+		let remainder = m & ( ( 1 << shift ) - 1 );
+		let halfway = 1 << ( shift - 1 );
+
+		let mut half = m >> shift;
+
+		if remainder > halfway || ( remainder == halfway && half & 1 != 0 ) {
+			half += 1;
+		}
+
+		return sign | half as u16;
+	}
+
+	let mut half = ( ( half_exponent as u32 ) << 10 ) | ( mantissa >> 13 );
+	let remainder = mantissa & 0x1fff;
+
+	// a carry out of the mantissa correctly bumps the exponent, up to Inf
+	if remainder > 0x1000 || ( remainder == 0x1000 && half & 1 != 0 ) {
+		half += 1;
+	}
+
+	sign | half as u16
+}
+
+pub fn from_half_float(val: u16) -> f32 {
+	let sign = ( val as u32 & 0x8000 ) << 16;
+	let exponent = ( val as u32 >> 10 ) & 0x1f;
+	let mantissa = val as u32 & 0x03ff;
+
+	if exponent == 0 {
+		// zero and denormals, mantissa * 2^-24
+		let magnitude = mantissa as f32 / 16_777_216.0;
+		return if sign != 0 { - magnitude } else { magnitude };
+	}
+
+	if exponent == 0x1f {
+		return f32::from_bits(sign | 0x7f80_0000 | ( mantissa << 13 ));
+	}
+
+	f32::from_bits(sign | ( ( exponent + 127 - 15 ) << 23 ) | ( mantissa << 13 ))
+}
+
+// The array helpers all take the packed array first, then the offset of the first element
+// in it, then the values to write or the target to read into.
+pub fn to_half_float_array(array: &mut [u16], offset: Option<usize>, values: &[f32]) {
+	let offset = offset.unwrap_or(0);
+
+	for (i, value) in values.iter().enumerate() {
+		array[ offset + i ] = to_half_float(*value);
+	}
+}
+
+pub fn from_half_float_array(array: &[u16], offset: Option<usize>, target: &mut [f32]) {
+	let offset = offset.unwrap_or(0);
+
+	for (i, value) in target.iter_mut().enumerate() {
+		*value = from_half_float(array[ offset + i ]);
+	}
+}
+
+// Normalized integers, see the OpenGL conversion rules: unorm maps [0, 1] to [0, max],
+// snorm maps [-1, 1] to [-max, max] and the most negative integer decodes to -1 as well.
+
+pub fn pack_unorm8(val: f32) -> u8 {
+	( clamp(val, 0.0, 1.0) * 255.0 ).round() as u8
+}
+
+pub fn unpack_unorm8(val: u8) -> f32 {
+	val as f32 / 255.0
+}
+
+pub fn pack_snorm8(val: f32) -> i8 {
+	( clamp(val, - 1.0, 1.0) * 127.0 ).round() as i8
+}
+
+pub fn unpack_snorm8(val: i8) -> f32 {
+	( val as f32 / 127.0 ).max(- 1.0)
+}
+
+pub fn pack_unorm16(val: f32) -> u16 {
+	( clamp(val, 0.0, 1.0) * 65535.0 ).round() as u16
+}
+
+pub fn unpack_unorm16(val: u16) -> f32 {
+	val as f32 / 65535.0
+}
+
+pub fn pack_snorm16(val: f32) -> i16 {
+	( clamp(val, - 1.0, 1.0) * 32767.0 ).round() as i16
+}
+
+pub fn unpack_snorm16(val: i16) -> f32 {
+	( val as f32 / 32767.0 ).max(- 1.0)
+}
+
+pub fn pack_unorm8_array(array: &mut [u8], offset: Option<usize>, values: &[f32]) {
+	let offset = offset.unwrap_or(0);
+
+	for (i, value) in values.iter().enumerate() {
+		array[ offset + i ] = pack_unorm8(*value);
+	}
+}
+
+pub fn unpack_unorm8_array(array: &[u8], offset: Option<usize>, target: &mut [f32]) {
+	let offset = offset.unwrap_or(0);
+
+	for (i, value) in target.iter_mut().enumerate() {
+		*value = unpack_unorm8(array[ offset + i ]);
+	}
+}
+
+pub fn pack_snorm8_array(array: &mut [i8], offset: Option<usize>, values: &[f32]) {
+	let offset = offset.unwrap_or(0);
+
+	for (i, value) in values.iter().enumerate() {
+		array[ offset + i ] = pack_snorm8(*value);
+	}
+}
+
+pub fn unpack_snorm8_array(array: &[i8], offset: Option<usize>, target: &mut [f32]) {
+	let offset = offset.unwrap_or(0);
+
+	for (i, value) in target.iter_mut().enumerate() {
+		*value = unpack_snorm8(array[ offset + i ]);
+	}
+}
+
+pub fn pack_unorm16_array(array: &mut [u16], offset: Option<usize>, values: &[f32]) {
+	let offset = offset.unwrap_or(0);
+
+	for (i, value) in values.iter().enumerate() {
+		array[ offset + i ] = pack_unorm16(*value);
+	}
+}
+
+pub fn unpack_unorm16_array(array: &[u16], offset: Option<usize>, target: &mut [f32]) {
+	let offset = offset.unwrap_or(0);
+
+	for (i, value) in target.iter_mut().enumerate() {
+		*value = unpack_unorm16(array[ offset + i ]);
+	}
+}
+
+pub fn pack_snorm16_array(array: &mut [i16], offset: Option<usize>, values: &[f32]) {
+	let offset = offset.unwrap_or(0);
+
+	for (i, value) in values.iter().enumerate() {
+		array[ offset + i ] = pack_snorm16(*value);
+	}
+}
+
+pub fn unpack_snorm16_array(array: &[i16], offset: Option<usize>, target: &mut [f32]) {
+	let offset = offset.unwrap_or(0);
+
+	for (i, value) in target.iter_mut().enumerate() {
+		*value = unpack_snorm16(array[ offset + i ]);
+	}
+}
+
+// Octahedral normal encoding, see "A Survey of Efficient Representations for Independent Unit Vectors"
+// by Cigolle et al. The unit sphere is folded onto the [-1, 1] square.
+
+fn sign_not_zero(v: f32) -> f32 {
+	if v >= 0.0 { 1.0 } else { - 1.0 }
+}
+
+pub fn oct_encode(normal: &Vector3, target: &mut Vector2) {
+	let l1_norm = normal.x.abs() + normal.y.abs() + normal.z.abs();
+
+	if l1_norm == 0.0 {
+		target.x = 0.0;
+		target.y = 0.0;
+		return;
+	}
+
+	let x = normal.x / l1_norm;
+	let y = normal.y / l1_norm;
+
+	if normal.z < 0.0 {
+		// fold the lower hemisphere over the diagonals
+		target.x = ( 1.0 - y.abs() ) * sign_not_zero(x);
+		target.y = ( 1.0 - x.abs() ) * sign_not_zero(y);
+	} else {
+		target.x = x;
+		target.y = y;
+	}
+}
+
+pub fn oct_decode(encoded: &Vector2, target: &mut Vector3) {
+	let mut x = encoded.x;
+	let mut y = encoded.y;
+	let z = 1.0 - x.abs() - y.abs();
+
+	if z < 0.0 {
+		let folded_x = ( 1.0 - y.abs() ) * sign_not_zero(x);
+		let folded_y = ( 1.0 - x.abs() ) * sign_not_zero(y);
+		x = folded_x;
+		y = folded_y;
+	}
+
+	target.set(x, y, z);
+	target.normalize();
+}
+
+// Writes the octahedral encoding of a unit vector as two snorm16 values.
+pub fn oct_encode_to_array(array: &mut [i16], offset: Option<usize>, normal: &Vector3) {
+	let offset = offset.unwrap_or(0);
+	let mut encoded = Vector2::new();
+	oct_encode(normal, &mut encoded);
+
+	array[ offset ] = pack_snorm16(encoded.x);
+	array[ offset + 1 ] = pack_snorm16(encoded.y);
+}
+
+pub fn oct_decode_from_array(array: &[i16], offset: Option<usize>, target: &mut Vector3) {
+	let offset = offset.unwrap_or(0);
+	let encoded = Vector2 {
+		x: unpack_snorm16(array[ offset ]),
+		y: unpack_snorm16(array[ offset + 1 ]),
+	};
+
+	oct_decode(&encoded, target);
+}
+
+// Radiance RGBE: a shared exponent in the alpha channel, see Greg Ward's "Real Pixels" in Graphics Gems II.
+
+pub fn encode_rgbe(r: f32, g: f32, b: f32) -> [u8; 4] {
+	let max = r.max(g).max(b);
+
+	if max <= 1e-32 {
+		return [ 0, 0, 0, 0 ];
+	}
+
+	// frexp: max = mantissa * 2^exponent with mantissa in [0.5, 1)
+	let mut exponent = max.log2().floor() as i32 + 1;
+	let mut mantissa = max / 2.0f32.powi(exponent);
+
+	if mantissa >= 1.0 {
+		mantissa *= 0.5;
+		exponent += 1;
+	} else if mantissa < 0.5 {
+		mantissa *= 2.0;
+		exponent -= 1;
+	}
+
+	let scale = mantissa * 256.0 / max;
+
+	[
+		( r.max(0.0) * scale ).min(255.0) as u8,
+		( g.max(0.0) * scale ).min(255.0) as u8,
+		( b.max(0.0) * scale ).min(255.0) as u8,
+		clamp(( exponent + 128 ) as f32, 0.0, 255.0) as u8,
+	]
+}
+
+pub fn decode_rgbe(rgbe: &[u8]) -> [f32; 3] {
+	if rgbe[ 3 ] == 0 {
+		return [ 0.0, 0.0, 0.0 ];
+	}
+
+	let f = 2.0f32.powi(rgbe[ 3 ] as i32 - ( 128 + 8 ));
+
+	[
+		( rgbe[ 0 ] as f32 + 0.5 ) * f,
+		( rgbe[ 1 ] as f32 + 0.5 ) * f,
+		( rgbe[ 2 ] as f32 + 0.5 ) * f,
+	]
+}
+
+// RGBM: a shared multiplier in the alpha channel, relative to max_range.
+
+pub fn encode_rgbm(r: f32, g: f32, b: f32, max_range: f32) -> [u8; 4] {
+	let max_rgb = r.max(g).max(b).max(1e-6);
+	let mut m = clamp(max_rgb / max_range, 0.0, 1.0);
+	m = ( m * 255.0 ).ceil() / 255.0;
+
+	let scale = 1.0 / ( m * max_range );
+
+	[
+		pack_unorm8(r * scale),
+		pack_unorm8(g * scale),
+		pack_unorm8(b * scale),
+		pack_unorm8(m),
+	]
+}
+
+pub fn decode_rgbm(rgbm: &[u8], max_range: f32) -> [f32; 3] {
+	let m = unpack_unorm8(rgbm[ 3 ]) * max_range;
+
+	[
+		unpack_unorm8(rgbm[ 0 ]) * m,
+		unpack_unorm8(rgbm[ 1 ]) * m,
+		unpack_unorm8(rgbm[ 2 ]) * m,
+	]
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn half_float_round_trip() {
+		for half in 0..=0xffffu16 {
+			let value = from_half_float(half);
+
+			if value.is_nan() {
+				assert!(from_half_float(to_half_float(value)).is_nan());
+			} else {
+				assert_eq!(to_half_float(value), half, "{:#06x}", half);
+			}
+		}
+	}
+
+	#[test]
+	fn half_float_denormals() {
+		let smallest = 1.0 / 16_777_216.0;
+
+		assert_eq!(to_half_float(smallest), 0x0001);
+		assert_eq!(to_half_float(- smallest), 0x8001);
+		assert_eq!(from_half_float(0x0001), smallest);
+		assert_eq!(from_half_float(0x03ff), 1023.0 * smallest);
+
+		// halfway cases round to even
+		assert_eq!(to_half_float(smallest * 0.5), 0x0000);
+		assert_eq!(to_half_float(smallest * 1.5), 0x0002);
+		assert_eq!(to_half_float(smallest * 2.5), 0x0002);
+
+		// just past halfway rounds up, and the largest denormal rounds up into the normals
+		assert_eq!(to_half_float(smallest * 0.5000001), 0x0001);
+		assert_eq!(to_half_float(1023.5 * smallest), 0x0400);
+		assert_eq!(to_half_float(smallest * 0.25), 0x0000);
+	}
+
+	#[test]
+	fn array_helpers_write_and_read_at_the_offset() {
+		let values = [ 0.0, 0.5, 1.0 ];
+		let mut unpacked = [ 0.0f32; 3 ];
+
+		let mut half = [ 0u16; 4 ];
+		to_half_float_array(&mut half, Some(1), &values);
+		assert_eq!(half, [ 0, 0x0000, 0x3800, 0x3c00 ]);
+		from_half_float_array(&half, Some(1), &mut unpacked);
+		assert_eq!(unpacked, values);
+
+		let mut unorm8 = [ 0u8; 4 ];
+		pack_unorm8_array(&mut unorm8, Some(1), &values);
+		assert_eq!(unorm8, [ 0, 0, 128, 255 ]);
+		unpack_unorm8_array(&unorm8, Some(2), &mut unpacked[ .. 2 ]);
+		assert_eq!(unpacked[ 1 ], 1.0);
+
+		let mut snorm16 = [ 0i16; 3 ];
+		pack_snorm16_array(&mut snorm16, None, &[ - 1.0, 0.0, 1.0 ]);
+		assert_eq!(snorm16, [ - 32767, 0, 32767 ]);
+
+		let mut oct = [ 0i16; 3 ];
+		let normal = Vector3 { x: 0.0, y: 0.0, z: - 1.0 };
+		oct_encode_to_array(&mut oct, Some(1), &normal);
+		assert_eq!(oct[ 0 ], 0);
+
+		let mut decoded = Vector3::new();
+		oct_decode_from_array(&oct, Some(1), &mut decoded);
+		assert!(decoded.distance_to(&normal) < 1e-4);
+	}
+}
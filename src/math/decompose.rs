@@ -0,0 +1,261 @@
+use std::cmp::Ordering;
+use super::vector3::Vector3;
+use super::matrix3::Matrix3;
+#[cfg(not(feature = "std"))]
+use super::float::Float;
+
+// Decompositions of 3x3 matrices. Internally matrices are handled as row-major [ row ][ column ]
+// arrays; inputs and outputs are the usual column-major Matrix3.
+
+type Rows = [[f32; 3]; 3];
+
+const MAX_SWEEPS: usize = 50;
+
+fn to_rows(m: &Matrix3) -> Rows {
+	let e = &m.elements;
+	[
+		[ e[ 0 ], e[ 3 ], e[ 6 ] ],
+		[ e[ 1 ], e[ 4 ], e[ 7 ] ],
+		[ e[ 2 ], e[ 5 ], e[ 8 ] ],
+	]
+}
+
+fn from_rows(r: &Rows, target: &mut Matrix3) {
+	target.set(
+		r[ 0 ][ 0 ], r[ 0 ][ 1 ], r[ 0 ][ 2 ],
+		r[ 1 ][ 0 ], r[ 1 ][ 1 ], r[ 1 ][ 2 ],
+		r[ 2 ][ 0 ], r[ 2 ][ 1 ], r[ 2 ][ 2 ]
+	);
+}
+
+fn identity() -> Rows {
+	[
+		[ 1.0, 0.0, 0.0 ],
+		[ 0.0, 1.0, 0.0 ],
+		[ 0.0, 0.0, 1.0 ],
+	]
+}
+
+fn column(r: &Rows, i: usize) -> Vector3 {
+	Vector3 {
+		x: r[ 0 ][ i ],
+		y: r[ 1 ][ i ],
+		z: r[ 2 ][ i ],
+	}
+}
+
+fn set_column(r: &mut Rows, i: usize, v: &Vector3) {
+	r[ 0 ][ i ] = v.x;
+	r[ 1 ][ i ] = v.y;
+	r[ 2 ][ i ] = v.z;
+}
+
+fn negate_column(r: &mut Rows, i: usize) {
+	for row in r.iter_mut() {
+		row[ i ] = - row[ i ];
+	}
+}
+
+// Applies the plane rotation [ c s; -s c ] to columns p and q.
+fn rotate_columns(r: &mut Rows, p: usize, q: usize, c: f32, s: f32) {
+	for row in r.iter_mut() {
+		let rp = row[ p ];
+		let rq = row[ q ];
+		row[ p ] = c * rp - s * rq;
+		row[ q ] = s * rp + c * rq;
+	}
+}
+
+fn determinant(r: &Rows) -> f32 {
+	r[ 0 ][ 0 ] * ( r[ 1 ][ 1 ] * r[ 2 ][ 2 ] - r[ 1 ][ 2 ] * r[ 2 ][ 1 ] ) -
+		r[ 0 ][ 1 ] * ( r[ 1 ][ 0 ] * r[ 2 ][ 2 ] - r[ 1 ][ 2 ] * r[ 2 ][ 0 ] ) +
+		r[ 0 ][ 2 ] * ( r[ 1 ][ 0 ] * r[ 2 ][ 1 ] - r[ 1 ][ 1 ] * r[ 2 ][ 0 ] )
+}
+
+// Unit vector orthogonal to the first `count` (orthonormal) vectors of `basis`, built from
+// whichever coordinate axis survives the projection best.
+fn complete_basis(basis: &[Vector3], count: usize) -> Vector3 {
+	let axes = [
+		Vector3 { x: 1.0, y: 0.0, z: 0.0 },
+		Vector3 { x: 0.0, y: 1.0, z: 0.0 },
+		Vector3 { x: 0.0, y: 0.0, z: 1.0 },
+	];
+
+	let mut best = axes[ 0 ];
+	let mut best_length = - 1.0;
+
+	for axis in &axes {
+		let mut v = *axis;
+
+		for b in &basis[ ..count ] {
+			let d = v.dot(b);
+			v.add_scaled_vector(b, - d);
+		}
+
+		let length = v.length();
+
+		if length > best_length {
+			best_length = length;
+			best = v;
+		}
+	}
+
+	best.divide_scalar(best_length);
+	best
+}
+
+// Singular value decomposition m = u * diag( sigma ) * v^T by one-sided Jacobi rotations.
+// Singular values are non-negative and sorted in descending order. u and v are orthogonal,
+// v is always a proper rotation, so det( u ) carries the sign of det( m ).
+pub fn svd(m: &Matrix3, u: &mut Matrix3, sigma: &mut Vector3, v: &mut Matrix3) {
+	let mut a = to_rows(m);
+	let mut rv = identity();
+
+	for _ in 0..MAX_SWEEPS {
+		let mut rotated = false;
+
+		for &(p, q) in &[ (0, 1), (0, 2), (1, 2) ] {
+			let mut alpha = 0.0;
+			let mut beta = 0.0;
+			let mut gamma = 0.0;
+
+			for row in &a {
+				alpha += row[ p ] * row[ p ];
+				beta += row[ q ] * row[ q ];
+				gamma += row[ p ] * row[ q ];
+			}
+
+			// columns p and q are already orthogonal
+			if gamma == 0.0 || gamma.abs() <= f32::EPSILON * ( alpha * beta ).sqrt() {
+				continue;
+			}
+
+			rotated = true;
+
+			let zeta = ( beta - alpha ) / ( 2.0 * gamma );
+			let t = zeta.signum() / ( zeta.abs() + ( zeta * zeta + 1.0 ).sqrt() );
+			let c = 1.0 / ( t * t + 1.0 ).sqrt();
+			let s = c * t;
+
+			rotate_columns(&mut a, p, q, c, s);
+			rotate_columns(&mut rv, p, q, c, s);
+		}
+
+		if !rotated {
+			break;
+		}
+	}
+
+	// the singular values are the lengths of the orthogonalized columns
+	let lengths = [ column(&a, 0).length(), column(&a, 1).length(), column(&a, 2).length() ];
+
+	let mut order = [ 0, 1, 2 ];
+	order.sort_by(|&i, &j| lengths[ j ].partial_cmp(&lengths[ i ]).unwrap_or(Ordering::Equal));
+
+	let tolerance = lengths[ order[ 0 ] ] * 3.0 * f32::EPSILON;
+
+	let mut ru = identity();
+	let mut sorted_v = identity();
+	let mut basis = [ Vector3::new(); 3 ];
+	let mut s = [ 0.0f32; 3 ];
+
+	for (i, &o) in order.iter().enumerate() {
+		set_column(&mut sorted_v, i, &column(&rv, o));
+
+		if lengths[ o ] > tolerance {
+			s[ i ] = lengths[ o ];
+			basis[ i ] = column(&a, o);
+			basis[ i ].divide_scalar(lengths[ o ]);
+		} else {
+			// rank deficient, any orthonormal completion of u works
+			s[ i ] = 0.0;
+			basis[ i ] = complete_basis(&basis, i);
+		}
+
+		set_column(&mut ru, i, &basis[ i ]);
+	}
+
+	if determinant(&sorted_v) < 0.0 {
+		negate_column(&mut sorted_v, 2);
+		negate_column(&mut ru, 2);
+	}
+
+	sigma.set(s[ 0 ], s[ 1 ], s[ 2 ]);
+	from_rows(&ru, u);
+	from_rows(&sorted_v, v);
+}
+
+// Polar decomposition m = rotation * stretch, with rotation a proper rotation and stretch symmetric.
+// When det( m ) < 0 the reflection ends up in stretch as a negative eigenvalue along its weakest axis.
+pub fn polar(m: &Matrix3, rotation: &mut Matrix3, stretch: &mut Matrix3) {
+	let mut u = Matrix3::new();
+	let mut sigma = Vector3::new();
+	let mut v = Matrix3::new();
+	svd(m, &mut u, &mut sigma, &mut v);
+
+	let mut ru = to_rows(&u);
+	let rv = to_rows(&v);
+
+	if determinant(&ru) < 0.0 {
+		negate_column(&mut ru, 2);
+		sigma.z = - sigma.z;
+	}
+
+	let s = [ sigma.x, sigma.y, sigma.z ];
+	let mut r = [ [ 0.0f32; 3 ]; 3 ];
+	let mut p = [ [ 0.0f32; 3 ]; 3 ];
+
+	for i in 0..3 {
+		for j in 0..3 {
+			for k in 0..3 {
+				// rotation = u * v^T, stretch = v * diag( sigma ) * v^T
+				r[ i ][ j ] += ru[ i ][ k ] * rv[ j ][ k ];
+				p[ i ][ j ] += rv[ i ][ k ] * s[ k ] * rv[ j ][ k ];
+			}
+		}
+	}
+
+	from_rows(&r, rotation);
+	from_rows(&p, stretch);
+}
+
+// QR decomposition m = q * r by modified Gram-Schmidt. q is orthogonal and r is upper
+// triangular with a non-negative diagonal; columns that are dependent on the previous ones
+// get a zero on the diagonal and an arbitrary orthonormal completion in q.
+pub fn qr(m: &Matrix3, q: &mut Matrix3, r: &mut Matrix3) {
+	let a = to_rows(m);
+	let mut rr = [ [ 0.0f32; 3 ]; 3 ];
+	let mut basis = [ Vector3::new(); 3 ];
+
+	let scale = column(&a, 0).length().max(column(&a, 1).length()).max(column(&a, 2).length());
+	let tolerance = scale * 3.0 * f32::EPSILON;
+
+	for j in 0..3 {
+		let mut v = column(&a, j);
+
+		for i in 0..j {
+			rr[ i ][ j ] = basis[ i ].dot(&v);
+			v.add_scaled_vector(&basis[ i ], - rr[ i ][ j ]);
+		}
+
+		let length = v.length();
+
+		if length > tolerance {
+			rr[ j ][ j ] = length;
+			v.divide_scalar(length);
+			basis[ j ] = v;
+		} else {
+			rr[ j ][ j ] = 0.0;
+			basis[ j ] = complete_basis(&basis, j);
+		}
+	}
+
+	q.make_basis(&basis[ 0 ], &basis[ 1 ], &basis[ 2 ]);
+	from_rows(&rr, r);
+}
+
+// Eigen-decomposition m = vectors * diag( values ) * vectors^T of a symmetric matrix, here
+// next to the other decompositions. See Matrix3::symmetric_eigen for the details.
+pub fn symmetric_eigen(m: &Matrix3, values: &mut Vector3, vectors: &mut Matrix3) {
+	m.symmetric_eigen(values, vectors);
+}
@@ -3,6 +3,8 @@ use super::vector3::Vector3;
 use super::quaternion::Quaternion;
 use super::math_static::clamp;
 use std::mem;
+#[cfg(not(feature = "std"))]
+use super::float::Float;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum RotationOrders {
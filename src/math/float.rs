@@ -0,0 +1,89 @@
+extern crate libm;
+
+// The f32 functions that live in std rather than core, backed by libm for no_std builds.
+// Math modules import this trait only when the std feature is off, so with std the
+// inherent methods are used as before.
+pub trait Float {
+	fn sqrt(self) -> Self;
+	fn sin(self) -> Self;
+	fn cos(self) -> Self;
+	fn tan(self) -> Self;
+	fn asin(self) -> Self;
+	fn acos(self) -> Self;
+	fn atan2(self, other: Self) -> Self;
+	fn floor(self) -> Self;
+	fn ceil(self) -> Self;
+	fn round(self) -> Self;
+	fn trunc(self) -> Self;
+	fn ln(self) -> Self;
+	fn log2(self) -> Self;
+	fn exp(self) -> Self;
+	fn powi(self, n: i32) -> Self;
+	fn powf(self, n: Self) -> Self;
+}
+
+impl Float for f32 {
+	fn sqrt(self) -> f32 {
+		libm::sqrtf(self)
+	}
+
+	fn sin(self) -> f32 {
+		libm::sinf(self)
+	}
+
+	fn cos(self) -> f32 {
+		libm::cosf(self)
+	}
+
+	fn tan(self) -> f32 {
+		libm::tanf(self)
+	}
+
+	fn asin(self) -> f32 {
+		libm::asinf(self)
+	}
+
+	fn acos(self) -> f32 {
+		libm::acosf(self)
+	}
+
+	fn atan2(self, other: f32) -> f32 {
+		libm::atan2f(self, other)
+	}
+
+	fn floor(self) -> f32 {
+		libm::floorf(self)
+	}
+
+	fn ceil(self) -> f32 {
+		libm::ceilf(self)
+	}
+
+	fn round(self) -> f32 {
+		libm::roundf(self)
+	}
+
+	fn trunc(self) -> f32 {
+		libm::truncf(self)
+	}
+
+	fn ln(self) -> f32 {
+		libm::logf(self)
+	}
+
+	fn log2(self) -> f32 {
+		libm::log2f(self)
+	}
+
+	fn exp(self) -> f32 {
+		libm::expf(self)
+	}
+
+	fn powi(self, n: i32) -> f32 {
+		libm::powf(self, n as f32)
+	}
+
+	fn powf(self, n: f32) -> f32 {
+		libm::powf(self, n)
+	}
+}
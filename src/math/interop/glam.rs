@@ -0,0 +1,157 @@
+extern crate glam;
+
+use super::super::vector2::Vector2;
+use super::super::vector3::Vector3;
+use super::super::quaternion::Quaternion;
+use super::super::matrix3::Matrix3;
+use super::super::matrix4::Matrix4;
+
+impl From<glam::Vec2> for Vector2 {
+	fn from(v: glam::Vec2) -> Vector2 {
+		Vector2 {
+			x: v.x,
+			y: v.y,
+		}
+	}
+}
+
+impl From<Vector2> for glam::Vec2 {
+	fn from(v: Vector2) -> glam::Vec2 {
+		glam::Vec2::new(v.x, v.y)
+	}
+}
+
+impl From<glam::Vec3> for Vector3 {
+	fn from(v: glam::Vec3) -> Vector3 {
+		Vector3 {
+			x: v.x,
+			y: v.y,
+			z: v.z,
+		}
+	}
+}
+
+impl From<Vector3> for glam::Vec3 {
+	fn from(v: Vector3) -> glam::Vec3 {
+		glam::Vec3::new(v.x, v.y, v.z)
+	}
+}
+
+impl From<glam::Vec3A> for Vector3 {
+	fn from(v: glam::Vec3A) -> Vector3 {
+		Vector3 {
+			x: v.x,
+			y: v.y,
+			z: v.z,
+		}
+	}
+}
+
+impl From<Vector3> for glam::Vec3A {
+	fn from(v: Vector3) -> glam::Vec3A {
+		glam::Vec3A::new(v.x, v.y, v.z)
+	}
+}
+
+impl From<glam::Quat> for Quaternion {
+	fn from(q: glam::Quat) -> Quaternion {
+		Quaternion {
+			x: q.x,
+			y: q.y,
+			z: q.z,
+			w: q.w,
+		}
+	}
+}
+
+impl From<Quaternion> for glam::Quat {
+	fn from(q: Quaternion) -> glam::Quat {
+		glam::Quat::from_xyzw(q.x, q.y, q.z, q.w)
+	}
+}
+
+impl From<glam::Mat3> for Matrix3 {
+	fn from(m: glam::Mat3) -> Matrix3 {
+		Matrix3 {
+			elements: m.to_cols_array(),
+		}
+	}
+}
+
+impl From<Matrix3> for glam::Mat3 {
+	fn from(m: Matrix3) -> glam::Mat3 {
+		glam::Mat3::from_cols_array(&m.elements)
+	}
+}
+
+impl From<glam::Mat4> for Matrix4 {
+	fn from(m: glam::Mat4) -> Matrix4 {
+		Matrix4 {
+			elements: m.to_cols_array(),
+		}
+	}
+}
+
+impl From<Matrix4> for glam::Mat4 {
+	fn from(m: Matrix4) -> glam::Mat4 {
+		glam::Mat4::from_cols_array(&m.elements)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::glam;
+	use super::super::super::vector3::Vector3;
+	use super::super::super::quaternion::Quaternion;
+	use super::super::super::matrix4::Matrix4;
+	use super::super::test_utils::{position, rotation, scale, compose, assert_close, matrix3_round_trip};
+
+	#[test]
+	fn compose_matches() {
+		let m = compose();
+		let g = glam::Mat4::from_scale_rotation_translation(scale().into(), rotation().into(), position().into());
+
+		assert_close(&m.elements, &g.to_cols_array());
+
+		let back: Matrix4 = g.into();
+		assert_close(&back.elements, &m.elements);
+	}
+
+	#[test]
+	fn decompose_matches() {
+		let g = glam::Mat4::from_scale_rotation_translation(scale().into(), rotation().into(), position().into());
+		let m: Matrix4 = g.into();
+
+		let mut p = Vector3::new();
+		let mut q = Quaternion::new();
+		let mut s = Vector3::new();
+		m.decompose(&mut p, &mut q, &mut s);
+
+		let (gs, gq, gp) = g.to_scale_rotation_translation();
+
+		assert_close(&[ p.x, p.y, p.z ], &gp.to_array());
+		assert_close(&[ s.x, s.y, s.z ], &gs.to_array());
+
+		// q and -q are the same rotation
+		let sign = if q.dot(&gq.into()) < 0.0 { - 1.0 } else { 1.0 };
+		assert_close(&[ q.x * sign, q.y * sign, q.z * sign, q.w * sign ], &gq.to_array());
+	}
+
+	#[test]
+	fn transforms_points_alike() {
+		let m = compose();
+		let g: glam::Mat4 = m.into();
+
+		let mut v = Vector3 { x: 0.3, y: 4.0, z: - 1.2 };
+		let gv = g.transform_point3(v.into());
+		v.apply_matrix4(&m);
+
+		assert_close(&[ v.x, v.y, v.z ], &gv.to_array());
+	}
+
+	#[test]
+	fn matrix3_columns() {
+		// glam indexes columns first
+		matrix3_round_trip(|g: &glam::Mat3, e01| assert_eq!(g.col(1).x, e01));
+	}
+}
@@ -0,0 +1,193 @@
+extern crate mint;
+
+use super::super::vector2::Vector2;
+use super::super::vector3::Vector3;
+use super::super::quaternion::Quaternion;
+use super::super::matrix3::Matrix3;
+use super::super::matrix4::Matrix4;
+
+impl From<mint::Vector2<f32>> for Vector2 {
+	fn from(v: mint::Vector2<f32>) -> Vector2 {
+		Vector2 {
+			x: v.x,
+			y: v.y,
+		}
+	}
+}
+
+impl From<Vector2> for mint::Vector2<f32> {
+	fn from(v: Vector2) -> mint::Vector2<f32> {
+		mint::Vector2 {
+			x: v.x,
+			y: v.y,
+		}
+	}
+}
+
+impl From<mint::Vector3<f32>> for Vector3 {
+	fn from(v: mint::Vector3<f32>) -> Vector3 {
+		Vector3 {
+			x: v.x,
+			y: v.y,
+			z: v.z,
+		}
+	}
+}
+
+impl From<Vector3> for mint::Vector3<f32> {
+	fn from(v: Vector3) -> mint::Vector3<f32> {
+		mint::Vector3 {
+			x: v.x,
+			y: v.y,
+			z: v.z,
+		}
+	}
+}
+
+impl From<mint::Point3<f32>> for Vector3 {
+	fn from(p: mint::Point3<f32>) -> Vector3 {
+		Vector3 {
+			x: p.x,
+			y: p.y,
+			z: p.z,
+		}
+	}
+}
+
+impl From<Vector3> for mint::Point3<f32> {
+	fn from(v: Vector3) -> mint::Point3<f32> {
+		mint::Point3 {
+			x: v.x,
+			y: v.y,
+			z: v.z,
+		}
+	}
+}
+
+impl From<mint::Quaternion<f32>> for Quaternion {
+	fn from(q: mint::Quaternion<f32>) -> Quaternion {
+		Quaternion {
+			x: q.v.x,
+			y: q.v.y,
+			z: q.v.z,
+			w: q.s,
+		}
+	}
+}
+
+impl From<Quaternion> for mint::Quaternion<f32> {
+	fn from(q: Quaternion) -> mint::Quaternion<f32> {
+		mint::Quaternion {
+			v: mint::Vector3 {
+				x: q.x,
+				y: q.y,
+				z: q.z,
+			},
+			s: q.w,
+		}
+	}
+}
+
+impl From<mint::ColumnMatrix3<f32>> for Matrix3 {
+	fn from(m: mint::ColumnMatrix3<f32>) -> Matrix3 {
+		Matrix3 {
+			elements: [
+				m.x.x, m.x.y, m.x.z,
+				m.y.x, m.y.y, m.y.z,
+				m.z.x, m.z.y, m.z.z,
+			],
+		}
+	}
+}
+
+impl From<Matrix3> for mint::ColumnMatrix3<f32> {
+	fn from(m: Matrix3) -> mint::ColumnMatrix3<f32> {
+		let e = m.elements;
+		mint::ColumnMatrix3 {
+			x: mint::Vector3 { x: e[ 0 ], y: e[ 1 ], z: e[ 2 ] },
+			y: mint::Vector3 { x: e[ 3 ], y: e[ 4 ], z: e[ 5 ] },
+			z: mint::Vector3 { x: e[ 6 ], y: e[ 7 ], z: e[ 8 ] },
+		}
+	}
+}
+
+impl From<mint::ColumnMatrix4<f32>> for Matrix4 {
+	fn from(m: mint::ColumnMatrix4<f32>) -> Matrix4 {
+		Matrix4 {
+			elements: [
+				m.x.x, m.x.y, m.x.z, m.x.w,
+				m.y.x, m.y.y, m.y.z, m.y.w,
+				m.z.x, m.z.y, m.z.z, m.z.w,
+				m.w.x, m.w.y, m.w.z, m.w.w,
+			],
+		}
+	}
+}
+
+impl From<Matrix4> for mint::ColumnMatrix4<f32> {
+	fn from(m: Matrix4) -> mint::ColumnMatrix4<f32> {
+		let e = m.elements;
+		mint::ColumnMatrix4 {
+			x: mint::Vector4 { x: e[ 0 ], y: e[ 1 ], z: e[ 2 ], w: e[ 3 ] },
+			y: mint::Vector4 { x: e[ 4 ], y: e[ 5 ], z: e[ 6 ], w: e[ 7 ] },
+			z: mint::Vector4 { x: e[ 8 ], y: e[ 9 ], z: e[ 10 ], w: e[ 11 ] },
+			w: mint::Vector4 { x: e[ 12 ], y: e[ 13 ], z: e[ 14 ], w: e[ 15 ] },
+		}
+	}
+}
+
+// mint's row-major matrices are accepted too, and transposed on the way in.
+
+impl From<mint::RowMatrix3<f32>> for Matrix3 {
+	fn from(m: mint::RowMatrix3<f32>) -> Matrix3 {
+		Matrix3::from(mint::ColumnMatrix3::from(m))
+	}
+}
+
+impl From<mint::RowMatrix4<f32>> for Matrix4 {
+	fn from(m: mint::RowMatrix4<f32>) -> Matrix4 {
+		Matrix4::from(mint::ColumnMatrix4::from(m))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::mint;
+	use super::super::super::quaternion::Quaternion;
+	use super::super::super::matrix4::Matrix4;
+	use super::super::test_utils::{compose, matrix3_round_trip};
+
+	#[test]
+	fn matrix4_columns() {
+		let m = compose();
+		let c: mint::ColumnMatrix4<f32> = m.into();
+
+		// the translation is the last column
+		assert_eq!(c.w.x, 1.0);
+		assert_eq!(c.w.y, - 2.0);
+		assert_eq!(c.w.z, 3.0);
+		assert_eq!(c.w.w, 1.0);
+
+		let back: Matrix4 = c.into();
+		assert_eq!(back.elements, m.elements);
+
+		let r: mint::RowMatrix4<f32> = c.into();
+		let from_rows: Matrix4 = r.into();
+		assert_eq!(from_rows.elements, m.elements);
+	}
+
+	#[test]
+	fn matrix3_columns() {
+		matrix3_round_trip(|c: &mint::ColumnMatrix3<f32>, e01| assert_eq!(c.y.x, e01));
+	}
+
+	#[test]
+	fn quaternion_round_trip() {
+		let q = Quaternion { x: 0.1, y: 0.2, z: 0.3, w: 0.9 };
+		let m: mint::Quaternion<f32> = q.into();
+		assert_eq!(m.s, 0.9);
+
+		let back: Quaternion = m.into();
+		assert!(back.equals(&q));
+	}
+}
@@ -0,0 +1,14 @@
+// Conversions to and from the math types of other crates, each behind the cargo feature of the same name.
+// Matrices are column-major on both sides, so elements map one to one.
+
+#[cfg(feature = "mint")]
+pub mod mint;
+#[cfg(feature = "glam")]
+pub mod glam;
+#[cfg(feature = "nalgebra")]
+pub mod nalgebra;
+
+// each feature's tests use only part of it
+#[cfg(all(test, any(feature = "mint", feature = "glam", feature = "nalgebra")))]
+#[allow(dead_code)]
+mod test_utils;
\ No newline at end of file
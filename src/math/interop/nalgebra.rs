@@ -0,0 +1,191 @@
+extern crate nalgebra;
+
+use super::super::vector2::Vector2;
+use super::super::vector3::Vector3;
+use super::super::quaternion::Quaternion;
+use super::super::matrix3::Matrix3;
+use super::super::matrix4::Matrix4;
+
+impl From<nalgebra::Vector2<f32>> for Vector2 {
+	fn from(v: nalgebra::Vector2<f32>) -> Vector2 {
+		Vector2 {
+			x: v.x,
+			y: v.y,
+		}
+	}
+}
+
+impl From<Vector2> for nalgebra::Vector2<f32> {
+	fn from(v: Vector2) -> nalgebra::Vector2<f32> {
+		nalgebra::Vector2::new(v.x, v.y)
+	}
+}
+
+impl From<nalgebra::Vector3<f32>> for Vector3 {
+	fn from(v: nalgebra::Vector3<f32>) -> Vector3 {
+		Vector3 {
+			x: v.x,
+			y: v.y,
+			z: v.z,
+		}
+	}
+}
+
+impl From<Vector3> for nalgebra::Vector3<f32> {
+	fn from(v: Vector3) -> nalgebra::Vector3<f32> {
+		nalgebra::Vector3::new(v.x, v.y, v.z)
+	}
+}
+
+impl From<nalgebra::Point3<f32>> for Vector3 {
+	fn from(p: nalgebra::Point3<f32>) -> Vector3 {
+		Vector3 {
+			x: p.x,
+			y: p.y,
+			z: p.z,
+		}
+	}
+}
+
+impl From<Vector3> for nalgebra::Point3<f32> {
+	fn from(v: Vector3) -> nalgebra::Point3<f32> {
+		nalgebra::Point3::new(v.x, v.y, v.z)
+	}
+}
+
+// nalgebra stores quaternions as [ i, j, k, w ] but takes w first in its constructor.
+
+impl From<nalgebra::Quaternion<f32>> for Quaternion {
+	fn from(q: nalgebra::Quaternion<f32>) -> Quaternion {
+		Quaternion {
+			x: q.i,
+			y: q.j,
+			z: q.k,
+			w: q.w,
+		}
+	}
+}
+
+impl From<Quaternion> for nalgebra::Quaternion<f32> {
+	fn from(q: Quaternion) -> nalgebra::Quaternion<f32> {
+		nalgebra::Quaternion::new(q.w, q.x, q.y, q.z)
+	}
+}
+
+impl From<nalgebra::UnitQuaternion<f32>> for Quaternion {
+	fn from(q: nalgebra::UnitQuaternion<f32>) -> Quaternion {
+		Quaternion::from(q.into_inner())
+	}
+}
+
+// Normalizes, since nothing guarantees a Quaternion is a unit quaternion.
+impl From<Quaternion> for nalgebra::UnitQuaternion<f32> {
+	fn from(q: Quaternion) -> nalgebra::UnitQuaternion<f32> {
+		nalgebra::UnitQuaternion::from_quaternion(q.into())
+	}
+}
+
+impl From<nalgebra::Matrix3<f32>> for Matrix3 {
+	fn from(m: nalgebra::Matrix3<f32>) -> Matrix3 {
+		let mut elements = [ 0.0; 9 ];
+		elements.copy_from_slice(m.as_slice());
+		Matrix3 {
+			elements,
+		}
+	}
+}
+
+impl From<Matrix3> for nalgebra::Matrix3<f32> {
+	fn from(m: Matrix3) -> nalgebra::Matrix3<f32> {
+		nalgebra::Matrix3::from_column_slice(&m.elements)
+	}
+}
+
+impl From<nalgebra::Matrix4<f32>> for Matrix4 {
+	fn from(m: nalgebra::Matrix4<f32>) -> Matrix4 {
+		let mut elements = [ 0.0; 16 ];
+		elements.copy_from_slice(m.as_slice());
+		Matrix4 {
+			elements,
+		}
+	}
+}
+
+impl From<Matrix4> for nalgebra::Matrix4<f32> {
+	fn from(m: Matrix4) -> nalgebra::Matrix4<f32> {
+		nalgebra::Matrix4::from_column_slice(&m.elements)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::nalgebra;
+	use super::super::super::vector3::Vector3;
+	use super::super::super::quaternion::Quaternion;
+	use super::super::super::matrix4::Matrix4;
+	use super::super::test_utils::{position, rotation, scale, compose, assert_close, matrix3_round_trip};
+
+	// translation * rotation * scale, built the nalgebra way
+	fn nalgebra_compose() -> nalgebra::Matrix4<f32> {
+		let t = nalgebra::Translation3::from(nalgebra::Vector3::from(position()));
+		let r: nalgebra::UnitQuaternion<f32> = rotation().into();
+		let s = nalgebra::Matrix4::new_nonuniform_scaling(&scale().into());
+
+		t.to_homogeneous() * r.to_homogeneous() * s
+	}
+
+	#[test]
+	fn compose_matches() {
+		let m = compose();
+		let n = nalgebra_compose();
+		assert_close(&m.elements, n.as_slice());
+
+		let back: Matrix4 = n.into();
+		assert_close(&back.elements, &m.elements);
+	}
+
+	#[test]
+	fn decompose_matches() {
+		let m: Matrix4 = nalgebra_compose().into();
+
+		let mut p = Vector3::new();
+		let mut q = Quaternion::new();
+		let mut s = Vector3::new();
+		m.decompose(&mut p, &mut q, &mut s);
+
+		assert_close(&[ p.x, p.y, p.z ], &[ position().x, position().y, position().z ]);
+		assert_close(&[ s.x, s.y, s.z ], &[ scale().x, scale().y, scale().z ]);
+
+		let expected: nalgebra::UnitQuaternion<f32> = rotation().into();
+		let actual: nalgebra::UnitQuaternion<f32> = q.into();
+		assert!(expected.angle_to(&actual) < 1e-3);
+	}
+
+	#[test]
+	fn transforms_points_alike() {
+		let m = compose();
+		let n: nalgebra::Matrix4<f32> = m.into();
+
+		let mut v = Vector3 { x: 0.3, y: 4.0, z: - 1.2 };
+		let nv = n.transform_point(&v.into());
+		v.apply_matrix4(&m);
+
+		assert_close(&[ v.x, v.y, v.z ], &[ nv.x, nv.y, nv.z ]);
+	}
+
+	#[test]
+	fn matrix3_indexing() {
+		// nalgebra indexes ( row, column )
+		matrix3_round_trip(|n: &nalgebra::Matrix3<f32>, e01| assert_eq!(n[ (0, 1) ], e01));
+	}
+
+	#[test]
+	fn quaternion_round_trip() {
+		let q = rotation();
+		let n: nalgebra::Quaternion<f32> = q.into();
+		assert_eq!(n.w, q.w);
+
+		let back: Quaternion = n.into();
+		assert!(back.equals(&q));
+	}
+}
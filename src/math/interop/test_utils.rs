@@ -0,0 +1,48 @@
+use super::super::vector3::Vector3;
+use super::super::quaternion::Quaternion;
+use super::super::matrix3::Matrix3;
+use super::super::matrix4::Matrix4;
+
+// Fixtures the interop tests share, so every crate is checked against the same transform.
+
+pub const EPSILON: f32 = 1e-5;
+
+pub fn position() -> Vector3 {
+	Vector3 { x: 1.0, y: - 2.0, z: 3.0 }
+}
+
+pub fn rotation() -> Quaternion {
+	let mut q = Quaternion::new();
+	q.set_from_axis_angle(&Vector3 { x: 0.0, y: 0.6, z: 0.8 }, 1.1);
+	q
+}
+
+pub fn scale() -> Vector3 {
+	Vector3 { x: 2.0, y: 0.5, z: 1.5 }
+}
+
+// translation * rotation * scale from the fixtures above
+pub fn compose() -> Matrix4 {
+	let mut m = Matrix4::new();
+	m.compose(&position(), &rotation(), &scale());
+	m
+}
+
+pub fn assert_close(a: &[f32], b: &[f32]) {
+	for (x, y) in a.iter().zip(b.iter()) {
+		assert!(( x - y ).abs() < EPSILON, "{:?} != {:?}", a, b);
+	}
+}
+
+// Converts a Matrix3 holding 1 to 9 in row order to T and back. check gets the converted
+// matrix and the element in row 0, column 1, to test how the other crate indexes it.
+pub fn matrix3_round_trip<T, F: FnOnce(&T, f32)>(check: F) where T: From<Matrix3>, Matrix3: From<T> {
+	let mut m = Matrix3::new();
+	m.set(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+
+	let converted = T::from(m);
+	check(&converted, 2.0);
+
+	let back = Matrix3::from(converted);
+	assert_eq!(back.elements, m.elements);
+}
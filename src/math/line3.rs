@@ -0,0 +1,93 @@
+use super::vector3::Vector3;
+use super::matrix4::Matrix4;
+use super::math_static::clamp;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Line3 {
+	pub start: Vector3,
+	pub end: Vector3,
+}
+
+impl Line3 {
+	pub fn new() -> Line3 {
+		Line3 {
+			start: Vector3::new(),
+			end: Vector3::new(),
+		}
+	}
+
+	pub fn set(&mut self, start: &Vector3, end: &Vector3) {
+		self.start.copy(start);
+		self.end.copy(end);
+	}
+
+	pub fn copy(&mut self, line: &Line3) {
+		self.start.copy(&line.start);
+		self.end.copy(&line.end);
+	}
+
+	pub fn get_center(&self, target: &mut Vector3) {
+		target.add_vectors(&self.start, &self.end);
+		target.multiply_scalar(0.5);
+	}
+
+	pub fn delta(&self, target: &mut Vector3) {
+		target.sub_vectors(&self.end, &self.start);
+	}
+
+	pub fn distance_sq(&self) -> f32 {
+		self.start.distance_to_squared(&self.end)
+	}
+
+	pub fn distance(&self) -> f32 {
+		self.start.distance_to(&self.end)
+	}
+
+	pub fn at(&self, t: f32, target: &mut Vector3) {
+		self.delta(target);
+		target.multiply_scalar(t);
+		target.add(&self.start);
+	}
+
+	pub fn closest_point_to_point_parameter(&self, point: &Vector3, clamp_to_line: bool) -> f32 {
+		let mut start_p = Vector3::new();
+		let mut start_end = Vector3::new();
+
+		start_p.sub_vectors(point, &self.start);
+		start_end.sub_vectors(&self.end, &self.start);
+
+		let start_end2 = start_end.dot(&start_end);
+
+		if start_end2 == 0.0 {
+			return 0.0;
+		}
+
+		let t = start_end.dot(&start_p) / start_end2;
+
+		if clamp_to_line {
+			clamp(t, 0.0, 1.0)
+		} else {
+			t
+		}
+	}
+
+	pub fn closest_point_to_point(&self, point: &Vector3, clamp_to_line: bool, target: &mut Vector3) {
+		let t = self.closest_point_to_point_parameter(point, clamp_to_line);
+		self.at(t, target);
+	}
+
+	pub fn apply_matrix4(&mut self, matrix: &Matrix4) {
+		self.start.apply_matrix4(matrix);
+		self.end.apply_matrix4(matrix);
+	}
+
+	pub fn equals(&self, line: &Line3) -> bool {
+		line.start.equals(&self.start) && line.end.equals(&self.end)
+	}
+}
+
+impl Default for Line3 {
+	fn default() -> Line3 {
+		Line3::new()
+	}
+}
@@ -1,6 +1,11 @@
+#[cfg(feature = "std")]
 extern crate uuid;
+#[cfg(feature = "std")]
 use self::uuid::Uuid;
+#[cfg(not(feature = "std"))]
+use super::float::Float;
 
+#[cfg(feature = "std")]
 pub fn generate_UUID() -> Uuid{
 	Uuid::new_v4()
 }
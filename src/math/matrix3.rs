@@ -1,5 +1,7 @@
 use super::matrix4::Matrix4;
 use super::vector3::Vector3;
+#[cfg(not(feature = "std"))]
+use super::float::Float;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Matrix3 {
@@ -61,6 +63,20 @@ impl Matrix3 {
 		);
 	}
 
+	pub fn extract_basis(&self, x_axis: &mut Vector3, y_axis: &mut Vector3, z_axis: &mut Vector3) {
+		x_axis.set(self.elements[ 0 ], self.elements[ 1 ], self.elements[ 2 ]);
+		y_axis.set(self.elements[ 3 ], self.elements[ 4 ], self.elements[ 5 ]);
+		z_axis.set(self.elements[ 6 ], self.elements[ 7 ], self.elements[ 8 ]);
+	}
+
+	pub fn make_basis(&mut self, x_axis: &Vector3, y_axis: &Vector3, z_axis: &Vector3) {
+		self.set(
+			x_axis.x, y_axis.x, z_axis.x,
+			x_axis.y, y_axis.y, z_axis.y,
+			x_axis.z, y_axis.z, z_axis.z
+		);
+	}
+
 	pub fn apply_to_vector3_array(&self, array: &mut [f32], offset: Option<usize>, length: Option<usize>) {
 		let mut v1 = Vector3::new();
 		let offset: usize = match offset {
@@ -81,6 +97,50 @@ impl Matrix3 {
 		}
 	}
 
+	pub fn multiply(&mut self, m: &Matrix3) {
+		let s = *self;
+		self.multiply_matrices(&s, m);
+	}
+
+	pub fn premultiply(&mut self, m: &Matrix3) {
+		let s = *self;
+		self.multiply_matrices(m, &s);
+	}
+
+	pub fn multiply_matrices(&mut self, a: &Matrix3, b: &Matrix3) {
+		let a11 = a.elements[ 0 ];
+		let a12 = a.elements[ 3 ];
+		let a13 = a.elements[ 6 ];
+		let a21 = a.elements[ 1 ];
+		let a22 = a.elements[ 4 ];
+		let a23 = a.elements[ 7 ];
+		let a31 = a.elements[ 2 ];
+		let a32 = a.elements[ 5 ];
+		let a33 = a.elements[ 8 ];
+
+		let b11 = b.elements[ 0 ];
+		let b12 = b.elements[ 3 ];
+		let b13 = b.elements[ 6 ];
+		let b21 = b.elements[ 1 ];
+		let b22 = b.elements[ 4 ];
+		let b23 = b.elements[ 7 ];
+		let b31 = b.elements[ 2 ];
+		let b32 = b.elements[ 5 ];
+		let b33 = b.elements[ 8 ];
+
+		self.elements[ 0 ] = a11 * b11 + a12 * b21 + a13 * b31;
+		self.elements[ 3 ] = a11 * b12 + a12 * b22 + a13 * b32;
+		self.elements[ 6 ] = a11 * b13 + a12 * b23 + a13 * b33;
+
+		self.elements[ 1 ] = a21 * b11 + a22 * b21 + a23 * b31;
+		self.elements[ 4 ] = a21 * b12 + a22 * b22 + a23 * b32;
+		self.elements[ 7 ] = a21 * b13 + a22 * b23 + a23 * b33;
+
+		self.elements[ 2 ] = a31 * b11 + a32 * b21 + a33 * b31;
+		self.elements[ 5 ] = a31 * b12 + a32 * b22 + a33 * b32;
+		self.elements[ 8 ] = a31 * b13 + a32 * b23 + a33 * b33;
+	}
+
 	pub fn multiply_scalar(&mut self, s: f32) {
 		self.elements[ 0 ] *= s;
 		self.elements[ 3 ] *= s;
@@ -134,10 +194,11 @@ impl Matrix3 {
 
 			if throw_on_degenerate {
 				panic!("{}", msg);
-			} else {
-				println!("{}", msg);
 			}
 
+			#[cfg(feature = "std")]
+			println!("{}", msg);
+
 			return self.identity();
 		}
 
@@ -178,6 +239,92 @@ impl Matrix3 {
 		self.transpose();
 	}
 
+	// Eigen-decomposition of a symmetric matrix by cyclic Jacobi rotations. Only the lower
+	// triangle is read. Eigenvalues come out in descending order and the matching unit
+	// eigenvectors are the columns of `vectors`, which is always a proper rotation.
+	pub fn symmetric_eigen(&self, values: &mut Vector3, vectors: &mut Matrix3) {
+		let e = &self.elements;
+		let mut a = [
+			[ e[ 0 ], e[ 1 ], e[ 2 ] ],
+			[ e[ 1 ], e[ 4 ], e[ 5 ] ],
+			[ e[ 2 ], e[ 5 ], e[ 8 ] ],
+		];
+		let mut v = [
+			[ 1.0, 0.0, 0.0 ],
+			[ 0.0, 1.0, 0.0 ],
+			[ 0.0, 0.0, 1.0 ],
+		];
+
+		for _ in 0..50 {
+			let off = a[ 0 ][ 1 ] * a[ 0 ][ 1 ] + a[ 0 ][ 2 ] * a[ 0 ][ 2 ] + a[ 1 ][ 2 ] * a[ 1 ][ 2 ];
+			let diag = a[ 0 ][ 0 ] * a[ 0 ][ 0 ] + a[ 1 ][ 1 ] * a[ 1 ][ 1 ] + a[ 2 ][ 2 ] * a[ 2 ][ 2 ];
+
+			if off <= f32::EPSILON * f32::EPSILON * diag || off == 0.0 {
+				break;
+			}
+
+			for &(p, q) in &[ (0, 1), (0, 2), (1, 2) ] {
+				let apq = a[ p ][ q ];
+
+				if apq == 0.0 {
+					continue;
+				}
+
+				// rotation angle that zeroes a[ p ][ q ]
+				let theta = ( a[ q ][ q ] - a[ p ][ p ] ) / ( 2.0 * apq );
+				let t = theta.signum() / ( theta.abs() + ( theta * theta + 1.0 ).sqrt() );
+				let c = 1.0 / ( t * t + 1.0 ).sqrt();
+				let s = t * c;
+
+				for row in &mut a {
+					let akp = row[ p ];
+					let akq = row[ q ];
+					row[ p ] = c * akp - s * akq;
+					row[ q ] = s * akp + c * akq;
+				}
+
+				let ap = a[ p ];
+				let aq = a[ q ];
+				for (k, (apk, aqk)) in ap.iter().zip(aq.iter()).enumerate() {
+					a[ p ][ k ] = c * apk - s * aqk;
+					a[ q ][ k ] = s * apk + c * aqk;
+				}
+
+				for row in &mut v {
+					let vkp = row[ p ];
+					let vkq = row[ q ];
+					row[ p ] = c * vkp - s * vkq;
+					row[ q ] = s * vkp + c * vkq;
+				}
+			}
+		}
+
+		// sort by descending eigenvalue
+		let mut order = [ 0, 1, 2 ];
+		order.sort_by(|&i, &j| a[ j ][ j ].partial_cmp(&a[ i ][ i ]).unwrap_or(::std::cmp::Ordering::Equal));
+
+		values.set(a[ order[ 0 ] ][ order[ 0 ] ], a[ order[ 1 ] ][ order[ 1 ] ], a[ order[ 2 ] ][ order[ 2 ] ]);
+
+		let column = |i: usize| Vector3 {
+			x: v[ 0 ][ i ],
+			y: v[ 1 ][ i ],
+			z: v[ 2 ][ i ],
+		};
+		let x_axis = column(order[ 0 ]);
+		let y_axis = column(order[ 1 ]);
+		let mut z_axis = column(order[ 2 ]);
+
+		// keep the basis right-handed
+		let mut cross = Vector3::new();
+		cross.cross_vectors(&x_axis, &y_axis);
+
+		if cross.dot(&z_axis) < 0.0 {
+			z_axis.negate();
+		}
+
+		vectors.make_basis(&x_axis, &y_axis, &z_axis);
+	}
+
 	pub fn transpose_into_array(&self, r: &mut [f32]) {
 		r[ 0 ] = self.elements[ 0 ];
 		r[ 1 ] = self.elements[ 3 ];
@@ -212,4 +359,71 @@ impl Matrix3 {
 		array[ offset + 7 ] = self.elements[ 7 ];
 		array[ offset + 8 ]  = self.elements[ 8 ];
 	}
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn symmetric_eigen_solves_a_v_equals_lambda_v() {
+		let mut m = Matrix3::new();
+		m.set(
+			4.0, 1.0, 2.0,
+			1.0, 3.0, 0.0,
+			2.0, 0.0, 5.0
+		);
+
+		let mut values = Vector3::new();
+		let mut vectors = Matrix3::new();
+		m.symmetric_eigen(&mut values, &mut vectors);
+
+		assert!(values.x >= values.y && values.y >= values.z);
+		assert!(( vectors.determinant() - 1.0 ).abs() < 1e-5);
+
+		// the trace is the sum of the eigenvalues
+		assert!(( values.x + values.y + values.z - 12.0 ).abs() < 1e-4);
+
+		let mut axes = [ Vector3::new(); 3 ];
+		{
+			let (u0, rest) = axes.split_at_mut(1);
+			let (u1, u2) = rest.split_at_mut(1);
+			vectors.extract_basis(&mut u0[ 0 ], &mut u1[ 0 ], &mut u2[ 0 ]);
+		}
+
+		for (v, &lambda) in axes.iter().zip(&[ values.x, values.y, values.z ]) {
+			assert!(( v.length() - 1.0 ).abs() < 1e-5);
+
+			let mut av = *v;
+			av.apply_matrix3(&m);
+			let mut lambda_v = *v;
+			lambda_v.multiply_scalar(lambda);
+
+			assert!(av.distance_to(&lambda_v) < 1e-4, "{:?} != {:?}", av, lambda_v);
+		}
+	}
+
+	#[test]
+	fn symmetric_eigen_of_a_diagonal_matrix() {
+		let mut m = Matrix3::new();
+		m.set(
+			1.0, 0.0, 0.0,
+			0.0, 3.0, 0.0,
+			0.0, 0.0, 2.0
+		);
+
+		let mut values = Vector3::new();
+		let mut vectors = Matrix3::new();
+		m.symmetric_eigen(&mut values, &mut vectors);
+
+		assert!(values.equals(&Vector3 { x: 3.0, y: 2.0, z: 1.0 }));
+
+		let mut x_axis = Vector3::new();
+		let mut y_axis = Vector3::new();
+		let mut z_axis = Vector3::new();
+		vectors.extract_basis(&mut x_axis, &mut y_axis, &mut z_axis);
+		assert!(( x_axis.y.abs() - 1.0 ).abs() < 1e-6);
+		assert!(( y_axis.z.abs() - 1.0 ).abs() < 1e-6);
+		assert!(( z_axis.x.abs() - 1.0 ).abs() < 1e-6);
+	}
+}
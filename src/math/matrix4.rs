@@ -1,6 +1,10 @@
 use super::quaternion::Quaternion;
 use super::vector3::Vector3;
 use super::euler::{Euler, RotationOrders};
+use super::matrix3::Matrix3;
+use super::decompose;
+#[cfg(not(feature = "std"))]
+use super::float::Float;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Matrix4 {
@@ -572,11 +576,11 @@ impl Matrix4 {
 
 				panic!("{}", msg);
 
-			} else {
+			}
 
-				println!("{}", msg);
+			#[cfg(feature = "std")]
+			println!("{}", msg);
 
-			}
 
 			self.identity();
 			return;
@@ -759,6 +763,71 @@ impl Matrix4 {
 		scale.set_z(sz);
 	}
 
+	// Like compose, with a shear applied between scale and rotation:
+	// matrix = translation * rotation * shear * scale, where shear is the unit upper triangular
+	// matrix with shear.x = xy, shear.y = xz and shear.z = yz above the diagonal.
+	pub fn compose_with_shear(&mut self, position: &Vector3, quaternion: &Quaternion, scale: &Vector3, shear: &Vector3) {
+		let mut shear_matrix = Matrix4::new();
+		shear_matrix.set(
+			1.0, shear.x, shear.y, 0.0,
+			0.0, 1.0, shear.z, 0.0,
+			0.0, 0.0, 1.0, 0.0,
+			0.0, 0.0, 0.0, 1.0
+		);
+
+		self.make_rotation_from_quaternion(quaternion);
+		self.multiply(&shear_matrix);
+		self.scale(scale);
+		self.set_position(position);
+	}
+
+	// Inverse of compose_with_shear for any affine matrix. The upper 3x3 is split by a QR
+	// decomposition, so shear is recovered rather than folded into rotation and scale.
+	// A reflection is carried by a negative scale.x, as in decompose.
+	pub fn decompose_with_shear(&self, position: &mut Vector3, quaternion: &mut Quaternion, scale: &mut Vector3, shear: &mut Vector3) {
+		position.set(self.elements[ 12 ], self.elements[ 13 ], self.elements[ 14 ]);
+
+		let mut m = Matrix3::new();
+		m.set_from_matrix4(self);
+
+		let mut q = Matrix3::new();
+		let mut r = Matrix3::new();
+		decompose::qr(&m, &mut q, &mut r);
+
+		// q has to be a proper rotation, move the reflection into the x scale
+		if q.determinant() < 0.0 {
+			q.elements[ 0 ] = - q.elements[ 0 ];
+			q.elements[ 1 ] = - q.elements[ 1 ];
+			q.elements[ 2 ] = - q.elements[ 2 ];
+
+			r.elements[ 0 ] = - r.elements[ 0 ];
+			r.elements[ 3 ] = - r.elements[ 3 ];
+			r.elements[ 6 ] = - r.elements[ 6 ];
+		}
+
+		let re = r.elements;
+		let sx = re[ 0 ];
+		let sy = re[ 4 ];
+		let sz = re[ 8 ];
+
+		scale.set(sx, sy, sz);
+
+		shear.set(
+			if sy != 0.0 { re[ 3 ] / sy } else { 0.0 },
+			if sz != 0.0 { re[ 6 ] / sz } else { 0.0 },
+			if sz != 0.0 { re[ 7 ] / sz } else { 0.0 }
+		);
+
+		let mut x_axis = Vector3::new();
+		let mut y_axis = Vector3::new();
+		let mut z_axis = Vector3::new();
+		q.extract_basis(&mut x_axis, &mut y_axis, &mut z_axis);
+
+		let mut rotation = Matrix4::new();
+		rotation.make_basis(&x_axis, &y_axis, &z_axis);
+		quaternion.set_from_rotation_matrix(&rotation);
+	}
+
 	pub fn make_frustum(&mut self, left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) {
 		let x = 2.0 * near / ( right - left );
 		let y = 2.0 * near / ( top - bottom );
@@ -869,4 +938,62 @@ impl Matrix4 {
 		array[ offset + 14 ] = self.elements[ 14 ];
 		array[ offset + 15 ] = self.elements[ 15 ];
 	}
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn assert_matrix_close(a: &Matrix4, b: &Matrix4) {
+		for (x, y) in a.elements.iter().zip(b.elements.iter()) {
+			assert!(( x - y ).abs() < 1e-4, "{:?} != {:?}", a.elements, b.elements);
+		}
+	}
+
+	#[test]
+	fn shear_round_trip() {
+		let position = Vector3 { x: 1.0, y: - 2.0, z: 3.0 };
+		let mut quaternion = Quaternion::new();
+		quaternion.set_from_axis_angle(&Vector3 { x: 0.0, y: 0.6, z: 0.8 }, 1.1);
+		let scale = Vector3 { x: 2.0, y: 3.0, z: 0.5 };
+		let shear = Vector3 { x: 0.3, y: - 0.2, z: 0.7 };
+
+		let mut m = Matrix4::new();
+		m.compose_with_shear(&position, &quaternion, &scale, &shear);
+
+		let mut p = Vector3::new();
+		let mut q = Quaternion::new();
+		let mut s = Vector3::new();
+		let mut sh = Vector3::new();
+		m.decompose_with_shear(&mut p, &mut q, &mut s, &mut sh);
+
+		assert!(p.distance_to(&position) < 1e-5);
+		assert!(( q.dot(&quaternion).abs() - 1.0 ).abs() < 1e-5);
+		assert!(s.distance_to(&scale) < 1e-4);
+		assert!(sh.distance_to(&shear) < 1e-4);
+	}
+
+	#[test]
+	fn shear_round_trip_with_reflection() {
+		let position = Vector3 { x: 0.5, y: 0.0, z: - 4.0 };
+		let mut quaternion = Quaternion::new();
+		quaternion.set_from_axis_angle(&Vector3 { x: 1.0, y: 0.0, z: 0.0 }, 0.4);
+		let shear = Vector3 { x: - 0.5, y: 0.25, z: 0.1 };
+
+		// a mirror in y comes back as a negative x scale, the matrix is the same
+		let mut m = Matrix4::new();
+		m.compose_with_shear(&position, &quaternion, &Vector3 { x: 1.5, y: - 2.0, z: 1.0 }, &shear);
+
+		let mut p = Vector3::new();
+		let mut q = Quaternion::new();
+		let mut s = Vector3::new();
+		let mut sh = Vector3::new();
+		m.decompose_with_shear(&mut p, &mut q, &mut s, &mut sh);
+
+		assert!(s.x < 0.0 && s.y > 0.0 && s.z > 0.0);
+
+		let mut back = Matrix4::new();
+		back.compose_with_shear(&p, &q, &s, &sh);
+		assert_matrix_close(&back, &m);
+	}
+}
@@ -1,3 +1,4 @@
+pub mod float;
 pub mod math_static;
 pub mod vector2;
 pub mod vector3;
@@ -5,4 +6,15 @@ pub mod quaternion;
 pub mod matrix3;
 pub mod matrix4;
 pub mod euler;
-pub mod spherical;
\ No newline at end of file
+pub mod spherical;
+pub mod ray;
+pub mod line3;
+pub mod triangle;
+pub mod convex_hull;
+pub mod box3;
+pub mod sphere;
+pub mod obb;
+pub mod decompose;
+pub mod data_utils;
+pub mod color;
+pub mod interop;
\ No newline at end of file
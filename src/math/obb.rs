@@ -0,0 +1,474 @@
+use super::vector3::Vector3;
+use super::matrix3::Matrix3;
+use super::matrix4::Matrix4;
+use super::box3::Box3;
+use super::sphere::Sphere;
+use super::ray::Ray;
+use super::math_static::clamp;
+
+// Oriented bounding box: a box of extent 2 * half_size around center, with its axes given by the columns of rotation.
+#[derive(Debug, Clone, Copy)]
+pub struct OBB {
+	pub center: Vector3,
+	pub half_size: Vector3,
+	pub rotation: Matrix3,
+}
+
+impl OBB {
+	pub fn new() -> OBB {
+		OBB {
+			center: Vector3::new(),
+			half_size: Vector3::new(),
+			rotation: Matrix3::new(),
+		}
+	}
+
+	pub fn set(&mut self, center: &Vector3, half_size: &Vector3, rotation: &Matrix3) {
+		self.center.copy(center);
+		self.half_size.copy(half_size);
+		self.rotation.copy(rotation);
+	}
+
+	pub fn copy(&mut self, obb: &OBB) {
+		self.center.copy(&obb.center);
+		self.half_size.copy(&obb.half_size);
+		self.rotation.copy(&obb.rotation);
+	}
+
+	pub fn get_size(&self, target: &mut Vector3) {
+		target.copy(&self.half_size);
+		target.multiply_scalar(2.0);
+	}
+
+	// Reference: Closest Point on OBB to Point in Real-Time Collision Detection
+	// by Christer Ericson (chapter 5.1.4)
+	pub fn clamp_point(&self, point: &Vector3, target: &mut Vector3) {
+		let half_size = self.half_size;
+
+		let mut v1 = Vector3::new();
+		v1.sub_vectors(point, &self.center);
+
+		let mut x_axis = Vector3::new();
+		let mut y_axis = Vector3::new();
+		let mut z_axis = Vector3::new();
+		self.rotation.extract_basis(&mut x_axis, &mut y_axis, &mut z_axis);
+
+		// start at the center position of the OBB
+		target.copy(&self.center);
+
+		// project the target onto the OBB axes and walk towards that point
+		let x = clamp(v1.dot(&x_axis), - half_size.x, half_size.x);
+		target.add_scaled_vector(&x_axis, x);
+
+		let y = clamp(v1.dot(&y_axis), - half_size.y, half_size.y);
+		target.add_scaled_vector(&y_axis, y);
+
+		let z = clamp(v1.dot(&z_axis), - half_size.z, half_size.z);
+		target.add_scaled_vector(&z_axis, z);
+	}
+
+	pub fn contains_point(&self, point: &Vector3) -> bool {
+		let mut v1 = Vector3::new();
+		v1.sub_vectors(point, &self.center);
+
+		let mut x_axis = Vector3::new();
+		let mut y_axis = Vector3::new();
+		let mut z_axis = Vector3::new();
+		self.rotation.extract_basis(&mut x_axis, &mut y_axis, &mut z_axis);
+
+		// project v1 onto each axis and check if these points lie inside the OBB
+		v1.dot(&x_axis).abs() <= self.half_size.x &&
+			v1.dot(&y_axis).abs() <= self.half_size.y &&
+			v1.dot(&z_axis).abs() <= self.half_size.z
+	}
+
+	pub fn intersects_box3(&self, box3: &Box3) -> bool {
+		let mut obb = OBB::new();
+		obb.from_box3(box3);
+		self.intersects_obb(&obb, f32::EPSILON)
+	}
+
+	pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+		// find the point on the OBB closest to the sphere center
+		let mut closest_point = Vector3::new();
+		self.clamp_point(&sphere.center, &mut closest_point);
+
+		// if that point is inside the sphere, the OBB and sphere intersect
+		closest_point.distance_to_squared(&sphere.center) <= sphere.radius * sphere.radius
+	}
+
+	// Reference: OBB-OBB Intersection in Real-Time Collision Detection
+	// by Christer Ericson (chapter 4.4.1)
+	// The epsilon is added to the rotation terms to counteract arithmetic errors
+	// when two edges are parallel and their cross product is (near) null.
+	pub fn intersects_obb(&self, obb: &OBB, epsilon: f32) -> bool {
+		let a_c = self.center;
+		let a_e = [ self.half_size.x, self.half_size.y, self.half_size.z ];
+		let mut a_u = [ Vector3::new(); 3 ];
+		{
+			let (u0, rest) = a_u.split_at_mut(1);
+			let (u1, u2) = rest.split_at_mut(1);
+			self.rotation.extract_basis(&mut u0[ 0 ], &mut u1[ 0 ], &mut u2[ 0 ]);
+		}
+
+		let b_c = obb.center;
+		let b_e = [ obb.half_size.x, obb.half_size.y, obb.half_size.z ];
+		let mut b_u = [ Vector3::new(); 3 ];
+		{
+			let (u0, rest) = b_u.split_at_mut(1);
+			let (u1, u2) = rest.split_at_mut(1);
+			obb.rotation.extract_basis(&mut u0[ 0 ], &mut u1[ 0 ], &mut u2[ 0 ]);
+		}
+
+		// compute rotation matrix expressing b in a's coordinate frame
+		let mut r = [ [ 0.0f32; 3 ]; 3 ];
+		let mut abs_r = [ [ 0.0f32; 3 ]; 3 ];
+
+		for i in 0..3 {
+			for j in 0..3 {
+				r[ i ][ j ] = a_u[ i ].dot(&b_u[ j ]);
+				// counteract arithmetic errors when two edges are parallel and
+				// their cross product is (near) null
+				abs_r[ i ][ j ] = r[ i ][ j ].abs() + epsilon;
+			}
+		}
+
+		// compute translation vector and bring it into a's coordinate frame
+		let mut v1 = Vector3::new();
+		v1.sub_vectors(&b_c, &a_c);
+		let t = [ v1.dot(&a_u[ 0 ]), v1.dot(&a_u[ 1 ]), v1.dot(&a_u[ 2 ]) ];
+
+		// test axes L = A0, L = A1, L = A2
+		for i in 0..3 {
+			let ra = a_e[ i ];
+			let rb = b_e[ 0 ] * abs_r[ i ][ 0 ] + b_e[ 1 ] * abs_r[ i ][ 1 ] + b_e[ 2 ] * abs_r[ i ][ 2 ];
+			if t[ i ].abs() > ra + rb {
+				return false;
+			}
+		}
+
+		// test axes L = B0, L = B1, L = B2
+		for i in 0..3 {
+			let ra = a_e[ 0 ] * abs_r[ 0 ][ i ] + a_e[ 1 ] * abs_r[ 1 ][ i ] + a_e[ 2 ] * abs_r[ 2 ][ i ];
+			let rb = b_e[ i ];
+			if ( t[ 0 ] * r[ 0 ][ i ] + t[ 1 ] * r[ 1 ][ i ] + t[ 2 ] * r[ 2 ][ i ] ).abs() > ra + rb {
+				return false;
+			}
+		}
+
+		// test the nine cross product axes L = Ai x Bj
+		for i in 0..3 {
+			let i1 = ( i + 1 ) % 3;
+			let i2 = ( i + 2 ) % 3;
+
+			for j in 0..3 {
+				let j1 = ( j + 1 ) % 3;
+				let j2 = ( j + 2 ) % 3;
+
+				let ra = a_e[ i1 ] * abs_r[ i2 ][ j ] + a_e[ i2 ] * abs_r[ i1 ][ j ];
+				let rb = b_e[ j1 ] * abs_r[ i ][ j2 ] + b_e[ j2 ] * abs_r[ i ][ j1 ];
+
+				if ( t[ i2 ] * r[ i1 ][ j ] - t[ i1 ] * r[ i2 ][ j ] ).abs() > ra + rb {
+					return false;
+				}
+			}
+		}
+
+		// since no separating axis is found, the OBBs must be intersecting
+		true
+	}
+
+	// Returns the closest point where the ray enters the OBB, or None if it misses.
+	pub fn intersect_ray(&self, ray: &Ray) -> Option<Vector3> {
+		// the idea is to perform the intersection test in the local space
+		// of the OBB.
+
+		let mut size = Vector3::new();
+		self.get_size(&mut size);
+
+		let mut aabb = Box3::new();
+		aabb.set_from_center_and_size(&Vector3::new(), &size);
+
+		// create a 4x4 transformation matrix
+		let mut transform = Matrix4::new();
+		self.get_matrix4(&mut transform);
+
+		// transform ray to the local space of the OBB
+		let mut inverse = Matrix4::new();
+		inverse.get_inverse(&transform, false);
+
+		let mut local_ray = *ray;
+		local_ray.apply_matrix4(&inverse);
+
+		// perform ray <-> AABB intersection test and transform the intersection point back to world space
+		aabb.intersect_ray(&local_ray).map(|mut point| {
+			point.apply_matrix4(&transform);
+			point
+		})
+	}
+
+	pub fn intersects_ray(&self, ray: &Ray) -> bool {
+		self.intersect_ray(ray).is_some()
+	}
+
+	pub fn from_box3(&mut self, box3: &Box3) {
+		box3.get_center(&mut self.center);
+
+		box3.get_size(&mut self.half_size);
+		self.half_size.multiply_scalar(0.5);
+
+		self.rotation.identity();
+	}
+
+	// Fits the box to a point set by principal component analysis: the axes follow the
+	// eigenvectors of the covariance matrix and the extents are the projected min/max.
+	pub fn set_from_points(&mut self, points: &[Vector3]) {
+		if points.is_empty() {
+			self.center.set(0.0, 0.0, 0.0);
+			self.half_size.set(0.0, 0.0, 0.0);
+			self.rotation.identity();
+			return;
+		}
+
+		let n = points.len() as f32;
+
+		let mut mean = Vector3::new();
+		for point in points {
+			mean.add(point);
+		}
+		mean.divide_scalar(n);
+
+		let mut c = [ 0.0f32; 6 ]; // xx, xy, xz, yy, yz, zz
+		for point in points {
+			let dx = point.x - mean.x;
+			let dy = point.y - mean.y;
+			let dz = point.z - mean.z;
+			c[ 0 ] += dx * dx;
+			c[ 1 ] += dx * dy;
+			c[ 2 ] += dx * dz;
+			c[ 3 ] += dy * dy;
+			c[ 4 ] += dy * dz;
+			c[ 5 ] += dz * dz;
+		}
+
+		let mut covariance = Matrix3::new();
+		covariance.set(
+			c[ 0 ] / n, c[ 1 ] / n, c[ 2 ] / n,
+			c[ 1 ] / n, c[ 3 ] / n, c[ 4 ] / n,
+			c[ 2 ] / n, c[ 4 ] / n, c[ 5 ] / n
+		);
+
+		let mut eigenvalues = Vector3::new();
+		covariance.symmetric_eigen(&mut eigenvalues, &mut self.rotation);
+
+		let mut axes = [ Vector3::new(); 3 ];
+		{
+			let (u0, rest) = axes.split_at_mut(1);
+			let (u1, u2) = rest.split_at_mut(1);
+			self.rotation.extract_basis(&mut u0[ 0 ], &mut u1[ 0 ], &mut u2[ 0 ]);
+		}
+
+		// extents of the point set along each principal axis
+		let mut min = [ f32::INFINITY; 3 ];
+		let mut max = [ f32::NEG_INFINITY; 3 ];
+
+		for point in points {
+			let mut d = Vector3::new();
+			d.sub_vectors(point, &mean);
+
+			for i in 0..3 {
+				let projection = d.dot(&axes[ i ]);
+				min[ i ] = min[ i ].min(projection);
+				max[ i ] = max[ i ].max(projection);
+			}
+		}
+
+		self.center.copy(&mean);
+		for i in 0..3 {
+			self.center.add_scaled_vector(&axes[ i ], ( min[ i ] + max[ i ] ) * 0.5);
+		}
+
+		self.half_size.set(
+			( max[ 0 ] - min[ 0 ] ) * 0.5,
+			( max[ 1 ] - min[ 1 ] ) * 0.5,
+			( max[ 2 ] - min[ 2 ] ) * 0.5
+		);
+	}
+
+	pub fn equals(&self, obb: &OBB) -> bool {
+		obb.center.equals(&self.center) &&
+			obb.half_size.equals(&self.half_size) &&
+			obb.rotation.elements == self.rotation.elements
+	}
+
+	pub fn apply_matrix4(&mut self, matrix: &Matrix4) {
+		let e = matrix.get_elements();
+
+		let mut v1 = Vector3::new();
+
+		v1.set(e[ 0 ], e[ 1 ], e[ 2 ]);
+		let mut sx = v1.length();
+		v1.set(e[ 4 ], e[ 5 ], e[ 6 ]);
+		let sy = v1.length();
+		v1.set(e[ 8 ], e[ 9 ], e[ 10 ]);
+		let sz = v1.length();
+
+		// if determinant is negative, we need to invert one scale
+		if matrix.determinant() < 0.0 {
+			sx = - sx;
+		}
+
+		let mut rotation_matrix = Matrix3::new();
+		rotation_matrix.set_from_matrix4(matrix);
+
+		let inv_sx = 1.0 / sx;
+		let inv_sy = 1.0 / sy;
+		let inv_sz = 1.0 / sz;
+
+		rotation_matrix.elements[ 0 ] *= inv_sx;
+		rotation_matrix.elements[ 1 ] *= inv_sx;
+		rotation_matrix.elements[ 2 ] *= inv_sx;
+
+		rotation_matrix.elements[ 3 ] *= inv_sy;
+		rotation_matrix.elements[ 4 ] *= inv_sy;
+		rotation_matrix.elements[ 5 ] *= inv_sy;
+
+		rotation_matrix.elements[ 6 ] *= inv_sz;
+		rotation_matrix.elements[ 7 ] *= inv_sz;
+		rotation_matrix.elements[ 8 ] *= inv_sz;
+
+		self.rotation.premultiply(&rotation_matrix);
+
+		// exact for uniform scale; a non-uniform scale of a rotated box is approximated
+		self.half_size.x *= sx.abs();
+		self.half_size.y *= sy.abs();
+		self.half_size.z *= sz.abs();
+
+		self.center.apply_matrix4(matrix);
+	}
+
+	fn get_matrix4(&self, target: &mut Matrix4) {
+		let mut x_axis = Vector3::new();
+		let mut y_axis = Vector3::new();
+		let mut z_axis = Vector3::new();
+		self.rotation.extract_basis(&mut x_axis, &mut y_axis, &mut z_axis);
+
+		target.make_basis(&x_axis, &y_axis, &z_axis);
+		target.set_position(&self.center);
+	}
+}
+
+impl Default for OBB {
+	fn default() -> OBB {
+		OBB::new()
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn rotation_z(theta: f32) -> Matrix3 {
+		let mut m = Matrix4::new();
+		m.make_rotation_z(theta);
+		let mut rotation = Matrix3::new();
+		rotation.set_from_matrix4(&m);
+		rotation
+	}
+
+	fn obb(center: Vector3, half_size: Vector3, rotation: Matrix3) -> OBB {
+		let mut obb = OBB::new();
+		obb.set(&center, &half_size, &rotation);
+		obb
+	}
+
+	fn axes(rotation: &Matrix3) -> [ Vector3; 3 ] {
+		let mut axes = [ Vector3::new(); 3 ];
+		{
+			let (u0, rest) = axes.split_at_mut(1);
+			let (u1, u2) = rest.split_at_mut(1);
+			rotation.extract_basis(&mut u0[ 0 ], &mut u1[ 0 ], &mut u2[ 0 ]);
+		}
+		axes
+	}
+
+	#[test]
+	fn set_from_points_fits_a_rotated_box() {
+		let mut axis = Vector3 { x: 1.0, y: 2.0, z: 3.0 };
+		axis.normalize();
+		let mut rotation = Matrix4::new();
+		rotation.make_rotation_axis(&axis, 0.7);
+
+		let center = Vector3 { x: 1.0, y: - 2.0, z: 0.5 };
+		let mut points = vec![];
+
+		for &x in &[ - 3.0, 3.0 ] {
+			for &y in &[ - 2.0, 2.0 ] {
+				for &z in &[ - 1.0, 1.0 ] {
+					let mut point = Vector3 { x, y, z };
+					point.apply_matrix4(&rotation);
+					point.add(&center);
+					points.push(point);
+				}
+			}
+		}
+
+		let mut fitted = OBB::new();
+		fitted.set_from_points(&points);
+
+		assert!(fitted.center.distance_to(&center) < 1e-4);
+		assert!(fitted.half_size.distance_to(&Vector3 { x: 3.0, y: 2.0, z: 1.0 }) < 1e-4, "{:?}", fitted.half_size);
+
+		// the axes come out ordered by extent, up to sign
+		let mut expected = Matrix3::new();
+		expected.set_from_matrix4(&rotation);
+
+		for (axis, expected) in axes(&fitted.rotation).iter().zip(axes(&expected).iter()) {
+			assert!(( axis.dot(expected).abs() - 1.0 ).abs() < 1e-4);
+		}
+
+		for point in &points {
+			let mut grown = fitted;
+			grown.half_size.add_scalar(1e-4);
+			assert!(grown.contains_point(point));
+		}
+	}
+
+	#[test]
+	fn intersects_obb_finds_separating_axes() {
+		let unit = Vector3 { x: 1.0, y: 1.0, z: 1.0 };
+		let a = obb(Vector3::new(), unit, Matrix3::new());
+
+		// the boxes overlap on the axes of a, only the diagonal axes of b separate them
+		let apart = obb(Vector3 { x: 2.3, y: 2.3, z: 0.0 }, unit, rotation_z(::std::f32::consts::FRAC_PI_4));
+		assert!(!a.intersects_obb(&apart, f32::EPSILON));
+		assert!(!apart.intersects_obb(&a, f32::EPSILON));
+
+		let overlapping = obb(Vector3 { x: 1.6, y: 1.6, z: 0.0 }, unit, rotation_z(::std::f32::consts::FRAC_PI_4));
+		assert!(a.intersects_obb(&overlapping, f32::EPSILON));
+		assert!(overlapping.intersects_obb(&a, f32::EPSILON));
+
+		let far = obb(Vector3 { x: 0.0, y: 0.0, z: 2.5 }, unit, Matrix3::new());
+		assert!(!a.intersects_obb(&far, f32::EPSILON));
+	}
+
+	#[test]
+	fn intersect_ray_hits_the_rotated_box() {
+		let unit = Vector3 { x: 1.0, y: 1.0, z: 1.0 };
+		let diamond = obb(Vector3 { x: 5.0, y: 0.0, z: 0.0 }, unit, rotation_z(::std::f32::consts::FRAC_PI_4));
+		let mut ray = Ray::new();
+
+		// the ray hits the corner pointing at the origin
+		ray.set(&Vector3::new(), &Vector3 { x: 1.0, y: 0.0, z: 0.0 });
+		let point = diamond.intersect_ray(&ray).unwrap();
+		assert!(point.distance_to(&Vector3 { x: 5.0 - ::std::f32::consts::SQRT_2, y: 0.0, z: 0.0 }) < 1e-4, "{:?}", point);
+
+		// passes above the top corner
+		ray.set(&Vector3 { x: 0.0, y: 1.5, z: 0.0 }, &Vector3 { x: 1.0, y: 0.0, z: 0.0 });
+		assert!(!diamond.intersects_ray(&ray));
+
+		ray.set(&Vector3::new(), &Vector3 { x: - 1.0, y: 0.0, z: 0.0 });
+		assert!(!diamond.intersects_ray(&ray));
+	}
+}
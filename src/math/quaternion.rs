@@ -1,6 +1,8 @@
 use super::euler::{Euler, RotationOrders};
 use super::vector3::Vector3;
 use super::matrix4::Matrix4;
+#[cfg(not(feature = "std"))]
+use super::float::Float;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Quaternion {
@@ -154,13 +156,13 @@ impl Quaternion {
 			self.y = ( m12 + m21 ) / s;
 			self.z = ( m13 + m31 ) / s;
 		} else if m22 > m33 {
-			let s = 2.0f32 * (1.0f32 + m22 - m11 - m33);
+			let s = 2.0f32 * (1.0f32 + m22 - m11 - m33).sqrt();
 			self.w = ( m13 - m31 ) / s;
 			self.x = ( m12 + m21 ) / s;
 			self.y = 0.25f32 * s;
 			self.z = ( m23 + m32 ) / s;
 		} else {
-			let s = 2.0f32 * (1.0f32 + m33 - m11 - m22);
+			let s = 2.0f32 * (1.0f32 + m33 - m11 - m22).sqrt();
 
 			self.w = ( m21 - m12 ) / s;
 			self.x = ( m13 + m31 ) / s;
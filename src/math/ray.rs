@@ -0,0 +1,248 @@
+use super::vector3::Vector3;
+use super::matrix4::Matrix4;
+#[cfg(not(feature = "std"))]
+use super::float::Float;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Ray {
+	pub origin: Vector3,
+	pub direction: Vector3,
+}
+
+impl Ray {
+	pub fn new() -> Ray {
+		Ray {
+			origin: Vector3::new(),
+			direction: Vector3 {
+				x: 0.0,
+				y: 0.0,
+				z: - 1.0,
+			},
+		}
+	}
+
+	pub fn set(&mut self, origin: &Vector3, direction: &Vector3) {
+		self.origin.copy(origin);
+		self.direction.copy(direction);
+	}
+
+	pub fn copy(&mut self, ray: &Ray) {
+		self.origin.copy(&ray.origin);
+		self.direction.copy(&ray.direction);
+	}
+
+	pub fn at(&self, t: f32, target: &mut Vector3) {
+		target.copy(&self.direction);
+		target.multiply_scalar(t);
+		target.add(&self.origin);
+	}
+
+	pub fn look_at(&mut self, v: &Vector3) {
+		self.direction.sub_vectors(v, &self.origin);
+		self.direction.normalize();
+	}
+
+	pub fn closest_point_to_point(&self, point: &Vector3, target: &mut Vector3) {
+		target.sub_vectors(point, &self.origin);
+		let direction_distance = target.dot(&self.direction);
+
+		if direction_distance < 0.0 {
+			target.copy(&self.origin);
+			return;
+		}
+
+		self.at(direction_distance, target);
+	}
+
+	pub fn distance_to_point(&self, point: &Vector3) -> f32 {
+		self.distance_sq_to_point(point).sqrt()
+	}
+
+	pub fn distance_sq_to_point(&self, point: &Vector3) -> f32 {
+		let mut v1 = Vector3::new();
+		v1.sub_vectors(point, &self.origin);
+		let direction_distance = v1.dot(&self.direction);
+
+		// point behind the ray
+
+		if direction_distance < 0.0 {
+			return self.origin.distance_to_squared(point);
+		}
+
+		self.at(direction_distance, &mut v1);
+
+		v1.distance_to_squared(point)
+	}
+
+	// From http://www.geometrictools.com/GTEngine/Include/Mathematics/GteDistRaySegment.h
+	// It returns the min distance between the ray and the segment defined by v0 and v1, and
+	// optionally the closest points on both.
+	pub fn distance_sq_to_segment(&self, v0: &Vector3, v1: &Vector3, point_on_ray: Option<&mut Vector3>, point_on_segment: Option<&mut Vector3>) -> f32 {
+		let mut seg_center = Vector3::new();
+		let mut seg_dir = Vector3::new();
+		let mut diff = Vector3::new();
+
+		seg_center.add_vectors(v0, v1);
+		seg_center.multiply_scalar(0.5);
+		seg_dir.sub_vectors(v1, v0);
+		seg_dir.normalize();
+		diff.sub_vectors(&self.origin, &seg_center);
+
+		let seg_extent = v0.distance_to(v1) * 0.5;
+		let a01 = - self.direction.dot(&seg_dir);
+		let b0 = diff.dot(&self.direction);
+		let b1 = - diff.dot(&seg_dir);
+		let c = diff.length_sq();
+		let det = ( 1.0 - a01 * a01 ).abs();
+		let mut s0;
+		let mut s1;
+		let sqr_dist;
+
+		if det > 0.0 {
+			// The ray and segment are not parallel.
+			s0 = a01 * b1 - b0;
+			s1 = a01 * b0 - b1;
+			let ext_det = seg_extent * det;
+
+			if s0 >= 0.0 {
+				if s1 >= - ext_det {
+					if s1 <= ext_det {
+						// region 0
+						// Minimum at interior points of ray and segment.
+						let inv_det = 1.0 / det;
+						s0 *= inv_det;
+						s1 *= inv_det;
+						sqr_dist = s0 * ( s0 + a01 * s1 + 2.0 * b0 ) + s1 * ( a01 * s0 + s1 + 2.0 * b1 ) + c;
+					} else {
+						// region 1
+						s1 = seg_extent;
+						s0 = ( - ( a01 * s1 + b0 ) ).max(0.0);
+						sqr_dist = - s0 * s0 + s1 * ( s1 + 2.0 * b1 ) + c;
+					}
+				} else {
+					// region 5
+					s1 = - seg_extent;
+					s0 = ( - ( a01 * s1 + b0 ) ).max(0.0);
+					sqr_dist = - s0 * s0 + s1 * ( s1 + 2.0 * b1 ) + c;
+				}
+			} else if s1 <= - ext_det {
+				// region 4
+				s0 = ( - ( - a01 * seg_extent + b0 ) ).max(0.0);
+				s1 = if s0 > 0.0 { - seg_extent } else { ( - b1 ).max(- seg_extent).min(seg_extent) };
+				sqr_dist = - s0 * s0 + s1 * ( s1 + 2.0 * b1 ) + c;
+			} else if s1 <= ext_det {
+				// region 3
+				s0 = 0.0;
+				s1 = ( - b1 ).max(- seg_extent).min(seg_extent);
+				sqr_dist = s1 * ( s1 + 2.0 * b1 ) + c;
+			} else {
+				// region 2
+				s0 = ( - ( a01 * seg_extent + b0 ) ).max(0.0);
+				s1 = if s0 > 0.0 { seg_extent } else { ( - b1 ).max(- seg_extent).min(seg_extent) };
+				sqr_dist = - s0 * s0 + s1 * ( s1 + 2.0 * b1 ) + c;
+			}
+		} else {
+			// Ray and segment are parallel.
+			s1 = if a01 > 0.0 { - seg_extent } else { seg_extent };
+			s0 = ( - ( a01 * s1 + b0 ) ).max(0.0);
+			sqr_dist = - s0 * s0 + s1 * ( s1 + 2.0 * b1 ) + c;
+		}
+
+		if let Some(point_on_ray) = point_on_ray {
+			self.at(s0, point_on_ray);
+		}
+
+		if let Some(point_on_segment) = point_on_segment {
+			point_on_segment.copy(&seg_dir);
+			point_on_segment.multiply_scalar(s1);
+			point_on_segment.add(&seg_center);
+		}
+
+		sqr_dist
+	}
+
+	// Compute the offset origin, edges, and normal.
+	// from http://www.geometrictools.com/GTEngine/Include/Mathematics/GteIntrRay3Triangle3.h
+	pub fn intersect_triangle(&self, a: &Vector3, b: &Vector3, c: &Vector3, backface_culling: bool) -> Option<Vector3> {
+		let mut edge1 = Vector3::new();
+		let mut edge2 = Vector3::new();
+		let mut normal = Vector3::new();
+		let mut diff = Vector3::new();
+		let mut v1 = Vector3::new();
+
+		edge1.sub_vectors(b, a);
+		edge2.sub_vectors(c, a);
+		normal.cross_vectors(&edge1, &edge2);
+
+		// Solve Q + t*D = b1*E1 + b2*E2 (Q = kDiff, D = ray direction,
+		// E1 = kEdge1, E2 = kEdge2, N = Cross(E1,E2)) by
+		//   |Dot(D,N)|*b1 = sign(Dot(D,N))*Dot(D,Cross(Q,E2))
+		//   |Dot(D,N)|*b2 = sign(Dot(D,N))*Dot(D,Cross(E1,Q))
+		//   |Dot(D,N)|*t = -sign(Dot(D,N))*Dot(Q,N)
+		let mut d_dot_n = self.direction.dot(&normal);
+		let sign;
+
+		if d_dot_n > 0.0 {
+			if backface_culling {
+				return None;
+			}
+			sign = 1.0;
+		} else if d_dot_n < 0.0 {
+			sign = - 1.0;
+			d_dot_n = - d_dot_n;
+		} else {
+			return None;
+		}
+
+		diff.sub_vectors(&self.origin, a);
+
+		v1.cross_vectors(&diff, &edge2);
+		let d_dot_q_x_e2 = sign * self.direction.dot(&v1);
+
+		// b1 < 0, no intersection
+		if d_dot_q_x_e2 < 0.0 {
+			return None;
+		}
+
+		v1.cross_vectors(&edge1, &diff);
+		let d_dot_e1_x_q = sign * self.direction.dot(&v1);
+
+		// b2 < 0, no intersection
+		if d_dot_e1_x_q < 0.0 {
+			return None;
+		}
+
+		// b1+b2 > 1, no intersection
+		if d_dot_q_x_e2 + d_dot_e1_x_q > d_dot_n {
+			return None;
+		}
+
+		// Line intersects triangle, check if ray does.
+		let q_dot_n = - sign * diff.dot(&normal);
+
+		// t < 0, no intersection
+		if q_dot_n < 0.0 {
+			return None;
+		}
+
+		// Ray intersects triangle.
+		let mut target = Vector3::new();
+		self.at(q_dot_n / d_dot_n, &mut target);
+		Some(target)
+	}
+
+	pub fn apply_matrix4(&mut self, matrix4: &Matrix4) {
+		self.origin.apply_matrix4(matrix4);
+		self.direction.transform_direction(matrix4);
+	}
+
+	pub fn equals(&self, ray: &Ray) -> bool {
+		ray.origin.equals(&self.origin) && ray.direction.equals(&self.direction)
+	}
+}
+
+impl Default for Ray {
+	fn default() -> Ray {
+		Ray::new()
+	}
+}
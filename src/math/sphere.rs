@@ -0,0 +1,60 @@
+use super::vector3::Vector3;
+use super::matrix4::Matrix4;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Sphere {
+	pub center: Vector3,
+	pub radius: f32,
+}
+
+impl Sphere {
+	pub fn new() -> Sphere {
+		Sphere {
+			center: Vector3::new(),
+			radius: 0.0,
+		}
+	}
+
+	pub fn set(&mut self, center: &Vector3, radius: f32) {
+		self.center.copy(center);
+		self.radius = radius;
+	}
+
+	pub fn copy(&mut self, sphere: &Sphere) {
+		self.center.copy(&sphere.center);
+		self.radius = sphere.radius;
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.radius <= 0.0
+	}
+
+	pub fn contains_point(&self, point: &Vector3) -> bool {
+		point.distance_to_squared(&self.center) <= self.radius * self.radius
+	}
+
+	pub fn distance_to_point(&self, point: &Vector3) -> f32 {
+		point.distance_to(&self.center) - self.radius
+	}
+
+	pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+		let radius_sum = self.radius + sphere.radius;
+		sphere.center.distance_to_squared(&self.center) <= radius_sum * radius_sum
+	}
+
+	pub fn apply_matrix4(&mut self, matrix: &Matrix4) {
+		self.center.apply_matrix4(matrix);
+		let mut m = *matrix;
+		self.radius *= m.get_max_scale_on_axis();
+	}
+
+	pub fn equals(&self, sphere: &Sphere) -> bool {
+		sphere.center.equals(&self.center) && sphere.radius == self.radius
+	}
+}
+
+impl Default for Sphere {
+	fn default() -> Sphere {
+		Sphere::new()
+	}
+}
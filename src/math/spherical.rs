@@ -1,5 +1,7 @@
 use super::vector3::Vector3;
 use super::math_static::clamp;
+#[cfg(not(feature = "std"))]
+use super::float::Float;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Spherical {
@@ -0,0 +1,114 @@
+use super::vector3::Vector3;
+#[cfg(not(feature = "std"))]
+use super::float::Float;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Triangle {
+	pub a: Vector3,
+	pub b: Vector3,
+	pub c: Vector3,
+}
+
+impl Triangle {
+	pub fn new() -> Triangle {
+		Triangle {
+			a: Vector3::new(),
+			b: Vector3::new(),
+			c: Vector3::new(),
+		}
+	}
+
+	pub fn set(&mut self, a: &Vector3, b: &Vector3, c: &Vector3) {
+		self.a.copy(a);
+		self.b.copy(b);
+		self.c.copy(c);
+	}
+
+	pub fn copy(&mut self, triangle: &Triangle) {
+		self.a.copy(&triangle.a);
+		self.b.copy(&triangle.b);
+		self.c.copy(&triangle.c);
+	}
+
+	pub fn get_normal_from_points(a: &Vector3, b: &Vector3, c: &Vector3, target: &mut Vector3) {
+		let mut v0 = Vector3::new();
+		target.sub_vectors(c, b);
+		v0.sub_vectors(a, b);
+		target.cross(&v0);
+
+		let target_length_sq = target.length_sq();
+		if target_length_sq > 0.0 {
+			target.multiply_scalar(1.0 / target_length_sq.sqrt());
+			return;
+		}
+
+		target.set(0.0, 0.0, 0.0);
+	}
+
+	// Barycentric coordinates of point, as the weights of a, b and c in target. Returns false
+	// for a degenerate triangle, with target set to zero.
+	pub fn get_barycoord_from_points(point: &Vector3, a: &Vector3, b: &Vector3, c: &Vector3, target: &mut Vector3) -> bool {
+		let mut v0 = Vector3::new();
+		let mut v1 = Vector3::new();
+		let mut v2 = Vector3::new();
+
+		v0.sub_vectors(c, a);
+		v1.sub_vectors(b, a);
+		v2.sub_vectors(point, a);
+
+		let dot00 = v0.dot(&v0);
+		let dot01 = v0.dot(&v1);
+		let dot02 = v0.dot(&v2);
+		let dot11 = v1.dot(&v1);
+		let dot12 = v1.dot(&v2);
+
+		let denom = dot00 * dot11 - dot01 * dot01;
+
+		if denom == 0.0 {
+			target.set(0.0, 0.0, 0.0);
+			return false;
+		}
+
+		let inv_denom = 1.0 / denom;
+		let u = ( dot11 * dot02 - dot01 * dot12 ) * inv_denom;
+		let v = ( dot00 * dot12 - dot01 * dot02 ) * inv_denom;
+
+		// barycentric coordinates must always sum to 1
+		target.set(1.0 - u - v, v, u);
+		true
+	}
+
+	pub fn get_barycoord(&self, point: &Vector3, target: &mut Vector3) -> bool {
+		Triangle::get_barycoord_from_points(point, &self.a, &self.b, &self.c, target)
+	}
+
+	pub fn get_area(&self) -> f32 {
+		let mut v0 = Vector3::new();
+		let mut v1 = Vector3::new();
+		v0.sub_vectors(&self.c, &self.b);
+		v1.sub_vectors(&self.a, &self.b);
+		v0.cross(&v1);
+
+		v0.length() * 0.5
+	}
+
+	pub fn get_midpoint(&self, target: &mut Vector3) {
+		target.add_vectors(&self.a, &self.b);
+		target.add(&self.c);
+		target.multiply_scalar(1.0 / 3.0);
+	}
+
+	pub fn get_normal(&self, target: &mut Vector3) {
+		Triangle::get_normal_from_points(&self.a, &self.b, &self.c, target);
+	}
+
+	pub fn equals(&self, triangle: &Triangle) -> bool {
+		triangle.a.equals(&self.a) && triangle.b.equals(&self.b) && triangle.c.equals(&self.c)
+	}
+}
+
+impl Default for Triangle {
+	fn default() -> Triangle {
+		Triangle::new()
+	}
+}
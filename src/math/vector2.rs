@@ -1,3 +1,6 @@
+#[cfg(not(feature = "std"))]
+use super::float::Float;
+
 #[derive(Debug, Clone, Copy)]
 pub struct Vector2 {
 	pub x: f32,
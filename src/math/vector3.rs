@@ -2,9 +2,12 @@ use super::matrix4::Matrix4;
 use super::quaternion::Quaternion;
 use super::euler::Euler;
 use super::matrix3::Matrix3;
+#[cfg(feature = "std")]
 use super::super::cameras::camera::Camera;
 use super::math_static::clamp;
 use super::spherical::Spherical;
+#[cfg(not(feature = "std"))]
+use super::float::Float;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Vector3 {
@@ -210,12 +213,14 @@ impl Vector3 {
 		self.z = iz * qw + iw * - qz + ix * - qy - iy * - qx;
 	}
 
+	#[cfg(feature = "std")]
 	pub fn project(&mut self, camera: &Camera) {
 		let mut matrix = Matrix4::new();
 		matrix.multiply_matrices(camera.get_projection_matrix(), camera.get_matrix_world_inverse());
 		self.apply_projection( &matrix );
 	}
 
+	#[cfg(feature = "std")]
 	pub fn unproject(&mut self, camera: &Camera) {
 		let mut matrix = Matrix4::new();
 		let mut matrix1 = Matrix4::new();
@@ -0,0 +1,49 @@
+use super::super::math::color::Color;
+use super::super::math::math_static::smoothstep;
+
+// Fog that goes from none at near to full at far.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fog {
+	pub name: String,
+	pub color: Color,
+	pub near: f32,
+	pub far: f32,
+}
+
+impl Fog {
+	pub fn new(color: &Color, near: f32, far: f32) -> Fog {
+		Fog {
+			name: String::new(),
+			color: *color,
+			near,
+			far,
+		}
+	}
+
+	// How much of the fog color to mix in at a view space depth, from 0 to 1, ramping up
+	// between near and far as the fog shader chunk does.
+	pub fn get_factor(&self, depth: f32) -> f32 {
+		smoothstep(depth, self.near, self.far)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn get_factor_ramps_from_near_to_far() {
+		let fog = Fog::new(&Color::new(), 10.0, 20.0);
+
+		assert_eq!(fog.get_factor(0.0), 0.0);
+		assert_eq!(fog.get_factor(10.0), 0.0);
+		assert_eq!(fog.get_factor(20.0), 1.0);
+		assert_eq!(fog.get_factor(100.0), 1.0);
+
+		// smoothstep, not a straight line
+		assert!(( fog.get_factor(15.0) - 0.5 ).abs() < 1e-6);
+		assert!(( fog.get_factor(12.5) - 0.15625 ).abs() < 1e-6);
+		assert!(( fog.get_factor(17.5) - 0.84375 ).abs() < 1e-6);
+	}
+}
@@ -0,0 +1,47 @@
+use super::super::math::color::Color;
+use super::super::math::math_static::clamp;
+
+// Fog that grows exponentially with the square of the depth, which looks closer to real fog.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FogExp2 {
+	pub name: String,
+	pub color: Color,
+	pub density: f32,
+}
+
+impl FogExp2 {
+	pub fn new(color: &Color, density: f32) -> FogExp2 {
+		FogExp2 {
+			name: String::new(),
+			color: *color,
+			density,
+		}
+	}
+
+	// Like Fog::get_factor, thickening with the square of density * depth.
+	pub fn get_factor(&self, depth: f32) -> f32 {
+		let density_depth = self.density * depth;
+		clamp(1.0 - ( - density_depth * density_depth ).exp(), 0.0, 1.0)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn get_factor_grows_with_density_times_depth() {
+		let fog = FogExp2::new(&Color::new(), 0.25);
+
+		assert_eq!(fog.get_factor(0.0), 0.0);
+		// density * depth = 1
+		assert!(( fog.get_factor(4.0) - ( 1.0 - ( -1.0f32 ).exp() ) ).abs() < 1e-6);
+		assert!(( fog.get_factor(8.0) - ( 1.0 - ( -4.0f32 ).exp() ) ).abs() < 1e-6);
+		assert!(( fog.get_factor(1000.0) - 1.0 ).abs() < 1e-6);
+
+		// the same fog twice as dense reaches that point at half the depth
+		let dense = FogExp2::new(&Color::new(), 0.5);
+		assert_eq!(dense.get_factor(2.0), fog.get_factor(4.0));
+	}
+}
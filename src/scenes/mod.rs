@@ -0,0 +1,3 @@
+pub mod fog;
+pub mod fog_exp2;
+pub mod scene;
\ No newline at end of file
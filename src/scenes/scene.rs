@@ -0,0 +1,183 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use super::super::core::object3d::{HasObject3D, Object3D};
+use super::super::math::color::Color;
+use super::super::materials::material::Material;
+use super::super::textures::texture::Texture;
+use super::super::textures::cube_texture::CubeTexture;
+use super::fog::Fog;
+use super::fog_exp2::FogExp2;
+
+#[derive(Debug, Clone)]
+pub enum Background {
+	Color(Color),
+	Texture(Rc<RefCell<Texture>>),
+	CubeMap(Rc<RefCell<CubeTexture>>),
+}
+
+// Lights everything that doesn't have an environment map of its own.
+#[derive(Debug, Clone)]
+pub enum Environment {
+	Texture(Rc<RefCell<Texture>>),
+	CubeMap(Rc<RefCell<CubeTexture>>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SceneFog {
+	Linear(Fog),
+	Exp2(FogExp2),
+}
+
+impl SceneFog {
+	pub fn get_color(&self) -> &Color {
+		match *self {
+			SceneFog::Linear(ref fog) => &fog.color,
+			SceneFog::Exp2(ref fog) => &fog.color,
+		}
+	}
+
+	pub fn get_factor(&self, depth: f32) -> f32 {
+		match *self {
+			SceneFog::Linear(ref fog) => fog.get_factor(depth),
+			SceneFog::Exp2(ref fog) => fog.get_factor(depth),
+		}
+	}
+
+	// Mixes the fog color into color as it would be at a view space depth.
+	pub fn apply(&self, color: &mut Color, depth: f32) {
+		color.lerp(self.get_color(), self.get_factor(depth));
+	}
+}
+
+// The root of a scene graph, an Object3D with what applies to the scene as a whole.
+#[derive(Clone)]
+pub struct Scene {
+	object3d: Object3D,
+	pub background: Option<Background>,
+	pub environment: Option<Environment>,
+	pub fog: Option<SceneFog>,
+	// Drawn with instead of each object's own material when set.
+	pub override_material: Option<Rc<RefCell<Material>>>,
+	// Whether update brings the world matrices up to date, turn it off to do that yourself.
+	pub auto_update: bool,
+}
+
+impl Scene {
+	pub fn new() -> Scene {
+		Scene {
+			object3d: Object3D::new(),
+			background: None,
+			environment: None,
+			fog: None,
+			override_material: None,
+			auto_update: true,
+		}
+	}
+
+	// What a renderer does with the scene before drawing it.
+	pub fn update(&mut self) {
+		if self.auto_update {
+			self.object3d.update_matrix_world(false);
+		}
+	}
+}
+
+impl Default for Scene {
+	fn default() -> Scene {
+		Scene::new()
+	}
+}
+
+impl HasObject3D for Scene {
+	fn get_object3d(&self) -> &Object3D {
+		&self.object3d
+	}
+
+	fn get_object3d_mut(&mut self) -> &mut Object3D {
+		&mut self.object3d
+	}
+
+	// Textures and the override material stay shared with the original.
+	fn clone_node(&self) -> Rc<RefCell<dyn HasObject3D>> {
+		Rc::new(RefCell::new(Scene {
+			object3d: self.object3d.clone_detached(),
+			background: self.background.clone(),
+			environment: self.environment.clone(),
+			fog: self.fog.clone(),
+			override_material: self.override_material.clone(),
+			auto_update: self.auto_update,
+		}))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use super::super::super::math::vector3::Vector3;
+
+	#[test]
+	fn clone_node_shares_textures() {
+		let texture = Rc::new(RefCell::new(Texture::new()));
+		let cube = Rc::new(RefCell::new(CubeTexture::new(vec![ texture.clone() ])));
+		let material = Rc::new(RefCell::new(Material::new()));
+
+		let mut scene = Scene::new();
+		scene.get_object3d_mut().set_name("scene");
+		scene.background = Some(Background::Texture(texture.clone()));
+		scene.environment = Some(Environment::CubeMap(cube.clone()));
+		scene.override_material = Some(material.clone());
+
+		let copy = scene.clone_node();
+		assert_eq!(copy.borrow().get_object3d().get_name(), "scene");
+		assert_ne!(copy.borrow().get_object3d().get_uuid(), scene.get_object3d().get_uuid());
+
+		// the copy holds the same Rcs rather than copies of the data
+		assert_eq!(Rc::strong_count(&texture), 4);
+		assert_eq!(Rc::strong_count(&cube), 3);
+		assert_eq!(Rc::strong_count(&material), 3);
+
+		drop(copy);
+		assert_eq!(Rc::strong_count(&texture), 3);
+	}
+
+	#[test]
+	fn apply_mixes_in_the_fog_color() {
+		let black = Color { r: 0.0, g: 0.0, b: 0.0 };
+		let fog = SceneFog::Linear(Fog::new(&Color { r: 1.0, g: 0.5, b: 0.0 }, 10.0, 20.0));
+
+		let mut color = black;
+		fog.apply(&mut color, 5.0);
+		assert_eq!(color, black);
+
+		fog.apply(&mut color, 15.0);
+		assert_eq!(color, Color { r: 0.5, g: 0.25, b: 0.0 });
+
+		let mut color = Color::new();
+		fog.apply(&mut color, 30.0);
+		assert_eq!(color, *fog.get_color());
+
+		let fog = SceneFog::Exp2(FogExp2::new(&black, 0.25));
+		let factor = 1.0 - ( -1.0f32 ).exp();
+		assert_eq!(fog.get_factor(4.0), factor);
+
+		let mut color = Color::new();
+		fog.apply(&mut color, 4.0);
+		assert!(( color.r - ( 1.0 - factor ) ).abs() < 1e-6);
+		assert_eq!(color.r, color.b);
+	}
+
+	#[test]
+	fn update_respects_auto_update() {
+		let mut scene = Scene::new();
+		scene.get_object3d_mut().set_position(&Vector3 { x: 1.0, y: 2.0, z: 3.0 });
+
+		scene.auto_update = false;
+		scene.update();
+		assert_eq!(scene.get_object3d().get_matrix_world().elements[ 12 ], 0.0);
+
+		scene.auto_update = true;
+		scene.update();
+		let e = scene.get_object3d().get_matrix_world().elements;
+		assert_eq!(( e[ 12 ], e[ 13 ], e[ 14 ] ), ( 1.0, 2.0, 3.0 ));
+	}
+}
@@ -0,0 +1,45 @@
+extern crate uuid;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::Ordering;
+use self::uuid::Uuid;
+use super::texture::{Texture, TEXTURE_ID};
+use super::super::core::change_tracker::next_version;
+
+// Six faces, in the order +x, -x, +y, -y, +z, -z. Ids are shared with Texture.
+#[derive(Debug, Clone)]
+pub struct CubeTexture {
+	id: usize,
+	uuid: Uuid,
+	pub name: String,
+	pub faces: Vec<Rc<RefCell<Texture>>>,
+	version: u64,
+}
+
+impl CubeTexture {
+	pub fn new(faces: Vec<Rc<RefCell<Texture>>>) -> CubeTexture {
+		CubeTexture {
+			id: TEXTURE_ID.fetch_add(1, Ordering::Relaxed),
+			uuid: Uuid::new_v4(),
+			name: String::new(),
+			faces,
+			version: next_version(),
+		}
+	}
+
+	pub fn get_id(&self) -> usize {
+		self.id
+	}
+
+	pub fn get_uuid(&self) -> &Uuid {
+		&self.uuid
+	}
+
+	pub fn get_version(&self) -> u64 {
+		self.version
+	}
+
+	pub fn needs_update(&mut self) {
+		self.version = next_version();
+	}
+}
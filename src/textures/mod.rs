@@ -0,0 +1,2 @@
+pub mod cube_texture;
+pub mod texture;
\ No newline at end of file
@@ -0,0 +1,51 @@
+extern crate uuid;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use self::uuid::Uuid;
+use super::super::core::change_tracker::next_version;
+
+pub(crate) static TEXTURE_ID: AtomicUsize = AtomicUsize::new(0);
+
+// A handle for image data that lives with the renderer. Call needs_update when the image
+// changes so it gets uploaded again.
+#[derive(Debug, Clone)]
+pub struct Texture {
+	id: usize,
+	uuid: Uuid,
+	pub name: String,
+	pub flip_y: bool,
+	version: u64,
+}
+
+impl Texture {
+	pub fn new() -> Texture {
+		Texture {
+			id: TEXTURE_ID.fetch_add(1, Ordering::Relaxed),
+			uuid: Uuid::new_v4(),
+			name: String::new(),
+			flip_y: true,
+			version: next_version(),
+		}
+	}
+
+	pub fn get_id(&self) -> usize {
+		self.id
+	}
+
+	pub fn get_uuid(&self) -> &Uuid {
+		&self.uuid
+	}
+
+	pub fn get_version(&self) -> u64 {
+		self.version
+	}
+
+	pub fn needs_update(&mut self) {
+		self.version = next_version();
+	}
+}
+
+impl Default for Texture {
+	fn default() -> Texture {
+		Texture::new()
+	}
+}
@@ -0,0 +1,917 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::rc::Rc;
+use super::super::core::buffer_attribute::{Attribute, BufferAttribute};
+use super::super::core::buffer_geometry::BufferGeometry;
+use super::super::core::interleaved_buffer::InterleavedBuffer;
+use super::super::core::interleaved_buffer_attribute::InterleavedBufferAttribute;
+use super::super::math::vector3::Vector3;
+use super::super::math::math_static::clamp;
+
+// Geometries and attributes are referred to by their position in the slice passed in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeometryError {
+	IndexMismatch(usize),
+	AttributeMismatch(usize, String),
+	CountMismatch(usize),
+	MissingAttribute(String),
+	IncompleteTriangles(usize),
+	ShortAttribute(String),
+}
+
+impl fmt::Display for GeometryError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			GeometryError::IndexMismatch(i) => write!(f, "geometry {} must have an index if and only if the first one has", i),
+			GeometryError::AttributeMismatch(i, ref name) => write!(f, "attribute \"{}\" of geometry {} is missing or doesn't match the first geometry", name, i),
+			GeometryError::CountMismatch(i) => write!(f, "attribute {} has a different count than the first one", i),
+			GeometryError::MissingAttribute(ref name) => write!(f, "the geometry has no \"{}\" attribute", name),
+			GeometryError::IncompleteTriangles(count) => write!(f, "{} vertices don't make whole triangles", count),
+			GeometryError::ShortAttribute(ref name) => write!(f, "the \"{}\" attribute has fewer items than \"position\"", name),
+		}
+	}
+}
+
+impl Error for GeometryError {}
+
+// How the index, or the vertices without one, make up triangles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrianglesDrawMode {
+	Triangles,
+	TriangleStrip,
+	TriangleFan,
+}
+
+fn get_vertex_count(geometry: &BufferGeometry) -> usize {
+	if let Some(position) = geometry.get_attribute("position") {
+		return position.get_count();
+	}
+
+	match geometry.get_attribute_names().first() {
+		Some(name) => geometry.get_attribute(name).unwrap().get_count(),
+		None => 0,
+	}
+}
+
+// The index, or 0..count for a geometry without one.
+fn get_indices(geometry: &BufferGeometry) -> Vec<u32> {
+	match geometry.get_index() {
+		Some(index) => index.array.clone(),
+		None => ( 0..get_vertex_count(geometry) as u32 ).collect(),
+	}
+}
+
+// Concatenates the geometries, which all need the same attributes and either all or none an
+// index. With use_groups every input becomes a group, with its position as material index.
+// The result has f32 attributes.
+pub fn merge_geometries(geometries: &[&BufferGeometry], use_groups: bool) -> Result<BufferGeometry, GeometryError> {
+	let mut merged = BufferGeometry::new();
+
+	let first = match geometries.first() {
+		Some(first) => first,
+		None => return Ok(merged),
+	};
+
+	let is_indexed = first.get_index().is_some();
+	let names = first.get_attribute_names();
+
+	for (i, geometry) in geometries.iter().enumerate() {
+		if geometry.get_index().is_some() != is_indexed {
+			return Err(GeometryError::IndexMismatch(i));
+		}
+
+		for name in &names {
+			let expected = first.get_attribute(name).unwrap();
+
+			match geometry.get_attribute(name) {
+				Some(attribute) if attribute.get_item_size() == expected.get_item_size() && attribute.is_normalized() == expected.is_normalized() => {},
+				_ => return Err(GeometryError::AttributeMismatch(i, name.clone())),
+			}
+		}
+
+		if let Some(name) = geometry.get_attribute_names().into_iter().find(|name| !first.has_attribute(name)) {
+			return Err(GeometryError::AttributeMismatch(i, name));
+		}
+	}
+
+	for name in &names {
+		let expected = first.get_attribute(name).unwrap();
+		let mut array = vec![];
+
+		for geometry in geometries {
+			array.extend(geometry.get_attribute(name).unwrap().to_f32_array());
+		}
+
+		// the values are already scaled back, so the f32 copy isn't normalized
+		merged.set_attribute(name, BufferAttribute::new(array, expected.get_item_size(), false));
+	}
+
+	let mut index = vec![];
+	let mut vertex_offset = 0;
+	let mut offset = 0;
+
+	for (i, geometry) in geometries.iter().enumerate() {
+		let vertex_count = get_vertex_count(geometry);
+
+		let count = match geometry.get_index() {
+			Some(geometry_index) => {
+				index.extend(geometry_index.array.iter().map(|j| j + vertex_offset as u32));
+				geometry_index.array.len()
+			},
+			None => vertex_count,
+		};
+
+		if use_groups {
+			merged.add_group(offset, count, i);
+		}
+
+		vertex_offset += vertex_count;
+		offset += count;
+	}
+
+	if is_indexed {
+		merged.set_index(Some(BufferAttribute::new(index, 1, false)));
+	}
+
+	Ok(merged)
+}
+
+// Snaps a value to the tolerance grid. Values that straddle a grid line aren't welded, even
+// if they are closer than tolerance. Without a tolerance only identical values are.
+fn quantize(value: f32, tolerance: f32) -> i64 {
+	if tolerance > 0.0 {
+		( value / tolerance ).round() as i64
+	} else {
+		// + 0.0 folds -0.0 into 0.0
+		i64::from(( value + 0.0 ).to_bits())
+	}
+}
+
+// Welds vertices whose attributes all match within tolerance, keeping the first of each.
+// Vertices are looked up by hash, so this is linear in the vertex count. The result is
+// indexed, with the same index length as before so groups and the draw range still apply,
+// and has f32 attributes.
+pub fn merge_vertices(geometry: &BufferGeometry, tolerance: f32) -> BufferGeometry {
+	let names = geometry.get_attribute_names();
+	let attributes: Vec<&dyn Attribute> = names.iter().map(|name| geometry.get_attribute(name).unwrap()).collect();
+	let indices = get_indices(geometry);
+
+	let mut hash_to_index: HashMap<Vec<i64>, u32> = HashMap::with_capacity(indices.len());
+	let mut arrays: Vec<Vec<f32>> = attributes.iter().map(|_| vec![]).collect();
+	let mut new_indices = Vec::with_capacity(indices.len());
+	let mut next_index = 0u32;
+	let mut key = vec![];
+
+	for i in indices {
+		let i = i as usize;
+
+		key.clear();
+
+		for attribute in &attributes {
+			for c in 0..attribute.get_item_size() {
+				key.push(quantize(attribute.get_component(i, c), tolerance));
+			}
+		}
+
+		if let Some(&index) = hash_to_index.get(&key) {
+			new_indices.push(index);
+			continue;
+		}
+
+		for (attribute, array) in attributes.iter().zip(arrays.iter_mut()) {
+			for c in 0..attribute.get_item_size() {
+				array.push(attribute.get_component(i, c));
+			}
+		}
+
+		hash_to_index.insert(key.clone(), next_index);
+		new_indices.push(next_index);
+		next_index += 1;
+	}
+
+	let mut result = BufferGeometry::new();
+	result.name = geometry.name.clone();
+
+	for ((name, attribute), array) in names.iter().zip(attributes.iter()).zip(arrays) {
+		result.set_attribute(name, BufferAttribute::new(array, attribute.get_item_size(), false));
+	}
+
+	result.set_index(Some(BufferAttribute::new(new_indices, 1, false)));
+
+	for group in geometry.get_groups() {
+		result.add_group(group.start, group.count, group.material_index);
+	}
+
+	let draw_range = geometry.get_draw_range();
+	result.set_draw_range(draw_range.start, draw_range.count);
+
+	result
+}
+
+// Turns a strip or a fan into a plain triangle list. The groups are dropped, as they no longer
+// line up with the index.
+pub fn to_triangles_draw_mode(geometry: &BufferGeometry, draw_mode: TrianglesDrawMode) -> BufferGeometry {
+	let mut result = geometry.clone();
+
+	if draw_mode == TrianglesDrawMode::Triangles {
+		return result;
+	}
+
+	let indices = get_indices(geometry);
+	let mut new_indices = vec![];
+
+	if indices.len() >= 3 {
+		match draw_mode {
+			TrianglesDrawMode::TriangleStrip => {
+				for i in 0..indices.len() - 2 {
+					// every other triangle is flipped to keep the winding
+					if i % 2 == 0 {
+						new_indices.extend_from_slice(&[ indices[ i ], indices[ i + 1 ], indices[ i + 2 ] ]);
+					} else {
+						new_indices.extend_from_slice(&[ indices[ i + 2 ], indices[ i + 1 ], indices[ i ] ]);
+					}
+				}
+			},
+			TrianglesDrawMode::TriangleFan => {
+				for i in 1..indices.len() - 1 {
+					new_indices.extend_from_slice(&[ indices[ 0 ], indices[ i ], indices[ i + 1 ] ]);
+				}
+			},
+			TrianglesDrawMode::Triangles => {},
+		}
+	}
+
+	result.set_index(Some(BufferAttribute::new(new_indices, 1, false)));
+	result.clear_groups();
+	result.set_draw_range(0, None);
+
+	result
+}
+
+// Packs the attributes, which need the same count, into one f32 buffer in the order given.
+pub fn interleave_attributes(attributes: &[&dyn Attribute]) -> Result<Vec<InterleavedBufferAttribute>, GeometryError> {
+	let count = match attributes.first() {
+		Some(first) => first.get_count(),
+		None => return Ok(vec![]),
+	};
+
+	if let Some(i) = attributes.iter().position(|attribute| attribute.get_count() != count) {
+		return Err(GeometryError::CountMismatch(i));
+	}
+
+	let stride: usize = attributes.iter().map(|attribute| attribute.get_item_size()).sum();
+	let mut array = Vec::with_capacity(count * stride);
+
+	for i in 0..count {
+		for attribute in attributes {
+			for c in 0..attribute.get_item_size() {
+				array.push(attribute.get_component(i, c));
+			}
+		}
+	}
+
+	let buffer = Rc::new(RefCell::new(InterleavedBuffer::new(array, stride)));
+	let mut offset = 0;
+
+	Ok(attributes.iter().map(|attribute| {
+		let interleaved = InterleavedBufferAttribute::new(&buffer, attribute.get_item_size(), offset, attribute.is_normalized());
+		offset += attribute.get_item_size();
+		interleaved
+	}).collect())
+}
+
+// Gives every interleaved attribute of the geometry its own array.
+pub fn deinterleave_attributes(geometry: &mut BufferGeometry) {
+	for name in geometry.get_attribute_names() {
+		let attribute = match geometry.get_attribute(&name) {
+			Some(attribute) if attribute.is_interleaved() => attribute.clone_attribute(),
+			_ => continue,
+		};
+
+		geometry.set_attribute_boxed(&name, attribute);
+	}
+}
+
+fn read_vector3(array: &[f32], i: usize, target: &mut Vector3) {
+	target.copy_from_array(array, Some(i * 3));
+}
+
+// Per triangle flags of compute_tangents, named as in MikkTSpace.
+const ORIENT_PRESERVING: u8 = 1;
+const GROUP_WITH_ANY: u8 = 2;
+const MARK_DEGENERATE: u8 = 4;
+
+#[derive(Debug, Clone)]
+struct TriInfo {
+	// the face across the edge from corner k to corner k + 1
+	neighbors: [ Option<usize>; 3 ],
+	groups: [ Option<usize>; 3 ],
+	os: Vector3,
+	ot: Vector3,
+	flags: u8,
+}
+
+// Faces that meet at a vertex, connected by edges and with the same uv winding.
+#[derive(Debug, Clone)]
+struct TangentGroup {
+	vertex: usize,
+	orient_preserving: bool,
+	faces: Vec<usize>,
+}
+
+fn not_zero(x: f32) -> bool {
+	x.abs() > f32::MIN_POSITIVE
+}
+
+fn normalize_not_zero(v: &mut Vector3) {
+	if not_zero(v.x) || not_zero(v.y) || not_zero(v.z) {
+		let length = v.length();
+		v.multiply_scalar(1.0 / length);
+	}
+}
+
+// v without its part along n, normalized.
+fn project(n: &Vector3, v: &Vector3) -> Vector3 {
+	let mut projected = *v;
+	projected.add_scaled_vector(n, - n.dot(v));
+	normalize_not_zero(&mut projected);
+	projected
+}
+
+fn corner_of(verts: &[usize], face: usize, vertex: usize) -> usize {
+	( 0..3 ).find(|&k| verts[ face * 3 + k ] == vertex).unwrap()
+}
+
+// AssignRecur, adds the face to the group and follows the two edges around the group's
+// vertex, unless the corner is taken or the uv winding differs.
+fn assign_recur(verts: &[usize], triangles: &mut [TriInfo], group: &mut TangentGroup, g: usize, face: usize) {
+	let k = corner_of(verts, face, group.vertex);
+	let info = &mut triangles[ face ];
+
+	if info.groups[ k ].is_some() {
+		return;
+	}
+
+	// a face without a usable uv direction takes the winding of the first group to reach it
+	if info.flags & GROUP_WITH_ANY != 0 && info.groups.iter().all(Option::is_none) {
+		info.flags &= !ORIENT_PRESERVING;
+
+		if group.orient_preserving {
+			info.flags |= ORIENT_PRESERVING;
+		}
+	}
+
+	if ( info.flags & ORIENT_PRESERVING != 0 ) != group.orient_preserving {
+		return;
+	}
+
+	group.faces.push(face);
+	info.groups[ k ] = Some(g);
+
+	let neighbors = [ info.neighbors[ k ], info.neighbors[ ( k + 2 ) % 3 ] ];
+
+	for &neighbor in neighbors.iter().flatten() {
+		assign_recur(verts, triangles, group, g, neighbor);
+	}
+}
+
+// EvalTspace, the corner angle weighted sum of the faces' tangents in the normal's plane.
+fn eval_tangent(faces: &[usize], vertex: usize, verts: &[usize], triangles: &[TriInfo], positions: &[f32], normals: &[f32]) -> Vector3 {
+	let mut sum = Vector3::new();
+	let mut n = Vector3::new();
+	let mut p0 = Vector3::new();
+	let mut p1 = Vector3::new();
+	let mut p2 = Vector3::new();
+	let mut v1 = Vector3::new();
+	let mut v2 = Vector3::new();
+
+	read_vector3(normals, vertex, &mut n);
+
+	for &f in faces {
+		if triangles[ f ].flags & GROUP_WITH_ANY != 0 {
+			continue;
+		}
+
+		let k = corner_of(verts, f, vertex);
+		read_vector3(positions, verts[ f * 3 + ( k + 2 ) % 3 ], &mut p0);
+		read_vector3(positions, vertex, &mut p1);
+		read_vector3(positions, verts[ f * 3 + ( k + 1 ) % 3 ], &mut p2);
+
+		v1.sub_vectors(&p0, &p1);
+		v2.sub_vectors(&p2, &p1);
+		let angle = clamp(project(&n, &v1).dot(&project(&n, &v2)), - 1.0, 1.0).acos();
+
+		sum.add_scaled_vector(&project(&n, &triangles[ f ].os), angle);
+	}
+
+	normalize_not_zero(&mut sum);
+	sum
+}
+
+// Computes a "tangent" attribute with MikkTSpace, ported from the reference implementation so
+// the tangents match those of baking tools. Corners with the same position, normal and uv are
+// one vertex. The faces around a vertex are grouped by the edges that connect them and by their
+// uv winding, and every corner gets the angle weighted sum of the tangents of its group, in the
+// plane of the normal. w is 1 for faces whose uvs run counter clockwise and -1 otherwise.
+//
+// An indexed geometry is made non-indexed first, since corners sharing a vertex can end up
+// with different tangents. Run merge_vertices afterwards to share them again.
+pub fn compute_tangents(geometry: &mut BufferGeometry) -> Result<(), GeometryError> {
+	for name in &[ "position", "normal", "uv" ] {
+		if !geometry.has_attribute(name) {
+			return Err(GeometryError::MissingAttribute(name.to_string()));
+		}
+	}
+
+	let vertex_count = match geometry.get_index() {
+		Some(index) => index.array.len(),
+		None => geometry.get_attribute("position").unwrap().get_count(),
+	};
+
+	if vertex_count % 3 != 0 {
+		return Err(GeometryError::IncompleteTriangles(vertex_count));
+	}
+
+	let position_count = geometry.get_attribute("position").unwrap().get_count();
+
+	for name in &[ "normal", "uv" ] {
+		if geometry.get_attribute(name).unwrap().get_count() < position_count {
+			return Err(GeometryError::ShortAttribute(name.to_string()));
+		}
+	}
+
+	if geometry.get_index().is_some() {
+		*geometry = geometry.to_non_indexed();
+	}
+
+	let positions = geometry.get_attribute("position").unwrap().to_f32_array();
+	let normals = geometry.get_attribute("normal").unwrap().to_f32_array();
+	let uv = geometry.get_attribute("uv").unwrap();
+	let mut uvs = Vec::with_capacity(uv.get_count() * 2);
+
+	for i in 0..uv.get_count() {
+		uvs.push(uv.get_x(i));
+		uvs.push(uv.get_y(i));
+	}
+
+	let count = positions.len() / 3;
+	let face_count = count / 3;
+
+	// every corner points at the first corner with the same position, normal and uv
+	let mut welded: HashMap<[ u32; 8 ], usize> = HashMap::with_capacity(count);
+	let verts: Vec<usize> = ( 0..count ).map(|i| {
+		let mut key = [ 0; 8 ];
+
+		for c in 0..3 {
+			// + 0.0 folds -0.0 into 0.0
+			key[ c ] = ( positions[ i * 3 + c ] + 0.0 ).to_bits();
+			key[ c + 3 ] = ( normals[ i * 3 + c ] + 0.0 ).to_bits();
+		}
+
+		key[ 6 ] = ( uvs[ i * 2 ] + 0.0 ).to_bits();
+		key[ 7 ] = ( uvs[ i * 2 + 1 ] + 0.0 ).to_bits();
+
+		*welded.entry(key).or_insert(i)
+	}).collect();
+
+	let mut triangles = Vec::with_capacity(face_count);
+	let mut p = [ Vector3::new(); 3 ];
+	let mut d1 = Vector3::new();
+	let mut d2 = Vector3::new();
+
+	for face in 0..face_count {
+		let a = face * 3;
+		let mut info = TriInfo {
+			neighbors: [ None; 3 ],
+			groups: [ None; 3 ],
+			os: Vector3::new(),
+			ot: Vector3::new(),
+			flags: GROUP_WITH_ANY,
+		};
+
+		for (k, point) in p.iter_mut().enumerate() {
+			read_vector3(&positions, verts[ a + k ], point);
+		}
+
+		if p[ 0 ].equals(&p[ 1 ]) || p[ 0 ].equals(&p[ 2 ]) || p[ 1 ].equals(&p[ 2 ]) {
+			info.flags = MARK_DEGENERATE;
+			triangles.push(info);
+			continue;
+		}
+
+		let uv_of = |k: usize, c: usize| uvs[ verts[ a + k ] * 2 + c ];
+		let t21x = uv_of(1, 0) - uv_of(0, 0);
+		let t21y = uv_of(1, 1) - uv_of(0, 1);
+		let t31x = uv_of(2, 0) - uv_of(0, 0);
+		let t31y = uv_of(2, 1) - uv_of(0, 1);
+
+		d1.sub_vectors(&p[ 1 ], &p[ 0 ]);
+		d2.sub_vectors(&p[ 2 ], &p[ 0 ]);
+
+		let signed_area = t21x * t31y - t21y * t31x;
+
+		info.os.copy(&d1);
+		info.os.multiply_scalar(t31y);
+		info.os.add_scaled_vector(&d2, - t21y);
+		info.ot.copy(&d2);
+		info.ot.multiply_scalar(t21x);
+		info.ot.add_scaled_vector(&d1, - t31x);
+
+		if signed_area > 0.0 {
+			info.flags |= ORIENT_PRESERVING;
+		}
+
+		if not_zero(signed_area) {
+			let abs_area = signed_area.abs();
+			let length_os = info.os.length();
+			let length_ot = info.ot.length();
+			let sign = if signed_area > 0.0 { 1.0 } else { - 1.0 };
+
+			if not_zero(length_os) {
+				info.os.multiply_scalar(sign / length_os);
+			}
+
+			if not_zero(length_ot) {
+				info.ot.multiply_scalar(sign / length_ot);
+			}
+
+			if not_zero(length_os / abs_area) && not_zero(length_ot / abs_area) {
+				info.flags &= !GROUP_WITH_ANY;
+			}
+		}
+
+		triangles.push(info);
+	}
+
+	let degenerate: Vec<bool> = triangles.iter().map(|info| info.flags & MARK_DEGENERATE != 0).collect();
+
+	// faces are neighbors across an edge they run along in opposite directions, the first
+	// free pair wins where more than two faces share an edge
+	let mut edges = Vec::with_capacity(count);
+
+	for face in ( 0..face_count ).filter(|&face| !degenerate[ face ]) {
+		for k in 0..3 {
+			let i0 = verts[ face * 3 + k ];
+			let i1 = verts[ face * 3 + ( k + 1 ) % 3 ];
+			edges.push(( i0.min(i1), i0.max(i1), face, k ));
+		}
+	}
+
+	edges.sort();
+
+	for e in 0..edges.len() {
+		let ( lo, hi, f, k ) = edges[ e ];
+
+		if triangles[ f ].neighbors[ k ].is_some() {
+			continue;
+		}
+
+		let a0 = verts[ f * 3 + k ];
+		let a1 = verts[ f * 3 + ( k + 1 ) % 3 ];
+
+		for &( _, _, t, j ) in edges[ e + 1.. ].iter().take_while(|edge| edge.0 == lo && edge.1 == hi) {
+			if triangles[ t ].neighbors[ j ].is_none() && verts[ t * 3 + j ] == a1 && verts[ t * 3 + ( j + 1 ) % 3 ] == a0 {
+				triangles[ f ].neighbors[ k ] = Some(t);
+				triangles[ t ].neighbors[ j ] = Some(f);
+				break;
+			}
+		}
+	}
+
+	let mut groups: Vec<TangentGroup> = vec![];
+
+	for f in 0..face_count {
+		if triangles[ f ].flags & ( GROUP_WITH_ANY | MARK_DEGENERATE ) != 0 {
+			continue;
+		}
+
+		for k in 0..3 {
+			if triangles[ f ].groups[ k ].is_some() {
+				continue;
+			}
+
+			let g = groups.len();
+			let mut group = TangentGroup {
+				vertex: verts[ f * 3 + k ],
+				orient_preserving: triangles[ f ].flags & ORIENT_PRESERVING != 0,
+				faces: vec![ f ],
+			};
+
+			triangles[ f ].groups[ k ] = Some(g);
+
+			let neighbors = [ triangles[ f ].neighbors[ k ], triangles[ f ].neighbors[ ( k + 2 ) % 3 ] ];
+
+			for &neighbor in neighbors.iter().flatten() {
+				assign_recur(&verts, &mut triangles, &mut group, g, neighbor);
+			}
+
+			groups.push(group);
+		}
+	}
+
+	let mut corner_tangents: Vec<Option<( Vector3, bool )>> = vec![ None; count ];
+	let mut n = Vector3::new();
+	let mut members = vec![];
+
+	for (g, group) in groups.iter().enumerate() {
+		read_vector3(&normals, group.vertex, &mut n);
+
+		// faces whose tangents point apart go into separate subgroups, with MikkTSpace's default
+		// threshold of 180 degrees that's only those pointing exactly opposite
+		let mut subgroups: Vec<( Vec<usize>, Vector3 )> = vec![];
+
+		for &f in &group.faces {
+			let k = ( 0..3 ).find(|&k| triangles[ f ].groups[ k ] == Some(g)).unwrap();
+			let os = project(&n, &triangles[ f ].os);
+			let ot = project(&n, &triangles[ f ].ot);
+
+			members.clear();
+
+			for &t in &group.faces {
+				let any = ( triangles[ f ].flags | triangles[ t ].flags ) & GROUP_WITH_ANY != 0;
+
+				if any || t == f || ( os.dot(&project(&n, &triangles[ t ].os)) > - 1.0 && ot.dot(&project(&n, &triangles[ t ].ot)) > - 1.0 ) {
+					members.push(t);
+				}
+			}
+
+			members.sort();
+
+			let l = match subgroups.iter().position(|subgroup| subgroup.0 == members) {
+				Some(l) => l,
+				None => {
+					let tangent = eval_tangent(&members, group.vertex, &verts, &triangles, &positions, &normals);
+					subgroups.push(( members.clone(), tangent ));
+					subgroups.len() - 1
+				},
+			};
+
+			corner_tangents[ f * 3 + k ] = Some(( subgroups[ l ].1, group.orient_preserving ));
+		}
+	}
+
+	// a degenerate face takes the tangents of the first other corner on the same vertex
+	let mut first_corners = vec![ None; count ];
+
+	for i in ( 0..count ).filter(|i| !degenerate[ i / 3 ]) {
+		if first_corners[ verts[ i ] ].is_none() {
+			first_corners[ verts[ i ] ] = Some(i);
+		}
+	}
+
+	for i in ( 0..count ).filter(|i| degenerate[ i / 3 ]) {
+		if let Some(j) = first_corners[ verts[ i ] ] {
+			corner_tangents[ i ] = corner_tangents[ j ];
+		}
+	}
+
+	let mut tangents = Vec::with_capacity(count * 4);
+	let mut tangent = Vector3::new();
+	let mut v1 = Vector3::new();
+
+	for (i, corner) in corner_tangents.iter().enumerate() {
+		let orient_preserving = match *corner {
+			Some(( t, orient_preserving )) => {
+				tangent.copy(&t);
+				orient_preserving
+			},
+			None => {
+				tangent.set(0.0, 0.0, 0.0);
+				triangles[ i / 3 ].flags & ORIENT_PRESERVING != 0
+			},
+		};
+
+		// degenerate uvs, any direction in the normal's plane will do
+		if tangent.length_sq() == 0.0 {
+			read_vector3(&normals, i, &mut n);
+			v1.set(if n.x.abs() < 0.9 { 1.0 } else { 0.0 }, if n.x.abs() < 0.9 { 0.0 } else { 1.0 }, 0.0);
+			tangent.cross_vectors(&n, &v1);
+			tangent.normalize();
+		}
+
+		tangents.extend_from_slice(&[ tangent.x, tangent.y, tangent.z, if orient_preserving { 1.0 } else { - 1.0 } ]);
+	}
+
+	geometry.set_attribute("tangent", BufferAttribute::new(tangents, 4, false));
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn geometry(positions: Vec<f32>, index: Option<Vec<u32>>) -> BufferGeometry {
+		let count = positions.len() / 3;
+		let mut geometry = BufferGeometry::new();
+		geometry.set_attribute("position", BufferAttribute::new(positions, 3, false));
+		geometry.set_attribute("normal", BufferAttribute::new(vec![ 0.0f32; count * 3 ], 3, false));
+		geometry.set_attribute("uv", BufferAttribute::new(vec![ 0.0f32; count * 2 ], 2, false));
+		geometry.set_index(index.map(|index| BufferAttribute::new(index, 1, false)));
+		geometry
+	}
+
+	#[test]
+	fn compute_tangents_rejects_incomplete_triangles() {
+		let mut g = geometry(vec![ 0.0; 12 ], None);
+		assert_eq!(compute_tangents(&mut g), Err(GeometryError::IncompleteTriangles(4)));
+		assert!(!g.has_attribute("tangent"));
+
+		let mut g = geometry(vec![ 0.0; 9 ], Some(vec![ 0, 1, 2, 0, 1 ]));
+		assert_eq!(compute_tangents(&mut g), Err(GeometryError::IncompleteTriangles(5)));
+		assert!(g.get_index().is_some());
+	}
+
+	#[test]
+	fn compute_tangents_rejects_short_attributes() {
+		let mut g = geometry(vec![ 0.0; 18 ], None);
+		g.set_attribute("uv", BufferAttribute::new(vec![ 0.0f32; 6 ], 2, false));
+		assert_eq!(compute_tangents(&mut g), Err(GeometryError::ShortAttribute("uv".to_string())));
+
+		g.set_attribute("normal", BufferAttribute::new(vec![ 0.0f32; 9 ], 3, false));
+		assert_eq!(compute_tangents(&mut g), Err(GeometryError::ShortAttribute("normal".to_string())));
+		assert!(!g.has_attribute("tangent"));
+	}
+
+	// Faces in the xy plane facing +z, with the given uvs.
+	fn flat(positions: Vec<f32>, uvs: Vec<f32>, index: Option<Vec<u32>>) -> BufferGeometry {
+		let mut g = geometry(positions, index);
+		let count = g.get_attribute("position").unwrap().get_count();
+		let normals = ( 0..count ).flat_map(|_| vec![ 0.0, 0.0, 1.0 ]).collect();
+		g.set_attribute("normal", BufferAttribute::new(normals, 3, false));
+		g.set_attribute("uv", BufferAttribute::new(uvs, 2, false));
+		g
+	}
+
+	fn assert_tangent(g: &BufferGeometry, i: usize, expected: [ f32; 4 ]) {
+		let tangent = g.get_attribute("tangent").unwrap();
+		let actual = [ tangent.get_x(i), tangent.get_y(i), tangent.get_z(i), tangent.get_w(i) ];
+
+		for c in 0..4 {
+			assert!(( actual[ c ] - expected[ c ] ).abs() < 1e-5, "corner {}: {:?} != {:?}", i, actual, expected);
+		}
+	}
+
+	const QUAD: [ f32; 12 ] = [ 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0 ];
+
+	#[test]
+	fn compute_tangents_follows_u() {
+		let mut g = flat(QUAD.to_vec(), vec![ 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0 ], Some(vec![ 0, 1, 2, 0, 2, 3 ]));
+		compute_tangents(&mut g).unwrap();
+
+		assert!(g.get_index().is_none());
+
+		for i in 0..6 {
+			assert_tangent(&g, i, [ 1.0, 0.0, 0.0, 1.0 ]);
+		}
+	}
+
+	#[test]
+	fn compute_tangents_flips_w_for_mirrored_uvs() {
+		// u runs along -x, so the uvs wind clockwise and the bitangent has to be flipped to
+		// point along v
+		let mut g = flat(QUAD.to_vec(), vec![ 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0 ], Some(vec![ 0, 1, 2, 0, 2, 3 ]));
+		compute_tangents(&mut g).unwrap();
+
+		for i in 0..6 {
+			assert_tangent(&g, i, [ - 1.0, 0.0, 0.0, - 1.0 ]);
+		}
+	}
+
+	#[test]
+	fn compute_tangents_splits_a_mirror_seam() {
+		// two quads sharing the edge at x = 0, mirrored around it
+		let positions = vec![
+			- 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, - 1.0, 1.0, 0.0,
+			0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0,
+		];
+		let uvs = vec![
+			1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0,
+			0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0,
+		];
+		let mut g = flat(positions, uvs, Some(vec![ 0, 1, 2, 0, 2, 3, 4, 5, 6, 4, 6, 7 ]));
+		compute_tangents(&mut g).unwrap();
+
+		for i in 0..6 {
+			assert_tangent(&g, i, [ - 1.0, 0.0, 0.0, - 1.0 ]);
+			assert_tangent(&g, i + 6, [ 1.0, 0.0, 0.0, 1.0 ]);
+		}
+	}
+
+	#[test]
+	fn compute_tangents_groups_by_connected_faces() {
+		// two faces that only touch at the origin, where their corners are the same vertex,
+		// don't share a tangent there
+		let positions = vec![ 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, - 1.0, 0.0, 0.0, 0.0, - 1.0, 0.0 ];
+		let uvs = vec![ 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, - 1.0, 1.0, 0.0 ];
+		let mut g = flat(positions, uvs, None);
+		compute_tangents(&mut g).unwrap();
+
+		for i in 0..3 {
+			assert_tangent(&g, i, [ 1.0, 0.0, 0.0, 1.0 ]);
+			assert_tangent(&g, i + 3, [ 0.0, - 1.0, 0.0, 1.0 ]);
+		}
+	}
+
+	#[test]
+	fn compute_tangents_averages_connected_faces() {
+		// two faces sharing an edge whose u directions are 45 degrees apart, weighted equally
+		// on the shared corners
+		let positions = vec![ 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, - 1.0, 0.0, 0.0 ];
+		let uvs = vec![ 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, - 1.0, 1.0 ];
+		let mut g = flat(positions, uvs, Some(vec![ 0, 1, 2, 0, 2, 3 ]));
+		compute_tangents(&mut g).unwrap();
+
+		let half = ::std::f32::consts::FRAC_1_SQRT_2;
+		let ( sin, cos ) = ( ::std::f32::consts::PI / 8.0 ).sin_cos();
+
+		for &i in &[ 0, 2, 3, 4 ] {
+			assert_tangent(&g, i, [ cos, sin, 0.0, 1.0 ]);
+		}
+
+		assert_tangent(&g, 1, [ 1.0, 0.0, 0.0, 1.0 ]);
+		assert_tangent(&g, 5, [ half, half, 0.0, 1.0 ]);
+	}
+
+	#[test]
+	fn compute_tangents_copies_into_degenerate_faces() {
+		let positions = vec![ 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0 ];
+		let uvs = vec![ 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0 ];
+		let mut g = flat(positions, uvs, None);
+		compute_tangents(&mut g).unwrap();
+
+		for i in 0..6 {
+			assert_tangent(&g, i, [ 1.0, 0.0, 0.0, 1.0 ]);
+		}
+	}
+
+	#[test]
+	fn merge_vertices_welds_within_tolerance() {
+		// two triangles of a quad, the shared corners a little apart
+		let mut g = BufferGeometry::new();
+		g.set_attribute("position", BufferAttribute::new(vec![
+			0.0, 0.0, 0.0,
+			1.0, 0.0, 0.0,
+			1.0, 1.0, 0.0,
+			0.0, 0.0, 0.00001,
+			1.0, 1.0, 0.0,
+			0.0, 1.0, 0.0,
+		], 3, false));
+		g.add_group(0, 6, 2);
+
+		let merged = merge_vertices(&g, 1e-4);
+		assert_eq!(merged.get_attribute("position").unwrap().get_count(), 4);
+		assert_eq!(merged.get_index().unwrap().array, vec![ 0, 1, 2, 0, 2, 3 ]);
+		assert_eq!(merged.get_groups(), g.get_groups());
+
+		// a differing attribute keeps vertices apart
+		g.set_attribute("uv", BufferAttribute::new(vec![ 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.5, 0.5, 1.0, 1.0, 0.0, 1.0 ], 2, false));
+		let merged = merge_vertices(&g, 1e-4);
+		assert_eq!(merged.get_attribute("position").unwrap().get_count(), 5);
+		assert_eq!(merged.get_index().unwrap().array, vec![ 0, 1, 2, 3, 2, 4 ]);
+
+		// with a zero tolerance only exact matches are welded
+		g.delete_attribute("uv");
+		let merged = merge_vertices(&g, 0.0);
+		assert_eq!(merged.get_attribute("position").unwrap().get_count(), 5);
+	}
+
+	#[test]
+	fn strips_and_fans_to_triangles() {
+		let mut g = BufferGeometry::new();
+		g.set_attribute("position", BufferAttribute::new(vec![ 0.0f32; 15 ], 3, false));
+		g.add_group(0, 5, 0);
+
+		let strip = to_triangles_draw_mode(&g, TrianglesDrawMode::TriangleStrip);
+		assert_eq!(strip.get_index().unwrap().array, vec![ 0, 1, 2, 3, 2, 1, 2, 3, 4 ]);
+		assert!(strip.get_groups().is_empty());
+
+		let fan = to_triangles_draw_mode(&g, TrianglesDrawMode::TriangleFan);
+		assert_eq!(fan.get_index().unwrap().array, vec![ 0, 1, 2, 0, 2, 3, 0, 3, 4 ]);
+
+		// an index is followed, not the vertex order
+		g.set_index(Some(BufferAttribute::new(vec![ 4, 3, 2, 1 ], 1, false)));
+		let strip = to_triangles_draw_mode(&g, TrianglesDrawMode::TriangleStrip);
+		assert_eq!(strip.get_index().unwrap().array, vec![ 4, 3, 2, 1, 2, 3 ]);
+
+		let triangles = to_triangles_draw_mode(&g, TrianglesDrawMode::Triangles);
+		assert_eq!(triangles.get_index().unwrap().array, vec![ 4, 3, 2, 1 ]);
+		assert_eq!(triangles.get_groups().len(), 1);
+	}
+
+	#[test]
+	fn merged_attributes_are_plain_f32() {
+		let mut g = BufferGeometry::new();
+		g.set_attribute("position", BufferAttribute::new(vec![ 0.0f32; 9 ], 3, false));
+		g.set_attribute("color", BufferAttribute::new(vec![ 255u8, 0, 0, 0, 255, 0, 0, 0, 255 ], 3, true));
+
+		for merged in &[ merge_geometries(&[ &g, &g ], false).unwrap(), merge_vertices(&g, 0.0) ] {
+			let color = merged.get_attribute("color").unwrap();
+			assert!(!color.is_normalized());
+			assert_eq!(color.get_x(0), 1.0);
+			assert_eq!(color.get_z(2), 1.0);
+		}
+	}
+}
@@ -0,0 +1 @@
+pub mod buffer_geometry_utils;
\ No newline at end of file